@@ -21,6 +21,11 @@ cargo test --features "macros async_std localpool"
 cargo test --features "macros async_std juliex localpool"
 cargo test --features "macros juliex localpool"
 cargo test --features "macros threadpool localpool"
+cargo test --features static_exec
+
+# The no_std build. The library is no_std, the test binary still uses std.
+#
+cargo test --no-default-features --features static_exec --test static_exec
 
 cargo run --example localpool  --features "macros localpool"
 cargo run --example juliex     --features "macros juliex"
@@ -39,8 +44,8 @@ cargo doc --no-deps --all-features
 # --no-default-features is needed to turn of notwasm so this won't try to compile examples
 # features don't work in wasm-pack, so using cargo test directly here
 #
-cargo test --target wasm32-unknown-unknown --no-default-features
-cargo test --target wasm32-unknown-unknown --no-default-features --features macros
+cargo test --target wasm32-unknown-unknown --no-default-features --features std
+cargo test --target wasm32-unknown-unknown --no-default-features --features "std macros"
 cargo test --target wasm32-unknown-unknown --no-default-features --features bindgen
 cargo test --target wasm32-unknown-unknown --no-default-features --features "bindgen macros"
 
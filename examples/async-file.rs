@@ -1,4 +1,4 @@
-//! This example shows how to use async file io.
+//! This example shows how to do file io without blocking the executor.
 //!
 //! run with `cargo run --example async-file --features macros localpool`
 //
@@ -6,7 +6,7 @@ use
 {
 	async_runtime as rt,
 
-	async_std_crate :: { fs::File, io::Error } ,
+	std :: { fs::File, io::Error } ,
 };
 
 // You can create an async main fn like this. You can also choose `thread_pool` over `local`
@@ -15,9 +15,13 @@ use
 //
 async fn main() -> Result< (), Error >
 {
-	let file = File::open( "Cargo.yml" ).await?;
+	// Std file io is blocking, so we run it on the blocking pool.
+	//
+	let meta = rt::spawn_blocking( ||
+	{
+		File::open( "Cargo.yml" )?.metadata()
 
-	let meta = file.metadata().await?;
+	}).await.expect( "join blocking task" )?;
 
 	println!( "length of Cargo.yml is {}", meta.len() );
 
@@ -87,7 +87,7 @@ pub fn tokio_ct( _args: TokenStream, item: TokenStream ) -> TokenStream
 		//
 		#vis fn #name( #args ) #ret
 		{
-			async_runtime::init_allow_same( async_runtime::Config::TokioCt ).expect( "no double executor init" );
+			async_runtime::init_allow_same( async_runtime::Config::TokioCt( Default::default() ) ).expect( "no double executor init" );
 
 			let body = async move #body ;
 
@@ -102,6 +102,43 @@ pub fn tokio_ct( _args: TokenStream, item: TokenStream ) -> TokenStream
 
 
 
+#[ proc_macro_attribute ]
+//
+pub fn tokio_local( _args: TokenStream, item: TokenStream ) -> TokenStream
+{
+	let input = match parse( item )
+	{
+		Ok (i) => i                                  ,
+		Err(e) => return e.to_compile_error().into() ,
+	};
+
+
+	let vis   = &input.vis        ;
+	let name  = &input.sig.ident  ;
+	let args  = &input.sig.inputs ;
+	let ret   = &input.sig.output ;
+	let body  = &input.block      ;
+	let attrs = &input.attrs      ;
+
+	let tokens = quote!
+	{
+		#( #attrs )*
+		//
+		#vis fn #name( #args ) #ret
+		{
+			async_runtime::init_allow_same( async_runtime::Config::TokioLocal ).expect( "no double executor init" );
+
+			let body = async move #body ;
+
+			async_runtime::tokio_local::run_until( body ).expect( "TokioLocal executor" )
+		}
+	};
+
+	tokens.into()
+}
+
+
+
 #[ proc_macro_attribute ]
 //
 pub fn juliex( _args: TokenStream, item: TokenStream ) -> TokenStream
@@ -126,7 +163,7 @@ pub fn juliex( _args: TokenStream, item: TokenStream ) -> TokenStream
 		//
 		#vis fn #name( #args ) #ret
 		{
-			async_runtime::init_allow_same( async_runtime::Config::Juliex ).expect( "no double executor init" );
+			async_runtime::init_allow_same( async_runtime::Config::Juliex( Default::default() ) ).expect( "no double executor init" );
 
 			async_runtime::block_on( async move #body )
 		}
@@ -161,7 +198,7 @@ pub fn threadpool( _args: TokenStream, item: TokenStream ) -> TokenStream
 		//
 		#vis fn #name( #args ) #ret
 		{
-			async_runtime::init_allow_same( async_runtime::Config::ThreadPool ).expect( "no double executor init" );
+			async_runtime::init_allow_same( async_runtime::Config::ThreadPool( Default::default() ) ).expect( "no double executor init" );
 
 			async_runtime::block_on( async move #body )
 		}
@@ -172,6 +209,78 @@ pub fn threadpool( _args: TokenStream, item: TokenStream ) -> TokenStream
 
 
 
+#[ proc_macro_attribute ]
+//
+pub fn smol( _args: TokenStream, item: TokenStream ) -> TokenStream
+{
+	let input = match parse( item )
+	{
+		Ok (i) => i                                  ,
+		Err(e) => return e.to_compile_error().into() ,
+	};
+
+
+	let vis   = &input.vis        ;
+	let name  = &input.sig.ident  ;
+	let args  = &input.sig.inputs ;
+	let ret   = &input.sig.output ;
+	let body  = &input.block      ;
+	let attrs = &input.attrs      ;
+
+	let tokens = quote!
+	{
+		#(#attrs)*
+		//
+		#vis fn #name( #args ) #ret
+		{
+			async_runtime::init_allow_same( async_runtime::Config::Smol( Default::default() ) ).expect( "no double executor init" );
+
+			async_runtime::block_on( async move #body )
+		}
+	};
+
+	tokens.into()
+}
+
+
+
+#[ proc_macro_attribute ]
+//
+pub fn smol_local( _args: TokenStream, item: TokenStream ) -> TokenStream
+{
+	let input = match parse( item )
+	{
+		Ok (i) => i                                  ,
+		Err(e) => return e.to_compile_error().into() ,
+	};
+
+
+	let vis   = &input.vis        ;
+	let name  = &input.sig.ident  ;
+	let args  = &input.sig.inputs ;
+	let ret   = &input.sig.output ;
+	let body  = &input.block      ;
+	let attrs = &input.attrs      ;
+
+	let tokens = quote!
+	{
+		#( #attrs )*
+		//
+		#vis fn #name( #args ) #ret
+		{
+			async_runtime::init_allow_same( async_runtime::Config::SmolLocal ).expect( "no double executor init" );
+
+			let body = async move #body ;
+
+			async_runtime::smol_local::run_until( body ).expect( "SmolLocal executor" )
+		}
+	};
+
+	tokens.into()
+}
+
+
+
 #[ proc_macro_attribute ]
 //
 pub fn async_std( _args: TokenStream, item: TokenStream ) -> TokenStream
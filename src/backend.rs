@@ -0,0 +1,143 @@
+use crate::{ import::*, Error, ErrorKind };
+
+
+/// An executor that is not built into async_runtime. Implement this for your own executor and install it for the
+/// current thread with [`init_custom`](crate::init_custom). After that, calls to [`spawn`](crate::spawn) and friends,
+/// including the ones made by libraries, land on your executor.
+///
+/// The methods take boxed futures, so the trait can be used as a trait object. [`spawn_handle`](crate::spawn_handle)
+/// and [`spawn_handle_local`](crate::spawn_handle_local) are built on top of [`Backend::spawn`] and
+/// [`Backend::spawn_local`], so you get the same [`JoinHandle`](crate::JoinHandle) as on the other executors.
+///
+/// The backend has to be `Send` and `Sync`, because the configuration of a thread can be sent to other threads,
+/// eg. with [`current_rt`](crate::current_rt). A single threaded backend can keep it's executor in a thread local.
+///
+/// If your executor runs tasks on worker threads, install a clone of the [`CustomBackend`](crate::CustomBackend)
+/// on them with [`init_with`](crate::init_with), so tasks that spawn end up on the same executor.
+///
+/// ### Example
+///
+/// ```
+/// use
+/// {
+///    async_runtime as rt,
+///    futures::{ executor::block_on, task::FutureObj },
+///    std::thread,
+/// };
+///
+/// // Runs every task on it's own thread.
+/// //
+/// #[ derive( Debug ) ]
+/// //
+/// struct ThreadPerTask;
+///
+/// impl rt::Backend for ThreadPerTask
+/// {
+///    fn name( &self ) -> &'static str { "ThreadPerTask" }
+///
+///    fn capabilities( &self ) -> rt::Capabilities { rt::Capabilities::default() }
+///
+///    fn spawn( &self, fut: FutureObj<'static, ()> ) -> Result< (), rt::Error >
+///    {
+///       thread::spawn( move || block_on( fut ) );
+///       Ok(())
+///    }
+/// }
+///
+/// rt::init_custom( ThreadPerTask ).expect( "no double executor init" );
+///
+/// let handle = rt::spawn_handle( async { 5 } ).expect( "spawn" );
+///
+/// assert_eq!( 5, block_on( handle ).expect( "join task" ) );
+/// ```
+//
+pub trait Backend: fmt::Debug + Send + Sync + 'static
+{
+	/// The name of the backend. It is used in metrics and tracing spans. Defaults to `"Custom"`.
+	//
+	fn name( &self ) -> &'static str { "Custom" }
+
+	/// What this backend supports beyond spawning `Send` futures. async_runtime returns the appropriate error
+	/// without calling the backend for operations it does not support.
+	//
+	fn capabilities( &self ) -> Capabilities;
+
+	/// Spawn a future.
+	///
+	/// ### Errors
+	///
+	/// Return [`ErrorKind::Spawn`] or [`ErrorKind::ExecutorShutdown`] if your executor fails to spawn.
+	//
+	fn spawn( &self, fut: FutureObj<'static, ()> ) -> Result< (), Error >;
+
+	/// Spawn a `!Send` future on the current thread. Only called when [`Capabilities::spawn_local`] is set.
+	///
+	/// ### Errors
+	///
+	/// The default implementation returns [`ErrorKind::SpawnLocalOnThreadPool`].
+	//
+	fn spawn_local( &self, _fut: LocalFutureObj<'static, ()> ) -> Result< (), Error >
+	{
+		Err( ErrorKind::SpawnLocalOnThreadPool.into() )
+	}
+
+	/// Stop accepting new tasks and wait until the tasks in flight finish or the timeout expires. Returns true
+	/// if all tasks finished in time. Only called when [`Capabilities::shutdown`] is set. See
+	/// [`shutdown`](crate::shutdown).
+	///
+	/// ### Errors
+	///
+	/// The default implementation returns [`ErrorKind::WrongExecutor`].
+	//
+	fn shutdown( &self, _timeout: Duration ) -> Result< bool, Error >
+	{
+		Err( ErrorKind::WrongExecutor.into() )
+	}
+}
+
+
+
+/// What a [`Backend`] supports beyond spawning `Send` futures. Everything is off by default.
+///
+/// ```
+/// use async_runtime as rt;
+///
+/// let caps = rt::Capabilities::default().spawn_local( true );
+///
+/// assert!(  caps.spawn_local );
+/// assert!( !caps.shutdown    );
+/// ```
+//
+#[ derive( Debug, Default, Clone, Copy, PartialEq, Eq, Hash ) ]
+//
+pub struct Capabilities
+{
+	/// The backend can spawn `!Send` futures on the current thread.
+	//
+	pub spawn_local: bool,
+
+	/// The backend can be shut down with [`shutdown`](crate::shutdown).
+	//
+	pub shutdown: bool,
+}
+
+
+impl Capabilities
+{
+	/// Set whether the backend can spawn `!Send` futures.
+	//
+	pub fn spawn_local( mut self, spawn_local: bool ) -> Self
+	{
+		self.spawn_local = spawn_local;
+		self
+	}
+
+
+	/// Set whether the backend can be shut down.
+	//
+	pub fn shutdown( mut self, shutdown: bool ) -> Self
+	{
+		self.shutdown = shutdown;
+		self
+	}
+}
@@ -0,0 +1,171 @@
+use crate :: { import::*, JoinHandle };
+
+
+// The maximum number of threads in the blocking pool. When all of them are busy, jobs are queued.
+//
+const MAX_THREADS: usize = 512;
+
+// How long an idle thread waits for a new job before it exits.
+//
+const KEEP_ALIVE: Duration = Duration::from_secs( 10 );
+
+
+static POOL: SyncLazy< BlockingPool > = SyncLazy::new( BlockingPool::default );
+
+
+type Job = Box< dyn FnOnce() + Send + 'static >;
+
+
+
+/// Run a blocking closure on a dedicated thread pool and get a [`JoinHandle`] to it's output.
+///
+/// Use this for work that would otherwise block the executor, like file I/O, compression or FFI calls.
+/// The pool is independent of the executor of the current thread, so it works whatever you passed to
+/// [`init`](crate::init), even on a thread without executor.
+///
+/// The pool starts new threads as needed, up to 512. After that, closures are queued until a thread
+/// becomes available. Threads that stay idle for 10 seconds exit.
+///
+/// If the closure panics, awaiting the handle returns a [`JoinError`](crate::JoinError). Dropping the
+/// handle or calling [`JoinHandle::abort`] only prevents the closure from running if it hasn't started yet,
+/// a closure that is running can't be interrupted.
+///
+/// If the operating system refuses to start a thread while the pool has none, the closure is dropped without
+/// running and awaiting the handle returns a cancelled [`JoinError`](crate::JoinError).
+///
+/// **Note:** This function is not available on WASM, since it has no threads.
+///
+/// ### Example
+#[cfg_attr(feature = "localpool", doc = r##"
+```
+use async_runtime as rt;
+
+rt::init( rt::Config::LocalPool ).expect( "no double executor init" );
+
+let program = async
+{
+   let meta = rt::spawn_blocking( || std::fs::metadata( "Cargo.yml" ) ).await.expect( "join task" );
+
+   assert!( meta.expect( "read metadata" ).len() > 0 );
+};
+
+rt::localpool::run_until( program ).expect( "run localpool" );
+```
+"##)]
+//
+pub fn spawn_blocking<T, F>( f: F ) -> JoinHandle<T>
+
+	where F: FnOnce() -> T + Send + 'static,
+	      T: Send + 'static,
+{
+	let (task, handle) = JoinHandle::new( async move { f() } );
+
+	// The task does not await anything except for the closure, so block_on just runs it to completion.
+	// It takes care of catching panics and of not running the closure if the handle was aborted.
+	//
+	POOL.execute( Box::new( move || futures::executor::block_on( task ) ) );
+
+	handle
+}
+
+
+
+#[ derive( Default ) ]
+//
+struct BlockingPool
+{
+	state: Mutex< State > ,
+	cond : Condvar        ,
+}
+
+
+#[ derive( Default ) ]
+//
+struct State
+{
+	queue  : VecDeque<Job> ,
+	threads: usize         ,
+	idle   : usize         ,
+	wakeups: usize         ,
+}
+
+
+
+impl BlockingPool
+{
+	fn execute( &'static self, job: Job )
+	{
+		let mut state = self.state.lock().expect( "lock blocking pool" );
+
+		state.queue.push_back( job );
+
+		// Only count on idle threads that haven't been woken up for another job yet.
+		//
+		if state.idle > state.wakeups
+		{
+			state.wakeups += 1;
+			self.cond.notify_one();
+		}
+
+		else if state.threads < MAX_THREADS
+		{
+			state.threads += 1;
+
+			let spawned = thread::Builder::new()
+
+				.name( "async_runtime_blocking".to_string() )
+				.spawn( move || self.run() )
+			;
+
+			if spawned.is_err()
+			{
+				state.threads -= 1;
+
+				// If there are other threads, the job stays queued and will be picked up by one of them.
+				// Otherwise nobody would run it, so we drop it and the handle resolves as cancelled.
+				//
+				if state.threads == 0
+				{
+					let job = state.queue.pop_back();
+
+					drop( state );
+					drop( job   );
+				}
+			}
+		}
+	}
+
+
+	// The loop of a worker thread.
+	//
+	fn run( &self )
+	{
+		let mut state = self.state.lock().expect( "lock blocking pool" );
+
+		loop
+		{
+			if let Some( job ) = state.queue.pop_front()
+			{
+				drop( state );
+				job();
+				state = self.state.lock().expect( "lock blocking pool" );
+
+				continue;
+			}
+
+			state.idle += 1;
+
+			let (new_state, wait) = self.cond.wait_timeout( state, KEEP_ALIVE ).expect( "lock blocking pool" );
+
+			state          = new_state;
+			state.idle    -= 1;
+			state.wakeups  = state.wakeups.saturating_sub( 1 );
+
+			if wait.timed_out() && state.queue.is_empty()
+			{
+				state.threads -= 1;
+				return;
+			}
+		}
+	}
+}
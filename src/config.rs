@@ -1,6 +1,23 @@
+use crate::CustomBackend;
+
+#[ cfg( feature = "tokio_ct" ) ] use crate::import::*;
+
+#[ cfg( feature = "tokio_ct" ) ] use crate :: { TokioClock } ;
+
+#[ cfg(any( feature = "threadpool", feature = "tokio_tp", feature = "smol" )) ] use core::num::NonZeroUsize;
+
+
 /// The configuration for wich executor that should be used on this thread.
+///
+/// Some executors take extra configuration. Two configurations are only considered equal if all of their
+/// options are equal. This matters for [`init_allow_same`](crate::init_allow_same) and
+/// [`current_rt`](crate::current_rt).
 //
-#[ derive( Debug, Copy, Clone, Hash, PartialEq, Eq ) ]
+// The threadpool config is bigger than the others.
+//
+#[ allow( variant_size_differences ) ]
+//
+#[ derive( Debug, Clone, Hash, PartialEq, Eq ) ]
 //
 pub enum Config
 {
@@ -8,13 +25,13 @@ pub enum Config
 	//
 	#[ cfg( feature = "threadpool" ) ]
 	//
-	ThreadPool,
+	ThreadPool( ThreadPoolConfig ),
 
 	/// A threadpool executor from the juliex crate.
 	//
 	#[ cfg( feature = "juliex" ) ]
 	//
-	Juliex,
+	Juliex( JuliexConfig ),
 
 	/// A threadpool executor from the async-std crate.
 	//
@@ -22,6 +39,13 @@ pub enum Config
 	//
 	AsyncStd,
 
+	/// A threadpool running an `async_executor::Executor`, the executor from smol. The worker threads drive the
+	/// async-io reactor.
+	//
+	#[ cfg( feature = "smol" ) ]
+	//
+	Smol( SmolConfig ),
+
 	/// An executor that runs futures on the current thread, capable of running `!`[`Send`] futures. Uses
 	/// `futures::executor::LocalPool`.
 	//
@@ -29,12 +53,45 @@ pub enum Config
 	//
 	LocalPool,
 
+	/// An executor that runs futures on the current thread, capable of running `!`[`Send`] futures. Uses
+	/// `async_executor::LocalExecutor`, the local executor from smol. See [`smol_local`](crate::smol_local).
+	//
+	#[ cfg( feature = "smol" ) ]
+	//
+	SmolLocal,
+
+	/// An executor that runs futures on the current thread, capable of running `!`[`Send`] futures. It polls the
+	/// tasks that are ready in a pseudo-random order determined by the seed. See [`deterministic`](crate::deterministic).
+	//
+	#[ cfg( feature = "deterministic" ) ]
+	//
+	Deterministic
+	{
+		/// The seed for the order in which tasks are polled.
+		//
+		seed: u64
+	},
+
 	/// An executor that runs futures on the current thread, capable of running `!`[`Send`] futures. Uses
 	/// `tokio::runtime::current_thread::Runtime`.
 	//
 	#[ cfg( feature = "tokio_ct" ) ]
 	//
-	TokioCt,
+	TokioCt( TokioCtConfig ),
+
+	/// An executor that runs futures on the current thread, capable of running `!`[`Send`] futures. Uses the tokio
+	/// 1.x current thread runtime with a `tokio::task::LocalSet`. See [`tokio_local`](crate::tokio_local).
+	//
+	#[ cfg( feature = "tokio_local" ) ]
+	//
+	TokioLocal,
+
+	/// A threadpool executor from tokio. Uses `tokio::runtime::Runtime`, so tasks can use the tokio reactor
+	/// and timers.
+	//
+	#[ cfg( feature = "tokio_tp" ) ]
+	//
+	TokioTp( TokioTpConfig ),
 
 	/// An executor that uses wasm-bindgen-futures under the hood. This is the only executor available on wasm
 	/// at the moment. It is also only available on the wasm32-unknown-unknown target.
@@ -43,7 +100,303 @@ pub enum Config
 	//
 	Bindgen,
 
+	/// A single executor for the whole program that only needs `alloc`. Tasks can be spawned and woken from
+	/// interrupt handlers. It can not spawn `!`[`Send`] futures. See [`static_exec`](crate::static_exec).
+	//
+	#[ cfg( feature = "static_exec" ) ]
+	//
+	Static,
+
+	/// An executor you plugged in by implementing [`Backend`](crate::Backend). It is only equal to a clone of the
+	/// same [`CustomBackend`](crate::CustomBackend). See [`init_custom`](crate::init_custom).
+	//
+	Custom( CustomBackend ),
+
 	/// Protect against adding other options being breaking changes.
 	//
 	__Nonexhaustive,
 }
+
+
+
+impl Config
+{
+	// Whether the executor can be spawned on from any thread. Custom backends that can spawn `!Send` futures are
+	// assumed to be tied to their thread. See `init_global`.
+	//
+	#[ cfg( feature = "std" ) ]
+	//
+	pub(crate) fn is_shared( &self ) -> bool
+	{
+		match self
+		{
+			#[ cfg( feature = "threadpool" ) ] Self::ThreadPool(_) => true,
+			#[ cfg( feature = "juliex"     ) ] Self::Juliex    (_) => true,
+			#[ cfg( feature = "async_std"  ) ] Self::AsyncStd      => true,
+			#[ cfg( feature = "smol"       ) ] Self::Smol      (_) => true,
+			#[ cfg( feature = "tokio_tp"   ) ] Self::TokioTp   (_) => true,
+			#[ cfg( feature = "static_exec") ] Self::Static        => true,
+
+			Self::Custom(c) => !c.capabilities().spawn_local,
+
+			_ => false,
+		}
+	}
+}
+
+
+
+/// The options for the executors that run their own pool of worker threads. Use it through
+/// `ThreadPoolConfig`, `TokioTpConfig` or `SmolConfig`, depending on which of them are enabled.
+//
+#[ cfg(any( feature = "threadpool", feature = "tokio_tp", feature = "smol" )) ]
+//
+#[ derive( Debug, Default, Clone, Hash, PartialEq, Eq ) ]
+//
+pub struct PoolConfig
+{
+	/// The number of worker threads. When `None`, this is the number of cpus on the system.
+	//
+	pub workers: Option<NonZeroUsize>,
+
+	/// The prefix for the names of the worker threads. Threads will be named `name_prefix` followed by
+	/// their index. When `None`, the prefix names the executor, eg. `async_runtime_threadpool_worker`.
+	//
+	pub name_prefix: Option<String>,
+
+	/// The stack size in bytes for the worker threads. When `None`, the default of the executor is used.
+	//
+	pub stack_size: Option<usize>,
+}
+
+
+#[ cfg(any( feature = "threadpool", feature = "tokio_tp", feature = "smol" )) ]
+//
+impl PoolConfig
+{
+	/// Set the number of worker threads. A pool without workers would never run it's tasks, so this takes a
+	/// [`NonZeroUsize`]:
+	///
+	/// ```compile_fail
+	/// async_runtime::PoolConfig::default().workers( 0 );
+	/// ```
+	//
+	pub fn workers( mut self, workers: NonZeroUsize ) -> Self
+	{
+		self.workers = Some( workers );
+		self
+	}
+
+
+	/// Set the prefix for the names of the worker threads.
+	//
+	pub fn name_prefix( mut self, name_prefix: impl Into<String> ) -> Self
+	{
+		self.name_prefix = Some( name_prefix.into() );
+		self
+	}
+
+
+	/// Set the stack size in bytes for the worker threads.
+	//
+	pub fn stack_size( mut self, stack_size: usize ) -> Self
+	{
+		self.stack_size = Some( stack_size );
+		self
+	}
+
+
+	// The prefix for the names of the worker threads, or the given default.
+	//
+	pub(crate) fn name_prefix_or<'a>( &'a self, default: &'a str ) -> &'a str
+	{
+		self.name_prefix.as_deref().unwrap_or( default )
+	}
+}
+
+
+
+/// Configuration for the futures [`ThreadPool`](https://docs.rs/futures/0.3/futures/executor/struct.ThreadPool.html).
+/// The worker threads are named `async_runtime_threadpool_worker` by default.
+///
+/// All threads that initialize with an equal configuration share the same threadpool. If you init with a
+/// different configuration, a new threadpool is created.
+///
+/// ### Example
+#[ cfg_attr( feature = "threadpool", doc = r##"
+```
+use { async_runtime as rt, std::num::NonZeroUsize };
+
+let config = rt::ThreadPoolConfig::default()
+
+   .workers    ( NonZeroUsize::new( 4 ).expect( "non zero" ) )
+   .name_prefix( "my_app_worker"                             )
+;
+
+rt::init( rt::Config::ThreadPool( config ) ).expect( "no double executor init" );
+```
+"##)]
+//
+#[ cfg( feature = "threadpool" ) ]
+//
+pub type ThreadPoolConfig = PoolConfig;
+
+
+
+/// Configuration for the juliex threadpool.
+///
+/// Juliex can't be tuned. It always starts twice as many worker threads as there are cpus on the system, without
+/// names and with the default stack size, so this has no options. It exists so options can be added without
+/// breaking changes.
+//
+#[ cfg( feature = "juliex" ) ]
+//
+#[ derive( Debug, Default, Clone, Hash, PartialEq, Eq ) ]
+//
+pub struct JuliexConfig
+{
+	_private: (),
+}
+
+
+#[ cfg( feature = "juliex" ) ]
+//
+impl JuliexConfig
+{
+	/// Create a configuration with the default options.
+	//
+	pub fn new() -> Self
+	{
+		Self::default()
+	}
+}
+
+
+
+/// Configuration for the tokio current thread runtime.
+///
+/// The runtime runs on the thread that initializes it, so options like the number of workers don't apply. You
+/// can set the clock tokio uses for it's timers, which lets tests control time.
+///
+/// Two configurations with a clock are only equal if the clock was set on one of them and the other is a clone.
+///
+/// ### Example
+#[ cfg_attr( feature = "tokio_ct", doc = r##"
+```
+use async_runtime as rt;
+
+let config = rt::TokioCtConfig::default().clock( rt::tokio_ct::Clock::new() );
+
+rt::init( rt::Config::TokioCt( config ) ).expect( "no double executor init" );
+```
+"##)]
+//
+#[ cfg( feature = "tokio_ct" ) ]
+//
+#[ derive( Debug, Default, Clone ) ]
+//
+pub struct TokioCtConfig
+{
+	clock: Option< Arc<TokioClock> >,
+}
+
+
+#[ cfg( feature = "tokio_ct" ) ]
+//
+impl TokioCtConfig
+{
+	/// Create a configuration with the default options.
+	//
+	pub fn new() -> Self
+	{
+		Self::default()
+	}
+
+
+	/// Set the clock used by tokio's timers. When not set, tokio uses the system clock. See
+	/// [`tokio_ct::Clock`](crate::tokio_ct::Clock).
+	//
+	pub fn clock( mut self, clock: TokioClock ) -> Self
+	{
+		self.clock = Some( Arc::new( clock ) );
+		self
+	}
+
+
+	/// The clock used by tokio's timers, if one was set.
+	//
+	pub fn get_clock( &self ) -> Option<&TokioClock>
+	{
+		self.clock.as_deref()
+	}
+}
+
+
+// The clock doesn't implement `Eq`, so we compare the clock by identity.
+//
+#[ cfg( feature = "tokio_ct" ) ]
+//
+impl PartialEq for TokioCtConfig
+{
+	fn eq( &self, other: &Self ) -> bool
+	{
+		match ( &self.clock, &other.clock )
+		{
+			( None   , None    ) => true                  ,
+			( Some(a), Some(b) ) => Arc::ptr_eq( a, b )   ,
+			_                    => false                 ,
+		}
+	}
+}
+
+
+#[ cfg( feature = "tokio_ct" ) ] impl Eq for TokioCtConfig {}
+
+
+#[ cfg( feature = "tokio_ct" ) ]
+//
+impl Hash for TokioCtConfig
+{
+	fn hash<H: Hasher>( &self, state: &mut H )
+	{
+		self.clock.as_ref().map( Arc::as_ptr ).hash( state );
+	}
+}
+
+
+
+/// Configuration for the tokio threadpool runtime. The worker threads are named `async_runtime_tokio_worker` by
+/// default.
+///
+/// All threads that initialize with an equal configuration share the same runtime. If you init with a
+/// different configuration, a new runtime is created.
+///
+/// ### Example
+#[ cfg_attr( feature = "tokio_tp", doc = r##"
+```
+use { async_runtime as rt, std::num::NonZeroUsize };
+
+let config = rt::TokioTpConfig::default()
+
+   .workers    ( NonZeroUsize::new( 4 ).expect( "non zero" ) )
+   .name_prefix( "my_app_worker"                             )
+;
+
+rt::init( rt::Config::TokioTp( config ) ).expect( "no double executor init" );
+```
+"##)]
+//
+#[ cfg( feature = "tokio_tp" ) ]
+//
+pub type TokioTpConfig = PoolConfig;
+
+
+
+/// Configuration for the smol threadpool. The worker threads are named `async_runtime_smol_worker` by default.
+///
+/// All threads that initialize with an equal configuration share the same threadpool. If you init with a
+/// different configuration, a new threadpool is created.
+//
+#[ cfg( feature = "smol" ) ]
+//
+pub type SmolConfig = PoolConfig;
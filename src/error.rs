@@ -1,4 +1,4 @@
-use crate::{ import::* };
+use crate::{ import::*, JoinError, task::Task };
 
 
 /// The error type for errors happening in `async_runtime`.
@@ -7,9 +7,12 @@ use crate::{ import::* };
 //
 pub struct Error
 {
-	kind: ErrorKind,
+	kind: ErrorKind    ,
+	task: Option<Task> ,
 }
 
+#[ cfg( feature = "std" ) ]
+//
 impl StdError for Error {}
 
 
@@ -38,7 +41,7 @@ pub enum ErrorKind
 	Spawn,
 
 	/// An error happened when running an executor to completion.
-	/// This is returned by tokio_ct::run.
+	/// This is returned by tokio_ct::run, and by static_exec::run when the static executor is already running.
 	//
 	Run,
 
@@ -54,10 +57,41 @@ pub enum ErrorKind
 	WrongExecutor,
 
 	/// You tried to call a spawn function on a thread that has no executor initialized. Please use
-	/// [`init`](crate::init) first.
+	/// [`init`](crate::init) first, or set a default executor for all threads with [`init_global`](crate::init_global).
 	//
 	NoExecutorInitialized,
 
+	/// A task spawned with [`spawn_handle`](crate::spawn_handle) panicked or was cancelled. This is what a
+	/// [`JoinError`](crate::JoinError) converts into. The error keeps the task, so it's message tells you which
+	/// task failed. If you need the panic payload, use the [`JoinError`](crate::JoinError) directly.
+	//
+	Join,
+
+	/// The executor was shut down with [`shutdown`](crate::shutdown) and no longer accepts new tasks. This is
+	/// also returned when a task on the worker thread of an owned threadpool spawns after all clones of the pool
+	/// were dropped.
+	//
+	ExecutorShutdown,
+
+	/// The future passed to [`time::timeout`](crate::time::timeout) did not finish in time.
+	//
+	Timeout,
+
+	/// A task local was accessed outside of [`LocalKey::scope`](crate::task::LocalKey::scope).
+	//
+	TaskLocalNotSet,
+
+	/// You used a function of the virtual clock, like [`time::advance`](crate::time::advance), on a thread
+	/// where the clock is not paused. Call [`time::pause`](crate::time::pause) first.
+	//
+	ClockNotPaused,
+
+	/// You passed the configuration of an executor that only runs on it's own thread, like _localpool_, to
+	/// [`init_global`](crate::init_global). The global executor is used by threads that didn't call
+	/// [`init`](crate::init), so it has to be one that can be spawned on from any thread.
+	//
+	GlobalExecutorNotSend,
+
 	/// Protect against adding other options being breaking changes.
 	//
 	__Nonexhaustive,
@@ -82,6 +116,18 @@ impl fmt::Display for ErrorKind
 
 			Self::NoExecutorInitialized => fmt::Display::fmt( "You must initialize an executor on this thread before calls to spawn.", f ) ,
 
+			Self::Join => fmt::Display::fmt( "Join: The task panicked or was cancelled before it finished.", f ) ,
+
+			Self::ExecutorShutdown => fmt::Display::fmt( "ExecutorShutdown: The executor was shut down and no longer accepts new tasks.", f ) ,
+
+			Self::Timeout => fmt::Display::fmt( "Timeout: The future did not finish before the timeout expired.", f ) ,
+
+			Self::TaskLocalNotSet => fmt::Display::fmt( "TaskLocalNotSet: The task local is not set for the current task.", f ) ,
+
+			Self::ClockNotPaused => fmt::Display::fmt( "ClockNotPaused: The clock of this thread is not paused.", f ) ,
+
+			Self::GlobalExecutorNotSend => fmt::Display::fmt( "GlobalExecutorNotSend: The global executor must be one that can be spawned on from any thread, like a threadpool.", f ) ,
+
 			_ => unreachable!(),
 		}
 	}
@@ -92,7 +138,13 @@ impl fmt::Display for Error
 {
 	fn fmt( &self, f: &mut fmt::Formatter<'_> ) -> fmt::Result
 	{
-		write!( f, "async_runtime::Error: {}", &self.kind )
+		write!( f, "async_runtime::Error: {}", &self.kind )?;
+
+		match &self.task
+		{
+			Some(task) => write!( f, " ({})", task ),
+			None       => Ok(())                    ,
+		}
 	}
 }
 
@@ -103,7 +155,7 @@ impl Error
 	//
 	pub fn new( kind: ErrorKind ) -> Self
 	{
-		Error { kind }
+		Error { kind, task: None }
 	}
 
 
@@ -113,6 +165,15 @@ impl Error
 	{
 		&self.kind
 	}
+
+
+	/// The task that failed, for errors converted from a [`JoinError`](crate::JoinError). See
+	/// [`JoinError::task`](crate::JoinError::task).
+	//
+	pub fn task( &self ) -> Option<&Task>
+	{
+		self.task.as_ref()
+	}
 }
 
 
@@ -120,6 +181,15 @@ impl From<ErrorKind> for Error
 {
 	fn from( kind: ErrorKind ) -> Error
 	{
-		Error { kind }
+		Error { kind, task: None }
+	}
+}
+
+
+impl From<JoinError> for Error
+{
+	fn from( err: JoinError ) -> Error
+	{
+		Error { kind: ErrorKind::Join, task: err.task().cloned() }
 	}
 }
@@ -1,43 +1,89 @@
 use
 {
-	crate :: { import::*, Error } ,
+	crate :: { import::*, Error, ErrorKind, JoinHandle, task::{ self, Task } } ,
 	super :: { Config           } ,
 };
 
+#[ cfg( feature = "std" ) ] use crate::ExecutorHandle;
+
 
 
 #[ cfg( feature = "localpool"  ) ] pub mod localpool  ;
 #[ cfg( feature = "async_std"  ) ] pub mod async_std  ;
 #[ cfg( feature = "tokio_ct"   ) ] pub mod tokio_ct   ;
+#[ cfg( feature = "tokio_local") ] pub mod tokio_local;
+#[ cfg( feature = "smol"       ) ] pub mod smol_local ;
+#[ cfg( feature = "static_exec") ] pub mod static_exec;
+
+#[ cfg( feature = "deterministic" ) ] pub mod deterministic ;
 #[ cfg( feature = "bindgen"    ) ]     mod bindgen    ;
 #[ cfg( feature = "juliex"     ) ]     mod juliex     ;
 #[ cfg( feature = "threadpool" ) ]     mod threadpool ;
+#[ cfg( feature = "tokio_tp"   ) ]     mod tokio_tp   ;
+#[ cfg( feature = "smol"       ) ]     mod smol       ;
+                                       mod custom     ;
+
+#[ cfg(any( feature = "juliex", feature = "threadpool", feature = "tokio_tp", feature = "smol" )) ] mod shutdown;
+#[ cfg(any( feature = "juliex", feature = "threadpool", feature = "tokio_tp", feature = "smol" )) ] mod pool_ref;
 
 
-#[ cfg( feature = "async_std"  ) ] use async_std  :: AsyncStd   ;
-#[ cfg( feature = "bindgen"    ) ] use bindgen    :: Bindgen    ;
-#[ cfg( feature = "juliex"     ) ] use juliex     :: Juliex     ;
-#[ cfg( feature = "threadpool" ) ] use threadpool :: ThreadPool ;
-#[ cfg( feature = "localpool"  ) ] use localpool  :: LocalPool  ;
-#[ cfg( feature = "tokio_ct"   ) ] use tokio_ct   :: TokioCt    ;
+#[ cfg( feature = "async_std"  ) ] pub use async_std  :: AsyncStd   ;
+#[ cfg( feature = "bindgen"    ) ] pub use bindgen    :: Bindgen    ;
+#[ cfg( feature = "juliex"     ) ] pub use juliex     :: Juliex     ;
+#[ cfg( feature = "threadpool" ) ] pub use threadpool :: ThreadPool ;
+#[ cfg( feature = "localpool"  ) ] pub use localpool  :: LocalPool  ;
+#[ cfg( feature = "tokio_ct"   ) ] pub use tokio_ct   :: TokioCt    ;
+#[ cfg( feature = "tokio_local") ] pub use tokio_local:: TokioLocal ;
+#[ cfg( feature = "tokio_tp"   ) ] pub use tokio_tp   :: TokioTp    ;
+#[ cfg( feature = "smol"       ) ] pub use smol       :: Smol       ;
+#[ cfg( feature = "smol"       ) ] pub use smol_local :: SmolLocal  ;
+#[ cfg( feature = "static_exec") ] pub use static_exec:: Static     ;
 
+#[ cfg( feature = "deterministic" ) ] pub use deterministic :: Deterministic ;
 
-/// The different executors we support.
+pub use custom::CustomBackend;
+
+
+/// The different executors we support. You can create one from any of the executor types with `into`
+/// and install it on the current thread with [`init_with`](crate::init_with). Clones refer to the same executor.
+///
+/// More executors might be added, so you can't match on this exhaustively.
 //
 // The tokio_ct is 256 bytes bigger than the next largest variant.
 //
 #[ allow( variant_size_differences ) ]
+#[ derive( Debug, Clone ) ]
+#[ non_exhaustive ]
 //
-pub(crate) enum Executor
+pub enum Executor
 {
+	/// A threadpool from the juliex crate.
+	//
 	#[ cfg( feature = "juliex" ) ]
 	//
 	Juliex( Juliex ),
 
+	/// A threadpool from the futures crate.
+	//
 	#[ cfg( feature = "threadpool" ) ]
 	//
 	ThreadPool( ThreadPool ),
 
+	/// A threadpool running the executor from smol.
+	//
+	#[ cfg( feature = "smol" ) ]
+	//
+	Smol( Smol ),
+
+	/// An executor that runs futures on the current thread, capable of running `!`[`Send`] futures. Uses the
+	/// local executor from smol.
+	//
+	#[ cfg( feature = "smol" ) ]
+	//
+	SmolLocal( SmolLocal ),
+
+	/// The async-std executor.
+	//
 	#[ cfg( feature = "async_std" ) ]
 	//
 	AsyncStd( AsyncStd ),
@@ -56,6 +102,19 @@ pub(crate) enum Executor
 	//
 	TokioCt( TokioCt ),
 
+	/// An executor that runs futures on the current thread, capable of running `!`[`Send`] futures. Uses the
+	/// tokio 1.x current thread runtime with a `tokio::task::LocalSet`.
+	//
+	#[ cfg( feature = "tokio_local" ) ]
+	//
+	TokioLocal( TokioLocal ),
+
+	/// A threadpool from tokio. Uses `tokio::runtime::Runtime`.
+	//
+	#[ cfg( feature = "tokio_tp" ) ]
+	//
+	TokioTp( TokioTp ),
+
 	/// An executor that uses wasm-bindgen-futures under the hood. This is the only executor available on wasm
 	/// at the moment. It is also only available on the wasm32-unknown-unknown target.
 	//
@@ -63,9 +122,23 @@ pub(crate) enum Executor
 	//
 	Bindgen( Bindgen ),
 
-	/// Protect against adding other options being breaking changes.
+	/// The global executor for `no_std` programs. It only needs `alloc` and tasks can be spawned and woken from
+	/// interrupt handlers.
+	//
+	#[ cfg( feature = "static_exec" ) ]
+	//
+	Static( Static ),
+
+	/// An executor that runs futures on the current thread and polls the ready tasks in a pseudo-random order
+	/// determined by a seed.
+	//
+	#[ cfg( feature = "deterministic" ) ]
+	//
+	Deterministic( Deterministic ),
+
+	/// An executor you plugged in by implementing [`Backend`](crate::Backend).
 	//
-	__Nonexhaustive,
+	Custom( CustomBackend ),
 }
 
 
@@ -75,12 +148,21 @@ impl Executor
 	{
 		match config
 		{
-			#[ cfg( feature = "localpool"  ) ] Config::LocalPool  => Self::LocalPool ( LocalPool  ::new() ),
-			#[ cfg( feature = "async_std"  ) ] Config::AsyncStd   => Self::AsyncStd  ( AsyncStd   ::new() ),
-			#[ cfg( feature = "juliex"     ) ] Config::Juliex     => Self::Juliex    ( Juliex     ::new() ),
-			#[ cfg( feature = "threadpool" ) ] Config::ThreadPool => Self::ThreadPool( ThreadPool ::new() ),
-			#[ cfg( feature = "bindgen"    ) ] Config::Bindgen    => Self::Bindgen   ( Bindgen    ::new() ),
-			#[ cfg( feature = "tokio_ct"   ) ] Config::TokioCt    => Self::TokioCt   ( TokioCt    ::new() ),
+			#[ cfg( feature = "localpool"  ) ] Config::LocalPool     => Self::LocalPool ( LocalPool  ::new()      ),
+			#[ cfg( feature = "async_std"  ) ] Config::AsyncStd      => Self::AsyncStd  ( AsyncStd   ::new()      ),
+			#[ cfg( feature = "juliex"     ) ] Config::Juliex    (c) => Self::Juliex    ( Juliex     ::shared( c ) ),
+			#[ cfg( feature = "threadpool" ) ] Config::ThreadPool(c) => Self::ThreadPool( ThreadPool ::shared( c ) ),
+			#[ cfg( feature = "bindgen"    ) ] Config::Bindgen       => Self::Bindgen   ( Bindgen    ::new()      ),
+			#[ cfg( feature = "tokio_ct"   ) ] Config::TokioCt   (c) => Self::TokioCt   ( TokioCt    ::new( c )   ),
+			#[ cfg( feature = "tokio_local") ] Config::TokioLocal    => Self::TokioLocal( TokioLocal ::new()      ),
+			#[ cfg( feature = "tokio_tp"   ) ] Config::TokioTp   (c) => Self::TokioTp   ( TokioTp    ::shared( c ) ),
+			#[ cfg( feature = "smol"       ) ] Config::Smol      (c) => Self::Smol      ( Smol       ::shared( c ) ),
+			#[ cfg( feature = "smol"       ) ] Config::SmolLocal     => Self::SmolLocal ( SmolLocal  ::new()      ),
+			#[ cfg( feature = "static_exec") ] Config::Static        => Self::Static    ( Static     ::new()      ),
+
+			#[ cfg( feature = "deterministic" ) ] Config::Deterministic{ seed } => Self::Deterministic( Deterministic::new( seed ) ),
+
+			Config::Custom(c) => Self::Custom( c ),
 
 			_ => unreachable!(),
 		}
@@ -90,24 +172,149 @@ impl Executor
 	{
 		match self
 		{
-			#[ cfg( feature = "localpool"  ) ] Self::LocalPool (_) => Config::LocalPool  ,
-			#[ cfg( feature = "juliex"     ) ] Self::Juliex    (_) => Config::Juliex     ,
-			#[ cfg( feature = "threadpool" ) ] Self::ThreadPool(_) => Config::ThreadPool ,
-			#[ cfg( feature = "async_std"  ) ] Self::AsyncStd  (_) => Config::AsyncStd   ,
-			#[ cfg( feature = "bindgen"    ) ] Self::Bindgen   (_) => Config::Bindgen    ,
-			#[ cfg( feature = "tokio_ct"   ) ] Self::TokioCt   (_) => Config::TokioCt    ,
+			#[ cfg( feature = "localpool"  ) ] Self::LocalPool (_) => Config::LocalPool                    ,
+			#[ cfg( feature = "juliex"     ) ] Self::Juliex    (e) => Config::Juliex    ( e.config().clone() ),
+			#[ cfg( feature = "threadpool" ) ] Self::ThreadPool(e) => Config::ThreadPool( e.config().clone() ),
+			#[ cfg( feature = "async_std"  ) ] Self::AsyncStd  (_) => Config::AsyncStd                     ,
+			#[ cfg( feature = "bindgen"    ) ] Self::Bindgen   (_) => Config::Bindgen                      ,
+			#[ cfg( feature = "tokio_ct"   ) ] Self::TokioCt   (e) => Config::TokioCt   ( e.config().clone() ),
+			#[ cfg( feature = "tokio_local") ] Self::TokioLocal(_) => Config::TokioLocal                   ,
+			#[ cfg( feature = "tokio_tp"   ) ] Self::TokioTp   (e) => Config::TokioTp   ( e.config().clone() ),
+			#[ cfg( feature = "smol"       ) ] Self::Smol      (e) => Config::Smol      ( e.config().clone() ),
+			#[ cfg( feature = "smol"       ) ] Self::SmolLocal (_) => Config::SmolLocal                    ,
+			#[ cfg( feature = "static_exec") ] Self::Static    (_) => Config::Static                       ,
+
+			#[ cfg( feature = "deterministic" ) ] Self::Deterministic(e) => e.config(),
+
+			Self::Custom(e) => Config::Custom( e.clone() ),
+		}
+	}
 
-			_ => unreachable!(),
+
+	// The name of the Config variant, to report metrics and in tracing spans.
+	//
+	#[ cfg(any( feature = "metrics", feature = "tracing" )) ]
+	//
+	pub(crate) fn name( &self ) -> &'static str
+	{
+		match self
+		{
+			#[ cfg( feature = "localpool"  ) ] Self::LocalPool (_) => "LocalPool"  ,
+			#[ cfg( feature = "juliex"     ) ] Self::Juliex    (_) => "Juliex"     ,
+			#[ cfg( feature = "threadpool" ) ] Self::ThreadPool(_) => "ThreadPool" ,
+			#[ cfg( feature = "async_std"  ) ] Self::AsyncStd  (_) => "AsyncStd"   ,
+			#[ cfg( feature = "bindgen"    ) ] Self::Bindgen   (_) => "Bindgen"    ,
+			#[ cfg( feature = "tokio_ct"   ) ] Self::TokioCt   (_) => "TokioCt"    ,
+			#[ cfg( feature = "tokio_local") ] Self::TokioLocal(_) => "TokioLocal" ,
+			#[ cfg( feature = "tokio_tp"   ) ] Self::TokioTp   (_) => "TokioTp"    ,
+			#[ cfg( feature = "smol"       ) ] Self::Smol      (_) => "Smol"       ,
+			#[ cfg( feature = "smol"       ) ] Self::SmolLocal (_) => "SmolLocal"  ,
+			#[ cfg( feature = "static_exec") ] Self::Static    (_) => "Static"     ,
+
+			#[ cfg( feature = "deterministic" ) ] Self::Deterministic(_) => "Deterministic",
+
+			Self::Custom(e) => e.name(),
 		}
 	}
 
 
-	// For the case where we compile without an executor enabled, the fut variable will be unused.
+	// Only the threadpools and custom backends that support it can be shut down.
 	//
-	#[ allow( unused_variables ) ]
+	pub(crate) fn shutdown( &self, timeout: Duration ) -> Result< bool, Error >
+	{
+		match self
+		{
+			#[ cfg( feature = "juliex"     ) ] Self::Juliex    (e) => Ok( e.shutdown( timeout ) ),
+			#[ cfg( feature = "threadpool" ) ] Self::ThreadPool(e) => Ok( e.shutdown( timeout ) ),
+			#[ cfg( feature = "tokio_tp"   ) ] Self::TokioTp   (e) => Ok( e.shutdown( timeout ) ),
+			#[ cfg( feature = "smol"       ) ] Self::Smol      (e) => Ok( e.shutdown( timeout ) ),
+
+			Self::Custom(e) => e.shutdown( timeout ),
+
+			// When only threadpools are enabled, there are no other executors.
+			//
+			#[ allow( unreachable_patterns ) ]
+			//
+			_ => Err( ErrorKind::WrongExecutor.into() ),
+		}
+	}
+
+
+	// Run single threaded executors until all their tasks are finished. Threadpools run on their own, so this
+	// returns right away. Custom backends are not run either, we don't know how.
 	//
-	pub(crate) fn spawn( &self, fut: impl Future< Output = () > + 'static + Send ) -> Result< (), Error >
+	#[ cfg(all( feature = "std", not( target_arch = "wasm32" ) )) ]
+	//
+	pub(crate) fn run( &self ) -> Result< (), Error >
 	{
+		match self
+		{
+			#[ cfg( feature = "localpool"  ) ] Self::LocalPool (e) => { e.run(); Ok(()) },
+			#[ cfg( feature = "tokio_ct"   ) ] Self::TokioCt   (e) => e.run()            ,
+			#[ cfg( feature = "tokio_local") ] Self::TokioLocal(e) => { e.run(); Ok(()) },
+			#[ cfg( feature = "smol"       ) ] Self::SmolLocal (e) => { e.run(); Ok(()) },
+
+			#[ cfg( feature = "deterministic" ) ] Self::Deterministic(e) => { e.run(); Ok(()) },
+
+			// If another thread is already running the static executor, it will run our tasks.
+			//
+			#[ cfg( feature = "static_exec") ] Self::Static(e) => match e.run()
+			{
+				Err( err ) if err.kind() == &ErrorKind::Run => Ok(()),
+				other                                      => other ,
+			},
+
+			_ => Ok(()),
+		}
+	}
+
+
+	// A handle to spawn on this executor from other threads. Single threaded executors, and custom backends that
+	// can spawn `!Send` futures, only run on their own thread, so the handle sends the futures there. The location
+	// is where the handle was requested, for the task that receives the futures.
+	//
+	#[ cfg( feature = "std" ) ]
+	//
+	pub(crate) fn handle( &self, location: &'static Location<'static> ) -> Result< ExecutorHandle, Error >
+	{
+		match self
+		{
+			#[ cfg( feature = "localpool"  ) ] Self::LocalPool (_) => ExecutorHandle::remote( self, location )      ,
+			#[ cfg( feature = "juliex"     ) ] Self::Juliex    (e) => Ok( ExecutorHandle::shared( e.clone() ) )     ,
+			#[ cfg( feature = "threadpool" ) ] Self::ThreadPool(e) => Ok( ExecutorHandle::shared( e.clone() ) )     ,
+			#[ cfg( feature = "async_std"  ) ] Self::AsyncStd  (e) => Ok( ExecutorHandle::shared( e.clone() ) )     ,
+			#[ cfg( feature = "bindgen"    ) ] Self::Bindgen   (_) => ExecutorHandle::remote( self, location )      ,
+			#[ cfg( feature = "tokio_ct"   ) ] Self::TokioCt   (_) => ExecutorHandle::remote( self, location )      ,
+			#[ cfg( feature = "tokio_local") ] Self::TokioLocal(_) => ExecutorHandle::remote( self, location )      ,
+			#[ cfg( feature = "tokio_tp"   ) ] Self::TokioTp   (e) => Ok( ExecutorHandle::shared( e.clone() ) )     ,
+			#[ cfg( feature = "smol"       ) ] Self::Smol      (e) => Ok( ExecutorHandle::shared( e.clone() ) )     ,
+			#[ cfg( feature = "smol"       ) ] Self::SmolLocal (_) => ExecutorHandle::remote( self, location )      ,
+			#[ cfg( feature = "static_exec") ] Self::Static    (e) => Ok( ExecutorHandle::shared( *e ) )            ,
+
+			#[ cfg( feature = "deterministic" ) ] Self::Deterministic(_) => ExecutorHandle::remote( self, location ),
+
+			Self::Custom(e) if e.capabilities().spawn_local => ExecutorHandle::remote( self, location )  ,
+			Self::Custom(e)                                 => Ok( ExecutorHandle::shared( e.clone() ) ) ,
+		}
+	}
+
+
+	pub(crate) fn spawn( &self, task: Task, fut: impl Future< Output = () > + 'static + Send ) -> Result< (), Error >
+	{
+		#[ cfg( feature = "tracing" ) ]
+		//
+		let span = crate::trace::span( self.name(), &task );
+
+		let fut = task::instrument( task, fut );
+
+		#[ cfg( feature = "metrics" ) ]
+		//
+		let fut = crate::metrics::instrument( self.name(), fut );
+
+		#[ cfg( feature = "tracing" ) ]
+		//
+		let fut = crate::trace::instrument( span, fut );
+
 		match self
 		{
 			#[ cfg( feature = "localpool"  ) ] Self::LocalPool  (e) => e.spawn( fut ),
@@ -116,18 +323,35 @@ impl Executor
 			#[ cfg( feature = "async_std"  ) ] Self::AsyncStd   (e) => e.spawn( fut ),
 			#[ cfg( feature = "bindgen"    ) ] Self::Bindgen    (e) => e.spawn( fut ),
 			#[ cfg( feature = "tokio_ct"   ) ] Self::TokioCt    (e) => e.spawn( fut ),
+			#[ cfg( feature = "tokio_local") ] Self::TokioLocal (e) => e.spawn( fut ),
+			#[ cfg( feature = "tokio_tp"   ) ] Self::TokioTp    (e) => e.spawn( fut ),
+			#[ cfg( feature = "smol"       ) ] Self::Smol       (e) => e.spawn( fut ),
+			#[ cfg( feature = "smol"       ) ] Self::SmolLocal  (e) => e.spawn( fut ),
+			#[ cfg( feature = "static_exec") ] Self::Static     (e) => e.spawn( fut ),
 
-			_ => unreachable!(),
+			#[ cfg( feature = "deterministic" ) ] Self::Deterministic(e) => e.spawn( fut ),
+
+			Self::Custom(e) => e.spawn( fut ),
 		}
 	}
 
 
-	// For the case where we compile without an executor enabled, the fut variable will be unused.
-	//
-	#[ allow( unused_variables ) ]
-	//
-	pub(crate) fn spawn_local( &self, fut: impl Future< Output = () > + 'static ) -> Result< (), Error >
+	pub(crate) fn spawn_local( &self, task: Task, fut: impl Future< Output = () > + 'static ) -> Result< (), Error >
 	{
+		#[ cfg( feature = "tracing" ) ]
+		//
+		let span = crate::trace::span( self.name(), &task );
+
+		let fut = task::instrument( task, fut );
+
+		#[ cfg( feature = "metrics" ) ]
+		//
+		let fut = crate::metrics::instrument( self.name(), fut );
+
+		#[ cfg( feature = "tracing" ) ]
+		//
+		let fut = crate::trace::instrument( span, fut );
+
 		match self
 		{
 			#[ cfg( feature = "localpool"  ) ] Self::LocalPool  (e) => e.spawn_local( fut ),
@@ -136,8 +360,15 @@ impl Executor
 			#[ cfg( feature = "async_std"  ) ] Self::AsyncStd   (e) => e.spawn_local( fut ),
 			#[ cfg( feature = "bindgen"    ) ] Self::Bindgen    (e) => e.spawn_local( fut ),
 			#[ cfg( feature = "tokio_ct"   ) ] Self::TokioCt    (e) => e.spawn_local( fut ),
+			#[ cfg( feature = "tokio_local") ] Self::TokioLocal (e) => e.spawn_local( fut ),
+			#[ cfg( feature = "tokio_tp"   ) ] Self::TokioTp    (e) => e.spawn_local( fut ),
+			#[ cfg( feature = "smol"       ) ] Self::Smol       (e) => e.spawn_local( fut ),
+			#[ cfg( feature = "smol"       ) ] Self::SmolLocal  (e) => e.spawn_local( fut ),
+			#[ cfg( feature = "static_exec") ] Self::Static     (e) => e.spawn_local( fut ),
 
-			_ => unreachable!(),
+			#[ cfg( feature = "deterministic" ) ] Self::Deterministic(e) => e.spawn_local( fut ),
+
+			Self::Custom(e) => e.spawn_local( fut ),
 		}
 	}
 
@@ -146,15 +377,25 @@ impl Executor
 
 	/// Spawn a future and recover the output.
 	//
-	// For the case where we compile without an executor enabled, the fut variable will be unused.
-	//
-	#[ allow( unused_variables ) ]
-	//
-	pub(crate) fn spawn_handle<T: 'static + Send>( &self, fut: impl Future< Output=T > + Send + 'static )
+	pub(crate) fn spawn_handle<T: 'static + Send>( &self, task: Task, fut: impl Future< Output=T > + Send + 'static )
 
-		-> Result< Box< dyn Future< Output=T > + Unpin + Send + 'static >, Error >
+		-> Result< JoinHandle<T>, Error >
 
 	{
+		#[ cfg( feature = "tracing" ) ]
+		//
+		let span = crate::trace::span( self.name(), &task );
+
+		let fut = task::instrument( task, fut );
+
+		#[ cfg( feature = "metrics" ) ]
+		//
+		let fut = crate::metrics::instrument( self.name(), fut );
+
+		#[ cfg( feature = "tracing" ) ]
+		//
+		let fut = crate::trace::instrument( span, fut );
+
 		match self
 		{
 			#[ cfg( feature = "localpool"  ) ] Self::LocalPool (e) => e.spawn_handle( fut ),
@@ -163,8 +404,15 @@ impl Executor
 			#[ cfg( feature = "async_std"  ) ] Self::AsyncStd  (e) => e.spawn_handle( fut ),
 			#[ cfg( feature = "bindgen"    ) ] Self::Bindgen   (e) => e.spawn_handle( fut ),
 			#[ cfg( feature = "tokio_ct"   ) ] Self::TokioCt   (e) => e.spawn_handle( fut ),
+			#[ cfg( feature = "tokio_local") ] Self::TokioLocal(e) => e.spawn_handle( fut ),
+			#[ cfg( feature = "tokio_tp"   ) ] Self::TokioTp   (e) => e.spawn_rt_handle( fut ),
+			#[ cfg( feature = "smol"       ) ] Self::Smol      (e) => e.spawn_handle( fut ),
+			#[ cfg( feature = "smol"       ) ] Self::SmolLocal (e) => e.spawn_handle( fut ),
+			#[ cfg( feature = "static_exec") ] Self::Static    (e) => e.spawn_handle( fut ),
 
-			_ => unreachable!(),
+			#[ cfg( feature = "deterministic" ) ] Self::Deterministic(e) => e.spawn_handle( fut ),
+
+			Self::Custom(e) => e.spawn_handle( fut ),
 		}
 	}
 
@@ -172,15 +420,25 @@ impl Executor
 
 	/// Spawn a future and recover the output for `!Send` futures.
 	//
-	// For the case where we compile without an executor enabled, the fut variable will be unused.
-	//
-	#[ allow( unused_variables ) ]
-	//
-	pub(crate) fn spawn_handle_local<T: 'static + Send>( &self, fut: impl Future< Output=T > + 'static )
+	pub(crate) fn spawn_handle_local<T: 'static>( &self, task: Task, fut: impl Future< Output=T > + 'static )
 
-		-> Result< Box< dyn Future< Output=T > + 'static + Unpin >, Error >
+		-> Result< JoinHandle<T>, Error >
 
 	{
+		#[ cfg( feature = "tracing" ) ]
+		//
+		let span = crate::trace::span( self.name(), &task );
+
+		let fut = task::instrument( task, fut );
+
+		#[ cfg( feature = "metrics" ) ]
+		//
+		let fut = crate::metrics::instrument( self.name(), fut );
+
+		#[ cfg( feature = "tracing" ) ]
+		//
+		let fut = crate::trace::instrument( span, fut );
+
 		match self
 		{
 			#[ cfg( feature = "localpool"  ) ] Self::LocalPool (e) => e.spawn_handle_local( fut ),
@@ -189,8 +447,150 @@ impl Executor
 			#[ cfg( feature = "async_std"  ) ] Self::AsyncStd  (e) => e.spawn_handle_local( fut ),
 			#[ cfg( feature = "bindgen"    ) ] Self::Bindgen   (e) => e.spawn_handle_local( fut ),
 			#[ cfg( feature = "tokio_ct"   ) ] Self::TokioCt   (e) => e.spawn_handle_local( fut ),
+			#[ cfg( feature = "tokio_local") ] Self::TokioLocal(e) => e.spawn_handle_local( fut ),
+			#[ cfg( feature = "tokio_tp"   ) ] Self::TokioTp   (e) => e.spawn_handle_local( fut ),
+			#[ cfg( feature = "smol"       ) ] Self::Smol      (e) => e.spawn_handle_local( fut ),
+			#[ cfg( feature = "smol"       ) ] Self::SmolLocal (e) => e.spawn_handle_local( fut ),
+			#[ cfg( feature = "static_exec") ] Self::Static    (e) => e.spawn_handle_local( fut ),
 
-			_ => unreachable!(),
+			#[ cfg( feature = "deterministic" ) ] Self::Deterministic(e) => e.spawn_handle_local( fut ),
+
+			Self::Custom(e) => e.spawn_handle_local( fut ),
 		}
 	}
 }
+
+
+
+// Create the executor the same way `init` does.
+//
+impl From<Config> for Executor
+{
+	fn from( config: Config ) -> Self { Self::new( config ) }
+}
+
+
+impl Spawn for Executor
+{
+	#[ track_caller ]
+	//
+	fn spawn_obj( &self, fut: FutureObj<'static, ()> ) -> Result< (), SpawnError >
+	{
+		self.spawn( Task::new( None, Location::caller() ), fut ).map_err( |_| SpawnError::shutdown() )
+	}
+}
+
+
+// On threadpools this returns an error, since they cannot spawn `!Send` futures.
+//
+impl LocalSpawn for Executor
+{
+	#[ track_caller ]
+	//
+	fn spawn_local_obj( &self, fut: LocalFutureObj<'static, ()> ) -> Result< (), SpawnError >
+	{
+		self.spawn_local( Task::new( None, Location::caller() ), fut ).map_err( |_| SpawnError::shutdown() )
+	}
+}
+
+
+#[ cfg( feature = "localpool" ) ]
+//
+impl From<LocalPool> for Executor
+{
+	fn from( e: LocalPool ) -> Self { Self::LocalPool( e ) }
+}
+
+
+#[ cfg( feature = "juliex" ) ]
+//
+impl From<Juliex> for Executor
+{
+	fn from( e: Juliex ) -> Self { Self::Juliex( e ) }
+}
+
+
+#[ cfg( feature = "threadpool" ) ]
+//
+impl From<ThreadPool> for Executor
+{
+	fn from( e: ThreadPool ) -> Self { Self::ThreadPool( e ) }
+}
+
+
+#[ cfg( feature = "async_std" ) ]
+//
+impl From<AsyncStd> for Executor
+{
+	fn from( e: AsyncStd ) -> Self { Self::AsyncStd( e ) }
+}
+
+
+#[ cfg( feature = "bindgen" ) ]
+//
+impl From<Bindgen> for Executor
+{
+	fn from( e: Bindgen ) -> Self { Self::Bindgen( e ) }
+}
+
+
+#[ cfg( feature = "tokio_ct" ) ]
+//
+impl From<TokioCt> for Executor
+{
+	fn from( e: TokioCt ) -> Self { Self::TokioCt( e ) }
+}
+
+
+#[ cfg( feature = "tokio_local" ) ]
+//
+impl From<TokioLocal> for Executor
+{
+	fn from( e: TokioLocal ) -> Self { Self::TokioLocal( e ) }
+}
+
+
+#[ cfg( feature = "tokio_tp" ) ]
+//
+impl From<TokioTp> for Executor
+{
+	fn from( e: TokioTp ) -> Self { Self::TokioTp( e ) }
+}
+
+
+#[ cfg( feature = "smol" ) ]
+//
+impl From<Smol> for Executor
+{
+	fn from( e: Smol ) -> Self { Self::Smol( e ) }
+}
+
+
+#[ cfg( feature = "smol" ) ]
+//
+impl From<SmolLocal> for Executor
+{
+	fn from( e: SmolLocal ) -> Self { Self::SmolLocal( e ) }
+}
+
+
+#[ cfg( feature = "static_exec" ) ]
+//
+impl From<Static> for Executor
+{
+	fn from( e: Static ) -> Self { Self::Static( e ) }
+}
+
+
+#[ cfg( feature = "deterministic" ) ]
+//
+impl From<Deterministic> for Executor
+{
+	fn from( e: Deterministic ) -> Self { Self::Deterministic( e ) }
+}
+
+
+impl From<CustomBackend> for Executor
+{
+	fn from( e: CustomBackend ) -> Self { Self::Custom( e ) }
+}
@@ -1,26 +1,34 @@
 //! Module containing functionality specific to async-std.
 //
-use crate :: { self as rt, import::*, Error, ErrorKind };
+use crate :: { self as rt, import::*, Error, JoinHandle, ErrorKind };
 
 
 
-// Async-std does not currently expose a handle to control the threadpool, so it's zero sized and zero control.
+/// The async-std executor.
+///
+/// Async-std does not currently expose a handle to control the threadpool, so it's zero sized and zero control.
+/// All instances spawn on the same global threadpool. It is still useful to pass to libraries that take a
+/// `T: Spawn`.
 //
 #[ derive( Debug, Default, Clone ) ]
 //
-pub(crate) struct AsyncStd {}
+pub struct AsyncStd {}
 
 
 
 impl AsyncStd
 {
-	pub(crate) fn new() -> Self
+	/// Create a handle to the async-std executor.
+	//
+	pub fn new() -> Self
 	{
 		Self {}
 	}
 
 
-	pub(crate) fn spawn( &self, fut: impl Future< Output = () > + 'static + Send ) -> Result< (), Error >
+	/// Spawn a future on async-std. See [`spawn`](crate::spawn).
+	//
+	pub fn spawn( &self, fut: impl Future< Output = () > + 'static + Send ) -> Result< (), Error >
 	{
 		// async-std does not allow initializing worker threads, so we need to check on each spawn.
 		//
@@ -39,38 +47,40 @@ impl AsyncStd
 
 
 
-	pub(crate) fn spawn_local( &self, _: impl Future< Output = () > + 'static  ) -> Result< (), Error >
+	/// This always returns [`ErrorKind::SpawnLocalOnThreadPool`]. It exists so the API is the same for all
+	/// executors.
+	//
+	pub fn spawn_local( &self, _: impl Future< Output = () > + 'static  ) -> Result< (), Error >
 	{
 		Err( ErrorKind::SpawnLocalOnThreadPool.into() )
 	}
 
 
 
-	// We don't need to user remote_handle, because async-std provides a handle out of the box.
+	/// Spawn a future on async-std and get a [`JoinHandle`] to it's output. See [`spawn_handle`](crate::spawn_handle).
+	//
+	// async-std provides a handle out of the box, but it detaches the task on drop, where all other executors
+	// cancel it. Use our own JoinHandle so the behavior is the same everywhere.
 	//
-	pub(crate) fn spawn_handle<T: 'static + Send>( &self, fut: impl Future< Output=T > + Send + 'static )
+	pub fn spawn_handle<T: 'static + Send>( &self, fut: impl Future< Output=T > + Send + 'static )
 
-		-> Result< Box< dyn Future< Output=T > + Send + 'static + Unpin >, Error >
+		-> Result< JoinHandle<T>, Error >
 
 	{
-		let task = async
-		{
-			if rt::current_rt().is_none()
-			{
-				rt::init( rt::Config::AsyncStd ).expect( "no double executor init" );
-			}
+		let (fut, handle) = JoinHandle::new( fut );
 
-			fut.await
-		};
-
-		Ok( Box::new( async_std_crate::task::spawn( task )))
+		self.spawn( fut )?;
+		Ok( handle )
 	}
 
 
 
-	pub(crate) fn spawn_handle_local<T: 'static + Send>( &self, _: impl Future< Output=T > + 'static )
+	/// This always returns [`ErrorKind::SpawnLocalOnThreadPool`]. It exists so the API is the same for all
+	/// executors.
+	//
+	pub fn spawn_handle_local<T: 'static>( &self, _: impl Future< Output=T > + 'static )
 
-		-> Result< Box< dyn Future< Output=T > + 'static + Unpin >, Error >
+		-> Result< JoinHandle<T>, Error >
 
 	{
 		Err( ErrorKind::SpawnLocalOnThreadPool.into() )
@@ -79,6 +89,16 @@ impl AsyncStd
 
 
 
+impl Spawn for AsyncStd
+{
+	fn spawn_obj( &self, fut: FutureObj<'static, ()> ) -> Result< (), SpawnError >
+	{
+		self.spawn( fut ).map_err( |_| SpawnError::shutdown() )
+	}
+}
+
+
+
 /// AsyncStd specific version of [`spawn_handle`](crate::spawn_handle). This avoids the need for
 /// boxing. The worker thread will be set up to further spawn on AsyncStd.
 ///
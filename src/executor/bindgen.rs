@@ -1,31 +1,41 @@
-use crate :: { import::*, Error };
+use crate :: { import::*, Error, JoinHandle };
 
 
 
+/// The wasm-bindgen-futures executor.
+///
+/// It is zero sized. It can be passed to libraries that take a `T: Spawn` or `T: LocalSpawn`.
+//
 // We only really have an interface to the spawn_local method. There are not threads on WASM, we cannot
 // block the current thread. These futures are just passed to Javascript to be converted into promises.
 //
-#[ derive( Debug, Clone ) ]
+#[ derive( Debug, Default, Clone ) ]
 //
-pub(crate) struct Bindgen {}
+pub struct Bindgen {}
 
 
 
 impl Bindgen
 {
-	pub(crate) fn new() -> Self
+	/// Create a handle to the wasm-bindgen-futures executor.
+	//
+	pub fn new() -> Self
 	{
 		Self {}
 	}
 
 
-	pub(crate) fn spawn( &self, fut: impl Future< Output = () > + 'static + Send ) -> Result< (), Error >
+	/// Spawn a future. See [`spawn`](crate::spawn).
+	//
+	pub fn spawn( &self, fut: impl Future< Output = () > + 'static + Send ) -> Result< (), Error >
 	{
 		self.spawn_local( fut )
 	}
 
 
-	pub(crate) fn spawn_local( &self, fut: impl Future< Output = () > + 'static  ) -> Result< (), Error >
+	/// Spawn a `!Send` future. See [`spawn_local`](crate::spawn_local).
+	//
+	pub fn spawn_local( &self, fut: impl Future< Output = () > + 'static  ) -> Result< (), Error >
 	{
 		spawn_local( fut );
 
@@ -33,25 +43,54 @@ impl Bindgen
 	}
 
 
-	pub(crate) fn spawn_handle<T: 'static + Send>( &self, fut: impl Future< Output=T > + Send + 'static )
+	/// Spawn a future and get a [`JoinHandle`] to it's output. See [`spawn_handle`](crate::spawn_handle).
+	//
+	pub fn spawn_handle<T: 'static + Send>( &self, fut: impl Future< Output=T > + Send + 'static )
 
-		-> Result< Box< dyn Future< Output=T > + Send + 'static + Unpin >, Error >
+		-> Result< JoinHandle<T>, Error >
 
 	{
-		let (fut, handle) = fut.remote_handle();
+		let (fut, handle) = JoinHandle::new( fut );
 
 		spawn_local( fut );
-		Ok(Box::new( handle ))
+		Ok( handle )
 	}
 
 
-	pub(crate) fn spawn_handle_local<T: 'static + Send>( &self, fut: impl Future< Output=T > + 'static )
+	/// Spawn a `!Send` future and get a [`JoinHandle`] to it's output.
+	/// See [`spawn_handle_local`](crate::spawn_handle_local).
+	//
+	pub fn spawn_handle_local<T: 'static>( &self, fut: impl Future< Output=T > + 'static )
 
-		-> Result< Box< dyn Future< Output=T > + 'static + Unpin >, Error >
+		-> Result< JoinHandle<T>, Error >
 	{
-		let (fut, handle) = fut.remote_handle();
+		let (fut, handle) = JoinHandle::new( fut );
 
 		spawn_local( fut );
-		Ok(Box::new( handle ))
+		Ok( handle )
+	}
+}
+
+
+
+impl Spawn for Bindgen
+{
+	fn spawn_obj( &self, fut: FutureObj<'static, ()> ) -> Result< (), SpawnError >
+	{
+		spawn_local( fut );
+
+		Ok(())
+	}
+}
+
+
+
+impl LocalSpawn for Bindgen
+{
+	fn spawn_local_obj( &self, fut: LocalFutureObj<'static, ()> ) -> Result< (), SpawnError >
+	{
+		spawn_local( fut );
+
+		Ok(())
 	}
 }
@@ -0,0 +1,167 @@
+use crate :: { import::*, Backend, Capabilities, Error, ErrorKind, JoinHandle };
+
+
+/// A [`Backend`] you implemented yourself, so it can be used like the executors that are built in. You can pass it
+/// to [`init_with`](crate::init_with) or wrap it in [`Config::Custom`](crate::Config::Custom). Clones refer to the
+/// same backend.
+///
+/// Two `CustomBackend` are only equal if they are clones of each other, so [`init_allow_same`](crate::init_allow_same)
+/// and [`current_rt`](crate::current_rt) compare the backend instance, not it's type.
+//
+#[ derive( Debug, Clone ) ]
+//
+pub struct CustomBackend
+{
+	backend: Arc<dyn Backend>,
+}
+
+
+
+impl CustomBackend
+{
+	/// Wrap your backend.
+	//
+	pub fn new( backend: impl Backend ) -> Self
+	{
+		Self { backend: Arc::new( backend ) }
+	}
+
+
+	/// The name of the backend. See [`Backend::name`].
+	//
+	pub fn name( &self ) -> &'static str
+	{
+		self.backend.name()
+	}
+
+
+	/// What the backend supports. See [`Backend::capabilities`].
+	//
+	pub fn capabilities( &self ) -> Capabilities
+	{
+		self.backend.capabilities()
+	}
+
+
+
+	/// Stop accepting new tasks and wait until the tasks in flight finish or the timeout expires. See
+	/// [`shutdown`](crate::shutdown).
+	///
+	/// ### Errors
+	///
+	/// Returns [`ErrorKind::WrongExecutor`] if the backend can not be shut down.
+	//
+	pub fn shutdown( &self, timeout: Duration ) -> Result< bool, Error >
+	{
+		if !self.capabilities().shutdown
+		{
+			return Err( ErrorKind::WrongExecutor.into() );
+		}
+
+		self.backend.shutdown( timeout )
+	}
+
+
+
+	/// Spawn a future on this backend. See [`spawn`](crate::spawn). This boxes the future.
+	//
+	pub fn spawn( &self, fut: impl Future< Output = () > + 'static + Send ) -> Result< (), Error >
+	{
+		self.backend.spawn( FutureObj::new( Box::new( fut ) ) )
+	}
+
+
+
+	/// Spawn a `!Send` future on this backend. See [`spawn_local`](crate::spawn_local). This boxes the future.
+	///
+	/// ### Errors
+	///
+	/// Returns [`ErrorKind::SpawnLocalOnThreadPool`] if the backend can not spawn `!Send` futures.
+	//
+	pub fn spawn_local( &self, fut: impl Future< Output = () > + 'static ) -> Result< (), Error >
+	{
+		if !self.capabilities().spawn_local
+		{
+			return Err( ErrorKind::SpawnLocalOnThreadPool.into() );
+		}
+
+		self.backend.spawn_local( LocalFutureObj::new( Box::new( fut ) ) )
+	}
+
+
+
+	/// Spawn a future on this backend and get a [`JoinHandle`] to it's output. See [`spawn_handle`](crate::spawn_handle).
+	//
+	pub fn spawn_handle<T: 'static + Send>( &self, fut: impl Future< Output=T > + Send + 'static )
+
+		-> Result< JoinHandle<T>, Error >
+
+	{
+		let (fut, handle) = JoinHandle::new( fut );
+
+		self.spawn( fut )?;
+		Ok( handle )
+	}
+
+
+
+	/// Spawn a `!Send` future on this backend and get a [`JoinHandle`] to it's output.
+	/// See [`spawn_handle_local`](crate::spawn_handle_local).
+	//
+	pub fn spawn_handle_local<T: 'static>( &self, fut: impl Future< Output=T > + 'static )
+
+		-> Result< JoinHandle<T>, Error >
+
+	{
+		let (fut, handle) = JoinHandle::new( fut );
+
+		self.spawn_local( fut )?;
+		Ok( handle )
+	}
+}
+
+
+
+impl PartialEq for CustomBackend
+{
+	fn eq( &self, other: &Self ) -> bool
+	{
+		Arc::ptr_eq( &self.backend, &other.backend )
+	}
+}
+
+impl Eq for CustomBackend {}
+
+
+impl Hash for CustomBackend
+{
+	fn hash<H: Hasher>( &self, state: &mut H )
+	{
+		Arc::as_ptr( &self.backend ).cast::<()>().hash( state );
+	}
+}
+
+
+
+impl Spawn for CustomBackend
+{
+	fn spawn_obj( &self, fut: FutureObj<'static, ()> ) -> Result< (), SpawnError >
+	{
+		self.backend.spawn( fut ).map_err( |_| SpawnError::shutdown() )
+	}
+}
+
+
+
+impl LocalSpawn for CustomBackend
+{
+	fn spawn_local_obj( &self, fut: LocalFutureObj<'static, ()> ) -> Result< (), SpawnError >
+	{
+		if !self.capabilities().spawn_local
+		{
+			return Err( SpawnError::shutdown() );
+		}
+
+		self.backend.spawn_local( fut ).map_err( |_| SpawnError::shutdown() )
+	}
+}
@@ -0,0 +1,498 @@
+//! Provides functionality specific to the deterministic executor.
+//!
+//! The deterministic executor runs futures on the current thread, like the localpool, but it picks the next task
+//! to poll among the tasks that are ready pseudo-randomly from a seed. Running the same program with the same seed
+//! polls the tasks in the same order, so you can test your code against many interleavings and replay the one that
+//! fails.
+//!
+//! Wakeups that come from other threads, like timers, can still change the order. Use [`fuzz`] to run a test
+//! over a range of seeds. If you [`pause`](crate::time::pause) the clock, the timers no longer depend on
+//! another thread, and the executor advances the clock to the next timer when no task is ready.
+
+use crate :: { self as rt, import::*, Config, Error, JoinHandle, ErrorKind };
+
+
+/// An executor that runs futures on the current thread and polls the ready tasks in a pseudo-random order
+/// determined by a seed.
+///
+/// You can create one yourself and pass it to libraries that take a `T: Spawn` or `T: LocalSpawn`, or install it as
+/// the executor for the current thread with [`init_with`](crate::init_with). Clones refer to the same executor.
+///
+/// Spawned futures will only be polled when you run the executor. If a task panics while the executor runs, the
+/// seed is printed to stderr so you can replay the failure.
+//
+#[ derive( Debug, Clone ) ]
+//
+pub struct Deterministic
+{
+	inner: Rc< RefCell<Inner> > ,
+	queue: Arc<Queue>           ,
+	seed : u64                  ,
+}
+
+
+// The tasks and the random number generator. We never keep this borrowed while polling a task, since the
+// task might spawn.
+//
+#[ derive( Debug ) ]
+//
+struct Inner
+{
+	tasks: Vec< Option<Task> > ,
+	free : Vec<usize>          ,
+	alive: usize               ,
+	rng  : u64                 ,
+}
+
+
+struct Task
+{
+	fut  : Pin< Box< dyn Future< Output=() > > > ,
+	waker: Waker                                 ,
+}
+
+
+impl fmt::Debug for Task
+{
+	fn fmt( &self, f: &mut fmt::Formatter<'_> ) -> fmt::Result
+	{
+		f.pad( "Task { .. }" )
+	}
+}
+
+
+// The ids of the tasks that have been woken up. Wakers can be called from any thread.
+//
+#[ derive( Debug ) ]
+//
+struct Queue
+{
+	ready : Mutex< Vec<usize> > ,
+	thread: thread::Thread      ,
+}
+
+
+impl Queue
+{
+	fn push( &self, id: usize )
+	{
+		let mut ready = self.ready.lock().expect( "lock deterministic queue" );
+
+		if !ready.contains( &id )
+		{
+			ready.push( id );
+		}
+
+		drop( ready );
+		self.thread.unpark();
+	}
+}
+
+
+struct TaskWaker
+{
+	id   : usize      ,
+	queue: Arc<Queue> ,
+}
+
+
+impl ArcWake for TaskWaker
+{
+	fn wake_by_ref( arc_self: &Arc<Self> )
+	{
+		arc_self.queue.push( arc_self.id );
+	}
+}
+
+
+// The id we use for the future passed to `run_until`, which is not stored in the tasks.
+//
+const MAIN: usize = usize::MAX;
+
+
+
+impl Deterministic
+{
+	/// Create a new deterministic executor with the given seed.
+	//
+	pub fn new( seed: u64 ) -> Self
+	{
+		let queue = Arc::new( Queue { ready: Mutex::new( Vec::new() ), thread: thread::current() } );
+		let inner = Inner { tasks: Vec::new(), free: Vec::new(), alive: 0, rng: seed };
+
+		Self { inner: Rc::new( RefCell::new( inner ) ), queue, seed }
+	}
+
+
+	/// The seed this executor was created with.
+	//
+	pub fn seed( &self ) -> u64
+	{
+		self.seed
+	}
+
+
+	/// The configuration this executor was created with.
+	//
+	pub fn config( &self ) -> Config
+	{
+		Config::Deterministic{ seed: self.seed }
+	}
+
+
+	/// Run all spawned futures to completion. This blocks the current thread.
+	///
+	/// ### Panics
+	///
+	/// If a task panics, the panic is propagated after printing the seed. Do not call this from within a task
+	/// running on the same executor.
+	//
+	pub fn run( &self )
+	{
+		let _guard = SeedGuard( self.seed );
+
+		while self.inner.borrow().alive > 0
+		{
+			match self.next()
+			{
+				Some(id) => self.poll( id ),
+				None     => self.idle()    ,
+			}
+		}
+	}
+
+
+	/// Runs all the tasks until the given future completes. The future takes part in the random order.
+	///
+	/// ### Panics
+	///
+	/// If a task panics, the panic is propagated after printing the seed. Do not call this from within a task
+	/// running on the same executor.
+	//
+	pub fn run_until<F: Future>( &self, future: F ) -> <F as Future>::Output
+	{
+		let _guard = SeedGuard( self.seed );
+
+		let mut future = std::pin::pin!( future );
+		let     waker  = waker( Arc::new( TaskWaker { id: MAIN, queue: self.queue.clone() } ) );
+
+		self.queue.push( MAIN );
+
+		loop
+		{
+			match self.next()
+			{
+				Some(MAIN) =>
+				{
+					if let Poll::Ready( out ) = future.as_mut().poll( &mut Context::from_waker( &waker ) )
+					{
+						return out;
+					}
+				}
+
+				Some(id) => self.poll( id ),
+				None     => self.idle()    ,
+			}
+		}
+	}
+
+
+	/// Runs all tasks and returns when no more progress can be made on any task.
+	//
+	pub fn run_until_stalled( &self )
+	{
+		let _guard = SeedGuard( self.seed );
+
+		while let Some(id) = self.next()
+		{
+			self.poll( id );
+		}
+	}
+
+
+
+	/// Spawn a future on this executor. See [`spawn`](crate::spawn).
+	//
+	pub fn spawn( &self, fut: impl Future< Output = () > + 'static + Send ) -> Result< (), Error >
+	{
+		self.spawn_local( fut )
+	}
+
+
+
+	/// Spawn a `!Send` future on this executor. See [`spawn_local`](crate::spawn_local).
+	//
+	pub fn spawn_local( &self, fut: impl Future< Output = () > + 'static ) -> Result< (), Error >
+	{
+		let mut inner = self.inner.borrow_mut();
+
+		let id = match inner.free.pop()
+		{
+			Some(id) => id,
+
+			None =>
+			{
+				inner.tasks.push( None );
+				inner.tasks.len() - 1
+			}
+		};
+
+		let waker = waker( Arc::new( TaskWaker { id, queue: self.queue.clone() } ) );
+
+		inner.tasks[id] = Some( Task { fut: Box::pin( fut ), waker } );
+		inner.alive    += 1;
+
+		drop( inner );
+		self.queue.push( id );
+
+		Ok(())
+	}
+
+
+
+	/// Spawn a future on this executor and get a [`JoinHandle`] to it's output. See [`spawn_handle`](crate::spawn_handle).
+	//
+	pub fn spawn_handle<T: 'static + Send>( &self, fut: impl Future< Output=T > + Send + 'static )
+
+		-> Result< JoinHandle<T>, Error >
+
+	{
+		let (fut, handle) = JoinHandle::new( fut );
+
+		self.spawn_local( fut )?;
+		Ok( handle )
+	}
+
+
+
+	/// Spawn a `!Send` future on this executor and get a [`JoinHandle`] to it's output.
+	/// See [`spawn_handle_local`](crate::spawn_handle_local).
+	//
+	pub fn spawn_handle_local<T: 'static>( &self, fut: impl Future< Output=T > + 'static )
+
+		-> Result< JoinHandle<T>, Error >
+	{
+		let (fut, handle) = JoinHandle::new( fut );
+
+		self.spawn_local( fut )?;
+		Ok( handle )
+	}
+
+
+
+	// Nothing is ready. Jump to the next timer if the clock is paused, otherwise wait for a wakeup.
+	//
+	fn idle( &self )
+	{
+		if !rt::time::auto_advance()
+		{
+			thread::park();
+		}
+	}
+
+
+
+	// Pick the next task to poll among the ready ones.
+	//
+	fn next( &self ) -> Option<usize>
+	{
+		let mut ready = self.queue.ready.lock().expect( "lock deterministic queue" );
+
+		if ready.is_empty()
+		{
+			return None;
+		}
+
+		let index = ( self.inner.borrow_mut().random() % ready.len() as u64 ) as usize;
+
+		Some( ready.swap_remove( index ) )
+	}
+
+
+
+	// Poll a task. The task is taken out while it's polled, so it can spawn on this executor.
+	//
+	fn poll( &self, id: usize )
+	{
+		// A waker can fire after the task has completed.
+		//
+		let mut task = match self.inner.borrow_mut().tasks.get_mut( id ).and_then( Option::take )
+		{
+			Some(task) => task,
+			None       => return,
+		};
+
+		let done = task.fut.as_mut().poll( &mut Context::from_waker( &task.waker ) ).is_ready();
+
+		let mut inner = self.inner.borrow_mut();
+
+		if done
+		{
+			inner.free.push( id );
+			inner.alive -= 1;
+		}
+
+		else
+		{
+			inner.tasks[id] = Some( task );
+		}
+	}
+}
+
+
+
+impl Inner
+{
+	// splitmix64, so every seed gives a good sequence, including 0.
+	//
+	fn random( &mut self ) -> u64
+	{
+		self.rng = self.rng.wrapping_add( 0x9E37_79B9_7F4A_7C15 );
+
+		let mut z = self.rng;
+
+		z = ( z ^ ( z >> 30 ) ).wrapping_mul( 0xBF58_476D_1CE4_E5B9 );
+		z = ( z ^ ( z >> 27 ) ).wrapping_mul( 0x94D0_49BB_1331_11EB );
+
+		z ^ ( z >> 31 )
+	}
+}
+
+
+
+// Print the seed if a task panics while we run the executor.
+//
+struct SeedGuard( u64 );
+
+impl Drop for SeedGuard
+{
+	fn drop( &mut self )
+	{
+		if thread::panicking()
+		{
+			eprintln!( "async_runtime: a task panicked on the deterministic executor with seed: {}", self.0 );
+		}
+	}
+}
+
+
+
+impl Spawn for Deterministic
+{
+	fn spawn_obj( &self, fut: FutureObj<'static, ()> ) -> Result< (), SpawnError >
+	{
+		self.spawn_local( fut ).map_err( |_| SpawnError::shutdown() )
+	}
+}
+
+
+
+impl LocalSpawn for Deterministic
+{
+	fn spawn_local_obj( &self, fut: LocalFutureObj<'static, ()> ) -> Result< (), SpawnError >
+	{
+		self.spawn_local( fut ).map_err( |_| SpawnError::shutdown() )
+	}
+}
+
+
+
+/// Run all spawned futures to completion. You must run this after spawning on the deterministic executor or
+/// futures won't be polled. Do not call it from within a spawned task, or your program will hang or panic.
+//
+pub fn run() -> Result< (), Error >
+{
+	rt::with_exec( |some|
+	{
+		match some
+		{
+			Some(super::Executor::Deterministic(e)) => { e.run(); Ok(()) }
+			None                                    => Err( ErrorKind::NoExecutorInitialized.into() ),
+			_                                       => Err( ErrorKind::WrongExecutor.into()         ),
+		}
+	})
+}
+
+
+/// Runs all the tasks until the given future completes.
+//
+pub fn run_until<F: Future>( future: F ) -> Result< <F as Future>::Output, Error >
+{
+	rt::with_exec( |some|
+	{
+		match some
+		{
+			Some(super::Executor::Deterministic(e)) => Ok ( e.run_until( future )                   ),
+			None                                    => Err( ErrorKind::NoExecutorInitialized.into() ),
+			_                                       => Err( ErrorKind::WrongExecutor.into()         ),
+		}
+	})
+}
+
+
+/// Runs all tasks and returns when no more progress can be made on any task.
+//
+pub fn run_until_stalled() -> Result< (), Error >
+{
+	rt::with_exec( |some|
+	{
+		match some
+		{
+			Some(super::Executor::Deterministic(e)) => { e.run_until_stalled(); Ok(()) }
+			None                                    => Err( ErrorKind::NoExecutorInitialized.into() ),
+			_                                       => Err( ErrorKind::WrongExecutor.into()         ),
+		}
+	})
+}
+
+
+/// The seed of the deterministic executor of the current thread.
+//
+pub fn seed() -> Result< u64, Error >
+{
+	rt::with_exec( |some|
+	{
+		match some
+		{
+			Some(super::Executor::Deterministic(e)) => Ok ( e.seed()                                ),
+			None                                    => Err( ErrorKind::NoExecutorInitialized.into() ),
+			_                                       => Err( ErrorKind::WrongExecutor.into()         ),
+		}
+	})
+}
+
+
+/// Run a test once for every seed, each time on a new deterministic executor. Within the closure, `spawn` and
+/// friends use that executor, like in [`exec`](crate::exec). If the test panics, the seed is printed to stderr
+/// and the panic is propagated, so you can replay it with `fuzz( seed..=seed, test )`.
+///
+/// ### Example
+///
+/// ```
+/// use async_runtime as rt;
+///
+/// rt::deterministic::fuzz( 0..50, ||
+/// {
+///    let handle = rt::spawn_handle( async { 1 } ).expect( "spawn" );
+///
+///    assert_eq!( 1, rt::deterministic::run_until( handle ).expect( "run" ).expect( "join" ) );
+/// });
+/// ```
+//
+pub fn fuzz( seeds: impl IntoIterator<Item=u64>, test: impl Fn() )
+{
+	for seed in seeds
+	{
+		let result = std::panic::catch_unwind( AssertUnwindSafe( ||
+		{
+			rt::exec( Deterministic::new( seed ), |_| test() )
+		}));
+
+		if let Err( payload ) = result
+		{
+			eprintln!( "async_runtime: test failed on the deterministic executor with seed: {}", seed );
+
+			std::panic::resume_unwind( payload );
+		}
+	}
+}
@@ -1,81 +1,170 @@
-use crate :: { self as rt, import::*, Error, ErrorKind };
+use
+{
+	crate :: { self as rt, import::*, Error, JoinHandle, ErrorKind, JuliexConfig } ,
+	super :: { shutdown::Shutdown, pool_ref::PoolRef } ,
+};
 
 
 
-/// We currently only support a global juliex threadpool. In principle this is the only supported
-/// executor that allows full control. We could expose an interface that allows users to control
-/// the lifetime and scope of a juliex threadpool.
+/// A threadpool from the juliex crate.
+///
+/// You can create one yourself and pass it to libraries that take a `T: Spawn`, or install it as the executor for
+/// the current thread with [`init_with`](crate::init_with). The worker threads will be set up to spawn on the
+/// same pool. Clones refer to the same pool.
+///
+/// The worker threads stop when all clones are dropped and the tasks in flight have finished. The pool installed
+/// on the worker threads doesn't keep it alive. Once you dropped the pool, tasks that still run on it can no
+/// longer spawn on it.
 //
-#[ derive( Debug, Default, Clone ) ]
+#[ derive( Clone ) ]
 //
-pub(crate) struct Juliex {}
+pub struct Juliex
+{
+	pool    : PoolRef< juliex_crate::ThreadPool > ,
+	config  : JuliexConfig                        ,
+	shutdown: Arc<Shutdown>                       ,
+}
 
-static JULIEX_POOL: SyncOnceCell<juliex_crate::ThreadPool> = SyncOnceCell::new();
+
+// The pool used by all threads that call `rt::init` with juliex.
+//
+static JULIEX_POOL: SyncOnceCell<Juliex> = SyncOnceCell::new();
 
 
 impl Juliex
 {
-	/// Create a new Juliex from a [Config](rt::Config) configuration.
+	/// Create a new Juliex threadpool. This pool is independent of the pool used by [`init`](crate::init).
 	//
-	pub(crate) fn new() -> Self
+	pub fn new( config: JuliexConfig ) -> Self
 	{
-		// We create one global juliex executor. Then we set the worker threads of this executor
-		// to spawn on Juliex executors again. This means that while constructing the global juliex::ThreadPool,
-		// we will re-enter this constructor for each worker thread.
+		// The worker threads need to be initialized with the pool, but it does not exist yet when we
+		// set up the pool. The worker threads wait for it to be created.
 		//
-		// OnceCell should be fine being called from several threads. When get_or_init returns an error,
-		// it means that we are already
+		let cell     = Arc::new( SyncOnceCell::<PoolRef<juliex_crate::ThreadPool>>::new() );
+		let worker   = cell.clone();
+		let wconfig  = config.clone();
+		let shutdown = Arc::new( Shutdown::default() );
+		let wshut    = shutdown.clone();
+
+		let pool = PoolRef::new( juliex_crate::ThreadPool::with_setup( move ||
+		{
+			let pool = Self { pool: worker.wait().clone(), config: wconfig.clone(), shutdown: wshut.clone() };
+
+			rt::init_with( pool ).expect( "set executor on juliex working thread" );
+		}));
+
+		// This cannot fail, nobody else has access to the cell yet.
 		//
-		JULIEX_POOL.get_or_init( ||
+		let _ = cell.set( pool.downgrade() );
+
+		Self { pool, config, shutdown }
+	}
+
+
 
-			juliex_crate::ThreadPool::with_setup( ||
-			{
-				rt::init( rt::Config::Juliex ).expect( "set executor on juliex working thread" );
-			})
+	/// Get the threadpool shared by all threads that init with juliex. It's created if it doesn't exist yet.
+	//
+	pub(crate) fn shared( config: JuliexConfig ) -> Self
+	{
+		JULIEX_POOL.get_or_init( || Self::new( config ) ).clone()
+	}
 
-		);
 
-		Self {}
+
+	/// The configuration this threadpool was created with.
+	//
+	pub fn config( &self ) -> &JuliexConfig
+	{
+		&self.config
 	}
 
 
 
-	pub(crate) fn spawn( &self, fut: impl Future< Output = () > + 'static + Send ) -> Result< (), Error >
+	/// Stop accepting new tasks and wait until the tasks in flight finish or the timeout expires. Returns true
+	/// if all tasks finished in time. See [`shutdown`](crate::shutdown).
+	///
+	/// This affects all clones of this pool, including the ones installed on the worker threads.
+	//
+	pub fn shutdown( &self, timeout: Duration ) -> bool
 	{
-		// We can unwrap, since the constructor guarantees that the pool is created, we are sure it exists.
-		//
-		JULIEX_POOL.get().unwrap().spawn( fut );
+		self.shutdown.shutdown( timeout )
+	}
+
+
+
+	/// Spawn a future on this pool. See [`spawn`](crate::spawn).
+	//
+	pub fn spawn( &self, fut: impl Future< Output = () > + 'static + Send ) -> Result< (), Error >
+	{
+		let pool = self.pool.get()?;
+		let fut  = self.shutdown.track( fut )?;
+
+		pool.spawn( fut );
 
 		Ok(())
 	}
 
 
 
-	pub(crate) fn spawn_local( &self, _: impl Future< Output = () > + 'static  ) -> Result< (), Error >
+	/// This always returns [`ErrorKind::SpawnLocalOnThreadPool`]. It exists so the API is the same for all
+	/// executors.
+	//
+	pub fn spawn_local( &self, _: impl Future< Output = () > + 'static  ) -> Result< (), Error >
 	{
 		Err( ErrorKind::SpawnLocalOnThreadPool.into() )
 	}
 
 
 
-	pub(crate) fn spawn_handle<T: 'static + Send>( &self, fut: impl Future< Output=T > + Send + 'static )
+	/// Spawn a future on this pool and get a [`JoinHandle`] to it's output. See [`spawn_handle`](crate::spawn_handle).
+	//
+	pub fn spawn_handle<T: 'static + Send>( &self, fut: impl Future< Output=T > + Send + 'static )
 
-		-> Result< Box< dyn Future< Output=T > + Send + 'static + Unpin >, Error >
+		-> Result< JoinHandle<T>, Error >
 
 	{
-		let (fut, handle) = fut.remote_handle();
+		let (fut, handle) = JoinHandle::new( fut );
 
 		self.spawn( fut )?;
-		Ok(Box::new( handle ))
+		Ok( handle )
 	}
 
 
 
-	pub(crate) fn spawn_handle_local<T: 'static + Send>( &self, _: impl Future< Output=T > + 'static )
+	/// This always returns [`ErrorKind::SpawnLocalOnThreadPool`]. It exists so the API is the same for all
+	/// executors.
+	//
+	pub fn spawn_handle_local<T: 'static>( &self, _: impl Future< Output=T > + 'static )
 
-		-> Result< Box< dyn Future< Output=T > + 'static + Unpin >, Error >
+		-> Result< JoinHandle<T>, Error >
 
 	{
 		Err( ErrorKind::SpawnLocalOnThreadPool.into() )
 	}
 }
+
+
+
+impl Spawn for Juliex
+{
+	fn spawn_obj( &self, fut: FutureObj<'static, ()> ) -> Result< (), SpawnError >
+	{
+		self.spawn( fut ).map_err( |_| SpawnError::shutdown() )
+	}
+}
+
+
+
+// juliex::ThreadPool does not implement Debug.
+//
+impl fmt::Debug for Juliex
+{
+	fn fmt( &self, f: &mut fmt::Formatter<'_> ) -> fmt::Result
+	{
+		f.debug_struct( "Juliex" )
+
+			.field( "config", &self.config )
+
+		.finish()
+	}
+}
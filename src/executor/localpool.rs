@@ -1,116 +1,265 @@
 //! Provides localpool executor specific functionality.
 
-use crate :: { self as rt, import::*, Error, ErrorKind };
+use crate :: { self as rt, import::*, Error, JoinHandle, ErrorKind };
 
 
 /// An executor that uses [futures 0.3 LocalPool](https://rust-lang-nursery.github.io/futures-api-docs/0.3.0-alpha.16/futures/executor/struct.LocalPool.html) or [LocalPool](https://docs.rs/LocalPool) threadpool under the hood.
+///
+/// You can create one yourself and pass it to libraries that take a `T: Spawn` or `T: LocalSpawn`, or install it as
+/// the executor for the current thread with [`init_with`](crate::init_with). Clones refer to the same pool.
+///
+/// Spawned futures will only be polled when you run the pool. If the clock of the thread is
+/// [paused](crate::time::pause), `run` and `run_until` advance it to the next timer when no task is ready.
 //
-#[ derive( Debug ) ]
+#[ derive( Debug, Clone ) ]
 //
-pub(crate) struct LocalPool
+pub struct LocalPool
 {
-	pool   : RefCell< FutLocalPool >,
-	spawner: RefCell< LocalSpawner >,
+	pool   : Rc< RefCell< FutLocalPool > >,
+	spawner: LocalSpawner                 ,
+	alive  : Rc< Cell<usize> >            ,
+}
+
+
+
+// Counts a task as alive until it's future is dropped, which the pool does when it completes.
+//
+struct Alive( Rc< Cell<usize> > );
+
+impl Alive
+{
+	fn new( alive: &Rc< Cell<usize> > ) -> Self
+	{
+		alive.set( alive.get() + 1 );
+
+		Self( alive.clone() )
+	}
+}
+
+impl Drop for Alive
+{
+	fn drop( &mut self )
+	{
+		self.0.set( self.0.get() - 1 );
+	}
+}
+
+
+
+// Wakes up the future passed to `run_until`.
+//
+struct MainWaker
+{
+	woken : AtomicBool     ,
+	thread: thread::Thread ,
+}
+
+impl ArcWake for MainWaker
+{
+	fn wake_by_ref( arc_self: &Arc<Self> )
+	{
+		arc_self.woken.store( true, Ordering::SeqCst );
+		arc_self.thread.unpark();
+	}
 }
 
 
 
 impl LocalPool
 {
-	/// Create a new LocalPool from an [Config](crate::Config) configuration.
+	/// Create a new LocalPool.
 	//
-	pub(crate) fn new() -> Self
+	pub fn new() -> Self
 	{
 		let pool    = FutLocalPool::new();
 		let spawner = pool.spawner();
 
-		Self { pool: RefCell::new( pool ), spawner: RefCell::new( spawner ) }
+		Self { pool: Rc::new( RefCell::new( pool ) ), spawner, alive: Rc::new( Cell::new(0) ) }
 	}
 
 
-	/// Run all spawned futures to completion. Note that this does nothing for the threadpool,
-	/// but if you are using a local pool, you will need to run this or futures will not be polled.
-	/// This blocks the current thread.
+	/// Run all spawned futures to completion. This blocks the current thread.
+	///
+	/// ### Panics
+	///
+	/// Do not call this from within a task running on the same pool, it will panic.
 	//
-	pub(crate) fn run( &self )
+	pub fn run( &self )
 	{
-		self.pool.borrow_mut().run()
+		let mut pool = self.pool.borrow_mut();
+
+		loop
+		{
+			pool.run_until_stalled();
+
+			if self.alive.get() == 0
+			{
+				return;
+			}
+
+			Self::idle();
+		}
 	}
 
 
+	/// Runs all the tasks in the pool until the given future completes.
 	///
+	/// ### Panics
+	///
+	/// Do not call this from within a task running on the same pool, it will panic.
 	//
-	pub(crate) fn run_until<F: Future>( &self, future: F ) -> <F as Future>::Output
+	pub fn run_until<F: Future>( &self, future: F ) -> <F as Future>::Output
 	{
-		self.pool.borrow_mut().run_until( future )
+		let mut pool   = self.pool.borrow_mut();
+		let mut future = std::pin::pin!( future );
+		let     main   = Arc::new( MainWaker { woken: AtomicBool::new( true ), thread: thread::current() } );
+		let     waker  = waker( main.clone() );
+
+		loop
+		{
+			if main.woken.swap( false, Ordering::SeqCst )
+			{
+				if let Poll::Ready( out ) = future.as_mut().poll( &mut Context::from_waker( &waker ) )
+				{
+					return out;
+				}
+			}
+
+			pool.run_until_stalled();
+
+			if !main.woken.load( Ordering::SeqCst )
+			{
+				Self::idle();
+			}
+		}
 	}
 
 
-	///
+	/// Runs all tasks and returns after completing one future or until no more progress can be made.
+	/// Returns true if one future was completed, false otherwise.
 	//
-	pub(crate) fn try_run_one( &self ) -> bool
+	pub fn try_run_one( &self ) -> bool
 	{
 		self.pool.borrow_mut().try_run_one()
 	}
 
 
-	///
+	/// Runs all tasks in the pool and returns if no more progress can be made on any task.
 	//
-	pub(crate) fn run_until_stalled( &self )
+	pub fn run_until_stalled( &self )
 	{
 		self.pool.borrow_mut().run_until_stalled()
 	}
 
 
 
-
-	pub(crate) fn spawn( &self, fut: impl Future< Output = () > + 'static + Send ) -> Result< (), Error >
+	/// Spawn a future on this pool. See [`spawn`](crate::spawn).
+	//
+	pub fn spawn( &self, fut: impl Future< Output = () > + 'static + Send ) -> Result< (), Error >
 	{
 		self.spawn_local( fut )
 	}
 
 
 
-	pub(crate) fn spawn_local( &self, fut: impl Future< Output = () > + 'static  ) -> Result< (), Error >
+	/// Spawn a `!Send` future on this pool. See [`spawn_local`](crate::spawn_local).
+	//
+	pub fn spawn_local( &self, fut: impl Future< Output = () > + 'static  ) -> Result< (), Error >
 	{
-		self.spawner.borrow_mut().spawn_local( fut ).map_err( |_| ErrorKind::Spawn.into() )
+		let alive = Alive::new( &self.alive );
+
+		self.spawner.spawn_local( async move
+		{
+			let _alive = alive;
+			fut.await
+
+		}).map_err( |_| ErrorKind::Spawn.into() )
 	}
 
 
 
-	pub(crate) fn spawn_handle<T: 'static + Send>( &self, fut: impl Future< Output=T > + Send + 'static )
+	/// Spawn a future on this pool and get a [`JoinHandle`] to it's output. See [`spawn_handle`](crate::spawn_handle).
+	//
+	pub fn spawn_handle<T: 'static + Send>( &self, fut: impl Future< Output=T > + Send + 'static )
 
-		-> Result< Box< dyn Future< Output=T > + Send + 'static + Unpin >, Error >
+		-> Result< JoinHandle<T>, Error >
 
 	{
-		let (fut, handle) = fut.remote_handle();
+		let (fut, handle) = JoinHandle::new( fut );
 
 		self.spawn_local( fut )?;
-		Ok(Box::new( handle ))
+		Ok( handle )
 	}
 
 
 
-	pub(crate) fn spawn_handle_local<T: 'static + Send>( &self, fut: impl Future< Output=T > + 'static )
+	/// Spawn a `!Send` future on this pool and get a [`JoinHandle`] to it's output.
+	/// See [`spawn_handle_local`](crate::spawn_handle_local).
+	//
+	pub fn spawn_handle_local<T: 'static>( &self, fut: impl Future< Output=T > + 'static )
 
-		-> Result< Box< dyn Future< Output=T > + 'static + Unpin >, Error >
+		-> Result< JoinHandle<T>, Error >
 	{
-		let (fut, handle) = fut.remote_handle();
+		let (fut, handle) = JoinHandle::new( fut );
 
 		self.spawn_local( fut )?;
-		Ok(Box::new( handle ))
+		Ok( handle )
+	}
+
+
+
+	// Nothing is ready. Jump to the next timer if the clock is paused, otherwise wait for a wakeup. The pool
+	// wakes up this thread when one of it's tasks is woken.
+	//
+	fn idle()
+	{
+		if !rt::time::auto_advance()
+		{
+			thread::park();
+		}
+	}
+}
+
+
+
+impl Default for LocalPool
+{
+	fn default() -> Self
+	{
+		Self::new()
 	}
 }
 
 
+
+impl Spawn for LocalPool
+{
+	fn spawn_obj( &self, fut: FutureObj<'static, ()> ) -> Result< (), SpawnError >
+	{
+		self.spawn_local( fut ).map_err( |_| SpawnError::shutdown() )
+	}
+}
+
+
+
+impl LocalSpawn for LocalPool
+{
+	fn spawn_local_obj( &self, fut: LocalFutureObj<'static, ()> ) -> Result< (), SpawnError >
+	{
+		self.spawn_local( fut ).map_err( |_| SpawnError::shutdown() )
+	}
+}
+
+
+
 /// Run all spawned futures to completion. You must run this after spawning on the local pool or
 /// futures won't be polled. Do not call it from within a spawned task, or your program will hang or panic.
 //
 pub fn run() -> Result< (), Error >
 {
-	rt::EXEC.with( |some|
+	rt::with_exec( |some|
 	{
-		match some.get()
+		match some
 		{
 			Some(super::Executor::LocalPool(e)) => Ok ( e.run()                                 ),
 			None                                => Err( ErrorKind::NoExecutorInitialized.into() ),
@@ -124,9 +273,9 @@ pub fn run() -> Result< (), Error >
 //
 pub fn run_until<F: Future>( future: F ) -> Result< <F as Future>::Output, Error >
 {
-	rt::EXEC.with( |some|
+	rt::with_exec( |some|
 	{
-		match some.get()
+		match some
 		{
 			Some(super::Executor::LocalPool(e)) => Ok ( e.run_until( future )                   ),
 			None                                => Err( ErrorKind::NoExecutorInitialized.into() ),
@@ -141,9 +290,9 @@ pub fn run_until<F: Future>( future: F ) -> Result< <F as Future>::Output, Error
 //
 pub fn try_run_one() -> Result< bool, Error >
 {
-	rt::EXEC.with( |some|
+	rt::with_exec( |some|
 	{
-		match some.get()
+		match some
 		{
 			Some(super::Executor::LocalPool(e)) => Ok ( e.try_run_one()                         ),
 			None                                => Err( ErrorKind::NoExecutorInitialized.into() ),
@@ -157,9 +306,9 @@ pub fn try_run_one() -> Result< bool, Error >
 //
 pub fn run_until_stalled() -> Result< (), Error >
 {
-	rt::EXEC.with( |some|
+	rt::with_exec( |some|
 	{
-		match some.get()
+		match some
 		{
 			Some(super::Executor::LocalPool(e)) => Ok ( e.run_until_stalled()                   ),
 			None                                => Err( ErrorKind::NoExecutorInitialized.into() ),
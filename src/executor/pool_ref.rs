@@ -0,0 +1,67 @@
+use crate :: { import::*, Error, ErrorKind };
+use std   :: { sync::Weak };
+
+
+/// A reference to the pool behind a threadpool executor. The clones users hold own the pool. The worker threads
+/// get a weak reference, otherwise the executor installed on them would keep the pool alive, and it's threads
+/// would never stop.
+//
+#[ derive( Debug ) ]
+//
+pub(crate) enum PoolRef<T>
+{
+	Owner ( Arc <T> ),
+	Worker( Weak<T> ),
+}
+
+
+
+impl<T> PoolRef<T>
+{
+	pub(crate) fn new( pool: T ) -> Self
+	{
+		Self::Owner( Arc::new( pool ) )
+	}
+
+
+	/// A reference for the worker threads, which doesn't keep the pool alive.
+	//
+	pub(crate) fn downgrade( &self ) -> Self
+	{
+		match self
+		{
+			Self::Owner ( pool ) => Self::Worker( Arc::downgrade( pool ) ),
+			Self::Worker( pool ) => Self::Worker( pool.clone()           ),
+		}
+	}
+
+
+	/// Get the pool to spawn on.
+	///
+	/// ### Errors
+	///
+	/// Returns [`ErrorKind::ExecutorShutdown`] on a worker thread when all the clones users held are dropped.
+	//
+	pub(crate) fn get( &self ) -> Result< Arc<T>, Error >
+	{
+		match self
+		{
+			Self::Owner ( pool ) => Ok( pool.clone() ),
+			Self::Worker( pool ) => pool.upgrade().ok_or_else( || ErrorKind::ExecutorShutdown.into() ),
+		}
+	}
+}
+
+
+
+impl<T> Clone for PoolRef<T>
+{
+	fn clone( &self ) -> Self
+	{
+		match self
+		{
+			Self::Owner ( pool ) => Self::Owner ( pool.clone() ),
+			Self::Worker( pool ) => Self::Worker( pool.clone() ),
+		}
+	}
+}
@@ -0,0 +1,120 @@
+use crate :: { import::*, Error, ErrorKind };
+
+
+/// Keeps track of the tasks running on a threadpool, so we can stop accepting new ones and wait for
+/// the ones in flight to finish.
+//
+#[ derive( Debug, Default ) ]
+//
+pub(crate) struct Shutdown
+{
+	state  : Mutex< State > ,
+	changed: Condvar        ,
+}
+
+
+#[ derive( Debug, Default ) ]
+//
+struct State
+{
+	closed   : bool  ,
+	in_flight: usize ,
+}
+
+
+
+impl Shutdown
+{
+	/// Wrap a future so it get's counted as in flight until it finishes or is dropped.
+	///
+	/// ### Errors
+	///
+	/// Returns [`ErrorKind::ExecutorShutdown`] if `shutdown` was called.
+	//
+	pub(crate) fn track<T>( self: &Arc<Self>, fut: impl Future< Output = T > )
+
+		-> Result< impl Future< Output = T >, Error >
+
+	{
+		let mut state = self.state.lock().expect( "lock shutdown state" );
+
+		if state.closed
+		{
+			return Err( ErrorKind::ExecutorShutdown.into() );
+		}
+
+		state.in_flight += 1;
+
+		let guard = TaskGuard( self.clone() );
+
+		Ok( async move
+		{
+			let _guard = guard;
+			fut.await
+		})
+	}
+
+
+	/// Stop accepting new tasks and wait until the tasks in flight finish or the timeout expires.
+	/// Returns true if all tasks have finished. A timeout too big to represent, like `Duration::MAX`,
+	/// waits until the tasks finish.
+	//
+	pub(crate) fn shutdown( &self, timeout: Duration ) -> bool
+	{
+		let deadline  = Instant::now().checked_add( timeout );
+		let mut state = self.state.lock().expect( "lock shutdown state" );
+
+		state.closed = true;
+
+		let deadline = match deadline
+		{
+			Some( deadline ) => deadline,
+
+			None =>
+			{
+				while state.in_flight > 0
+				{
+					state = self.changed.wait( state ).expect( "lock shutdown state" );
+				}
+
+				return true;
+			}
+		};
+
+		while state.in_flight > 0
+		{
+			let now = Instant::now();
+
+			if now >= deadline
+			{
+				return false;
+			}
+
+			state = self.changed.wait_timeout( state, deadline - now ).expect( "lock shutdown state" ).0;
+		}
+
+		true
+	}
+}
+
+
+
+// Decrements the count of tasks in flight when the task finishes, but also when it panics or when the
+// executor drops it.
+//
+struct TaskGuard( Arc<Shutdown> );
+
+impl Drop for TaskGuard
+{
+	fn drop( &mut self )
+	{
+		let mut state = self.0.state.lock().expect( "lock shutdown state" );
+
+		state.in_flight -= 1;
+
+		if state.in_flight == 0
+		{
+			self.0.changed.notify_all();
+		}
+	}
+}
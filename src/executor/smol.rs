@@ -0,0 +1,198 @@
+use
+{
+	crate :: { self as rt, import::*, Error, ErrorKind, JoinHandle, SmolConfig } ,
+	super :: { shutdown::Shutdown, pool_ref::PoolRef } ,
+	core  :: { num::NonZeroUsize                     } ,
+};
+
+
+
+/// A threadpool running the executor from smol, `async_executor::Executor`. The worker threads drive the
+/// async-io reactor while they wait for work, so tasks can use async-io.
+///
+/// You can create one yourself and pass it to libraries that take a `T: Spawn`, or install it as the executor for
+/// the current thread with [`init_with`](crate::init_with). The worker threads will be set up to spawn on the
+/// same pool. Clones refer to the same pool.
+///
+/// The worker threads stop when all clones are dropped. Tasks that haven't finished by then are dropped. The pool
+/// installed on the worker threads doesn't keep it alive.
+//
+#[ derive( Debug, Clone ) ]
+//
+pub struct Smol
+{
+	pool    : PoolRef<Pool> ,
+	config  : SmolConfig    ,
+	shutdown: Arc<Shutdown> ,
+}
+
+
+// The worker threads run the executor until their stop channel is closed, which happens when the pool is dropped.
+//
+#[ derive( Debug ) ]
+//
+struct Pool
+{
+	executor: Arc< AsyncExecutor<'static> > ,
+	_stop   : Vec< oneshot::Sender<()> >    ,
+}
+
+
+// All threads that init with the same configuration share a threadpool.
+//
+static SMOL_POOLS: SyncLazy< Mutex< HashMap<SmolConfig, Smol> > > = SyncLazy::new( Default::default );
+
+
+impl Smol
+{
+	/// Create a new smol threadpool. This pool is independent of the pools used by [`init`](crate::init), even if
+	/// the configuration is the same.
+	///
+	/// ### Panics
+	///
+	/// When the operating system fails to create the worker threads.
+	//
+	pub fn new( config: SmolConfig ) -> Self
+	{
+		// The worker threads need to be initialized with the pool, but it does not exist yet when we
+		// start them. The worker threads wait for it to be created.
+		//
+		let cell     = Arc::new( SyncOnceCell::<PoolRef<Pool>>::new() );
+		let executor = Arc::new( AsyncExecutor::new() );
+		let shutdown = Arc::new( Shutdown::default() );
+
+		let prefix  = config.name_prefix_or( "async_runtime_smol_worker" );
+		let workers = config.workers.or_else( || thread::available_parallelism().ok() ).map_or( 1, NonZeroUsize::get );
+
+		let mut stop = Vec::with_capacity( workers );
+
+		for index in 0..workers
+		{
+			let mut builder = thread::Builder::new().name( format!( "{}{}", prefix, index ) );
+
+			if let Some( stack_size ) = config.stack_size { builder = builder.stack_size( stack_size ); }
+
+			let (tx, rx) = oneshot::channel::<()>();
+			let worker   = cell.clone();
+			let wexec    = executor.clone();
+			let wconfig  = config.clone();
+			let wshut    = shutdown.clone();
+
+			builder.spawn( move ||
+			{
+				let pool = Self { pool: worker.wait().clone(), config: wconfig, shutdown: wshut };
+
+				rt::init_with( pool ).expect( "set executor on smol working thread" );
+
+				io_block_on( wexec.run( async { let _ = rx.await; } ) );
+
+			}).expect( "spawn smol worker thread" );
+
+			stop.push( tx );
+		}
+
+		let pool = PoolRef::new( Pool { executor, _stop: stop } );
+
+		// This cannot fail, nobody else has access to the cell yet.
+		//
+		let _ = cell.set( pool.downgrade() );
+
+		Self { pool, config, shutdown }
+	}
+
+
+
+	/// Get the threadpool shared by all threads that init with this configuration. It's created if it doesn't
+	/// exist yet.
+	//
+	pub(crate) fn shared( config: SmolConfig ) -> Self
+	{
+		SMOL_POOLS.lock().expect( "lock smol pools" )
+
+			.entry( config.clone() )
+			.or_insert_with( || Self::new( config ) )
+			.clone()
+	}
+
+
+
+	/// The configuration this threadpool was created with.
+	//
+	pub fn config( &self ) -> &SmolConfig
+	{
+		&self.config
+	}
+
+
+
+	/// Stop accepting new tasks and wait until the tasks in flight finish or the timeout expires. Returns true
+	/// if all tasks finished in time. See [`shutdown`](crate::shutdown).
+	///
+	/// This affects all clones of this pool, including the ones installed on the worker threads.
+	//
+	pub fn shutdown( &self, timeout: Duration ) -> bool
+	{
+		self.shutdown.shutdown( timeout )
+	}
+
+
+
+	/// Spawn a future on this pool. See [`spawn`](crate::spawn).
+	//
+	pub fn spawn( &self, fut: impl Future< Output = () > + 'static + Send ) -> Result< (), Error >
+	{
+		let pool = self.pool.get()?;
+		let fut  = self.shutdown.track( fut )?;
+
+		pool.executor.spawn( fut ).detach();
+		Ok(())
+	}
+
+
+
+	/// This always returns [`ErrorKind::SpawnLocalOnThreadPool`]. It exists so the API is the same for all
+	/// executors.
+	//
+	pub fn spawn_local( &self, _: impl Future< Output = () > + 'static  ) -> Result< (), Error >
+	{
+		Err( ErrorKind::SpawnLocalOnThreadPool.into() )
+	}
+
+
+
+	/// Spawn a future on this pool and get a [`JoinHandle`] to it's output. See [`spawn_handle`](crate::spawn_handle).
+	//
+	pub fn spawn_handle<T: 'static + Send>( &self, fut: impl Future< Output=T > + Send + 'static )
+
+		-> Result< JoinHandle<T>, Error >
+
+	{
+		let (fut, handle) = JoinHandle::new( fut );
+
+		self.spawn( fut )?;
+		Ok( handle )
+	}
+
+
+
+	/// This always returns [`ErrorKind::SpawnLocalOnThreadPool`]. It exists so the API is the same for all
+	/// executors.
+	//
+	pub fn spawn_handle_local<T: 'static>( &self, _: impl Future< Output=T > + 'static )
+
+		-> Result< JoinHandle<T>, Error >
+
+	{
+		Err( ErrorKind::SpawnLocalOnThreadPool.into() )
+	}
+}
+
+
+
+impl Spawn for Smol
+{
+	fn spawn_obj( &self, fut: FutureObj<'static, ()> ) -> Result< (), SpawnError >
+	{
+		self.spawn( fut ).map_err( |_| SpawnError::shutdown() )
+	}
+}
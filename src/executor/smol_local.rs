@@ -0,0 +1,188 @@
+//! Provides functionality specific to the smol local executor.
+
+use crate :: { self as rt, import::*, Error, JoinHandle, ErrorKind };
+
+
+/// An executor that runs futures on the current thread, using the local executor from smol,
+/// `async_executor::LocalExecutor`. While it waits for tasks to wake up, it drives the async-io reactor.
+///
+/// You can create one yourself and pass it to libraries that take a `T: Spawn` or `T: LocalSpawn`, or install it as
+/// the executor for the current thread with [`init_with`](crate::init_with). Clones refer to the same executor.
+///
+/// Spawned futures will only be polled when you run the executor.
+//
+#[ derive( Debug, Clone, Default ) ]
+//
+pub struct SmolLocal
+{
+	executor: Rc< AsyncLocalExecutor<'static> >,
+}
+
+
+
+impl SmolLocal
+{
+	/// Create a new smol local executor.
+	//
+	pub fn new() -> Self
+	{
+		Self::default()
+	}
+
+
+	/// Run all spawned futures to completion. This blocks the current thread.
+	///
+	/// ### Panics
+	///
+	/// Do not call this from within a task running on the same executor.
+	//
+	pub fn run( &self )
+	{
+		io_block_on( async
+		{
+			while !self.executor.is_empty()
+			{
+				self.executor.tick().await;
+			}
+		})
+	}
+
+
+	/// Runs all the tasks until the given future completes.
+	///
+	/// ### Panics
+	///
+	/// Do not call this from within a task running on the same executor.
+	//
+	pub fn run_until<F: Future>( &self, future: F ) -> <F as Future>::Output
+	{
+		io_block_on( self.executor.run( future ) )
+	}
+
+
+	/// Runs all tasks and returns when no more progress can be made on any task.
+	//
+	pub fn run_until_stalled( &self )
+	{
+		while self.executor.try_tick() {}
+	}
+
+
+
+	/// Spawn a future on this executor. See [`spawn`](crate::spawn).
+	//
+	pub fn spawn( &self, fut: impl Future< Output = () > + 'static + Send ) -> Result< (), Error >
+	{
+		self.spawn_local( fut )
+	}
+
+
+
+	/// Spawn a `!Send` future on this executor. See [`spawn_local`](crate::spawn_local).
+	//
+	pub fn spawn_local( &self, fut: impl Future< Output = () > + 'static  ) -> Result< (), Error >
+	{
+		self.executor.spawn( fut ).detach();
+		Ok(())
+	}
+
+
+
+	/// Spawn a future on this executor and get a [`JoinHandle`] to it's output. See [`spawn_handle`](crate::spawn_handle).
+	//
+	pub fn spawn_handle<T: 'static + Send>( &self, fut: impl Future< Output=T > + Send + 'static )
+
+		-> Result< JoinHandle<T>, Error >
+
+	{
+		let (fut, handle) = JoinHandle::new( fut );
+
+		self.spawn_local( fut )?;
+		Ok( handle )
+	}
+
+
+
+	/// Spawn a `!Send` future on this executor and get a [`JoinHandle`] to it's output.
+	/// See [`spawn_handle_local`](crate::spawn_handle_local).
+	//
+	pub fn spawn_handle_local<T: 'static>( &self, fut: impl Future< Output=T > + 'static )
+
+		-> Result< JoinHandle<T>, Error >
+	{
+		let (fut, handle) = JoinHandle::new( fut );
+
+		self.spawn_local( fut )?;
+		Ok( handle )
+	}
+}
+
+
+
+impl Spawn for SmolLocal
+{
+	fn spawn_obj( &self, fut: FutureObj<'static, ()> ) -> Result< (), SpawnError >
+	{
+		self.spawn_local( fut ).map_err( |_| SpawnError::shutdown() )
+	}
+}
+
+
+
+impl LocalSpawn for SmolLocal
+{
+	fn spawn_local_obj( &self, fut: LocalFutureObj<'static, ()> ) -> Result< (), SpawnError >
+	{
+		self.spawn_local( fut ).map_err( |_| SpawnError::shutdown() )
+	}
+}
+
+
+
+/// Run all spawned futures to completion. You must run this after spawning on the smol local executor or
+/// futures won't be polled. Do not call it from within a spawned task, or your program will hang or panic.
+//
+pub fn run() -> Result< (), Error >
+{
+	rt::with_exec( |some|
+	{
+		match some
+		{
+			Some(super::Executor::SmolLocal(e)) => { e.run(); Ok(()) }
+			None                                => Err( ErrorKind::NoExecutorInitialized.into() ),
+			_                                   => Err( ErrorKind::WrongExecutor.into()         ),
+		}
+	})
+}
+
+
+/// Runs all the tasks until the given future completes.
+//
+pub fn run_until<F: Future>( future: F ) -> Result< <F as Future>::Output, Error >
+{
+	rt::with_exec( |some|
+	{
+		match some
+		{
+			Some(super::Executor::SmolLocal(e)) => Ok ( e.run_until( future )                   ),
+			None                                => Err( ErrorKind::NoExecutorInitialized.into() ),
+			_                                   => Err( ErrorKind::WrongExecutor.into()         ),
+		}
+	})
+}
+
+
+/// Runs all tasks and returns when no more progress can be made on any task.
+//
+pub fn run_until_stalled() -> Result< (), Error >
+{
+	rt::with_exec( |some|
+	{
+		match some
+		{
+			Some(super::Executor::SmolLocal(e)) => { e.run_until_stalled(); Ok(()) }
+			None                                => Err( ErrorKind::NoExecutorInitialized.into() ),
+			_                                   => Err( ErrorKind::WrongExecutor.into()         ),
+		}
+	})
+}
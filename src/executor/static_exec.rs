@@ -0,0 +1,371 @@
+//! Provides functionality specific to the static executor.
+//!
+//! The static executor is a single executor for the whole program. It only needs `alloc`, so it works on
+//! bare-metal targets when you turn off the default `std` feature. Tasks can be spawned and woken from interrupt
+//! handlers: waking only stores to atomics and spawning never waits for a lock that the interrupted code might hold.
+//!
+//! The executor is driven by whoever calls [`run`] or [`run_until_stalled`]. Don't call these from an interrupt
+//! handler. With std, [`run`] parks the thread while no task is ready. Without std it spins, so on a
+//! microcontroller you probably want to call [`run_until_stalled`] from your main loop and put the core to sleep
+//! when [`is_woken`] returns `false`. Check it with interrupts disabled, so a wake up can not slip in between:
+//!
+//! ```ignore
+//! use async_runtime as rt;
+//!
+//! rt::init( rt::Config::Static ).expect( "no double executor init" );
+//!
+//! loop
+//! {
+//!    rt::static_exec::run_until_stalled().expect( "run static executor" );
+//!
+//!    cortex_m::interrupt::free( |_| if !rt::static_exec::is_woken() { cortex_m::asm::wfi() } );
+//! }
+//! ```
+//!
+//! The executor can not run `!Send` futures, since an interrupt handler or another thread might spawn them.
+//!
+//! With the `std` feature it runs the same code, so you can test code that uses it on your development machine.
+
+use crate :: { self as rt, import::*, Error, JoinHandle, ErrorKind };
+
+
+// The number of queues new tasks can go to. An interrupt can only preempt code that holds one queue, so with nested
+// interrupts each level finds a free one. Beyond that, spawning spins until a queue is released.
+//
+const QUEUES: usize = 4;
+
+type Task = ( Arc<Flag>, FutureObj<'static, ()> );
+
+
+// The tasks owned by the executor. Only the code that runs the executor locks this.
+//
+static TASKS: SpinMutex< Vec<Task> > = SpinMutex::new( Vec::new() );
+
+// Newly spawned tasks. Spawning tries the queues in turn, so it never waits on a queue that the code it interrupted
+// holds.
+//
+static INCOMING: [ SpinMutex< Vec<Task> >; QUEUES ] =
+[
+	SpinMutex::new( Vec::new() ),
+	SpinMutex::new( Vec::new() ),
+	SpinMutex::new( Vec::new() ),
+	SpinMutex::new( Vec::new() ),
+];
+
+// Whether a task was spawned or woken since the executor last looked.
+//
+static WOKEN: AtomicBool = AtomicBool::new( false );
+
+
+// Unparks the thread that is running the executor, so wake ups from other threads reach it. The runner registers
+// it before parking. `AtomicWaker` doesn't lock, so waking stays fine from an interrupt handler.
+//
+#[ cfg( feature = "std" ) ]
+//
+static RUNNER: AtomicWaker = AtomicWaker::new();
+
+
+
+/// The static executor. This is just a handle, all handles refer to the same global executor. It only needs
+/// `alloc`, and you can spawn on it and wake it's tasks from interrupt handlers.
+///
+/// You can pass it to libraries that take a `T: Spawn`, or install it as the executor with
+/// [`init_with`](crate::init_with).
+///
+/// Spawned futures will only be polled when you run the executor.
+//
+#[ derive( Debug, Clone, Copy, Default, PartialEq, Eq, Hash ) ]
+//
+pub struct Static;
+
+
+
+impl Static
+{
+	/// Get a handle to the static executor.
+	//
+	pub fn new() -> Self
+	{
+		Self
+	}
+
+
+	/// Run the tasks until they are all finished. With std, this parks the thread while no task is ready.
+	/// Without std, it spins.
+	///
+	/// ### Errors
+	///
+	/// Returns [`ErrorKind::Run`] if the executor is already running, eg. if you call this from within a task.
+	//
+	pub fn run( &self ) -> Result< (), Error >
+	{
+		loop
+		{
+			if poll_tasks()? == 0 && !self.is_woken()
+			{
+				return Ok(());
+			}
+
+			if !self.is_woken()
+			{
+				idle();
+			}
+		}
+	}
+
+
+	/// Poll the tasks until none of them are ready. This returns as soon as all tasks are waiting.
+	///
+	/// ### Errors
+	///
+	/// Returns [`ErrorKind::Run`] if the executor is already running, eg. if you call this from within a task.
+	//
+	pub fn run_until_stalled( &self ) -> Result< (), Error >
+	{
+		poll_tasks().map( |_| () )
+	}
+
+
+	/// Whether a task was spawned or woken since the executor last looked. When this returns `false` after
+	/// [`run_until_stalled`](Static::run_until_stalled), there is nothing to do until the next interrupt.
+	//
+	pub fn is_woken( &self ) -> bool
+	{
+		WOKEN.load( Ordering::Acquire )
+	}
+
+
+	/// Spawn a future on the static executor. See [`spawn`](crate::spawn). This boxes the future and can be called
+	/// from an interrupt handler.
+	//
+	pub fn spawn( &self, fut: impl Future< Output = () > + 'static + Send ) -> Result< (), Error >
+	{
+		// New tasks start out woken, so they get polled a first time.
+		//
+		let flag = Arc::new( Flag { woken: AtomicBool::new( true ) } );
+		let task = ( flag, FutureObj::new( Box::new( fut ) ) );
+
+		// Cycle never ends, so this spins until one of the queues is free.
+		//
+		let mut queue = INCOMING.iter().cycle().find_map( SpinMutex::try_lock ).expect( "cycle never ends" );
+
+		queue.push( task );
+		drop( queue );
+
+		notify();
+		Ok(())
+	}
+
+
+	/// Always returns `Err(`[`ErrorKind::SpawnLocalOnThreadPool`]`)`. The static executor can be used from other
+	/// threads and interrupt handlers, so it can not hold `!Send` futures.
+	//
+	pub fn spawn_local( &self, _: impl Future< Output = () > + 'static ) -> Result< (), Error >
+	{
+		Err( ErrorKind::SpawnLocalOnThreadPool.into() )
+	}
+
+
+	/// Spawn a future on the static executor and get a [`JoinHandle`] to it's output. See
+	/// [`spawn_handle`](crate::spawn_handle).
+	//
+	pub fn spawn_handle<T: 'static + Send>( &self, fut: impl Future< Output=T > + Send + 'static )
+
+		-> Result< JoinHandle<T>, Error >
+
+	{
+		let (fut, handle) = JoinHandle::new( fut );
+
+		self.spawn( fut )?;
+		Ok( handle )
+	}
+
+
+	/// Always returns `Err(`[`ErrorKind::SpawnLocalOnThreadPool`]`)`. See [`spawn_local`](Static::spawn_local).
+	//
+	pub fn spawn_handle_local<T: 'static>( &self, _: impl Future< Output=T > + 'static )
+
+		-> Result< JoinHandle<T>, Error >
+
+	{
+		Err( ErrorKind::SpawnLocalOnThreadPool.into() )
+	}
+}
+
+
+
+impl Spawn for Static
+{
+	fn spawn_obj( &self, fut: FutureObj<'static, ()> ) -> Result< (), SpawnError >
+	{
+		self.spawn( fut ).map_err( |_| SpawnError::shutdown() )
+	}
+}
+
+
+
+// Tells the executor a task can make progress. This is what the waker of each task points to. Waking only
+// stores to atomics, so it's fine from an interrupt handler.
+//
+#[ derive( Debug ) ]
+//
+struct Flag
+{
+	woken: AtomicBool,
+}
+
+
+impl ArcWake for Flag
+{
+	fn wake_by_ref( this: &Arc<Self> )
+	{
+		this.woken.store( true, Ordering::Release );
+
+		notify();
+	}
+}
+
+
+
+// Let the executor know there is work. With std, unpark the thread running it.
+//
+fn notify()
+{
+	WOKEN.store( true, Ordering::Release );
+
+	#[ cfg( feature = "std" ) ]
+	//
+	RUNNER.wake();
+}
+
+
+
+// Wait for a wake up. Spurious returns are fine, the caller checks again. We check `WOKEN` after registering, so
+// a wake up that comes in between unparks us rather than getting lost.
+//
+#[ cfg( feature = "std" ) ]
+//
+fn idle()
+{
+	RUNNER.register( &waker( Arc::new( Unpark( thread::current() ) ) ) );
+
+	if !WOKEN.load( Ordering::Acquire )
+	{
+		thread::park();
+	}
+}
+
+
+#[ cfg(not( feature = "std" )) ]
+//
+fn idle()
+{
+	core::hint::spin_loop();
+}
+
+
+
+// The waker the runner registers before parking.
+//
+#[ cfg( feature = "std" ) ]
+//
+struct Unpark( thread::Thread );
+
+
+#[ cfg( feature = "std" ) ]
+//
+impl ArcWake for Unpark
+{
+	fn wake_by_ref( this: &Arc<Self> )
+	{
+		this.0.unpark();
+	}
+}
+
+
+
+// Poll the woken tasks until none are left. Returns the number of tasks that are still pending.
+//
+fn poll_tasks() -> Result< usize, Error >
+{
+	let mut tasks = TASKS.try_lock().ok_or_else( || Error::from( ErrorKind::Run ) )?;
+
+	loop
+	{
+		WOKEN.store( false, Ordering::Release );
+
+		for queue in &INCOMING
+		{
+			tasks.append( &mut queue.lock() );
+		}
+
+		let mut progress = false;
+		let mut i        = 0;
+
+		while i < tasks.len()
+		{
+			let ( flag, fut ) = &mut tasks[i];
+
+			if flag.woken.swap( false, Ordering::AcqRel )
+			{
+				progress = true;
+
+				let waker = waker( flag.clone() );
+
+				if Pin::new( fut ).poll( &mut Context::from_waker( &waker ) ).is_ready()
+				{
+					tasks.swap_remove( i );
+					continue;
+				}
+			}
+
+			i += 1;
+		}
+
+		if !progress
+		{
+			return Ok( tasks.len() );
+		}
+	}
+}
+
+
+
+/// Run the tasks on the static executor until they are all finished. See [`Static::run`].
+//
+pub fn run() -> Result< (), Error >
+{
+	rt::with_exec( |some|
+	{
+		match some
+		{
+			Some(super::Executor::Static(e)) => e.run()                                      ,
+			None                             => Err( ErrorKind::NoExecutorInitialized.into() ),
+			_                                => Err( ErrorKind::WrongExecutor.into()         ),
+		}
+	})
+}
+
+
+/// Poll the tasks on the static executor until none of them are ready. See [`Static::run_until_stalled`].
+//
+pub fn run_until_stalled() -> Result< (), Error >
+{
+	rt::with_exec( |some|
+	{
+		match some
+		{
+			Some(super::Executor::Static(e)) => e.run_until_stalled()                        ,
+			None                             => Err( ErrorKind::NoExecutorInitialized.into() ),
+			_                                => Err( ErrorKind::WrongExecutor.into()         ),
+		}
+	})
+}
+
+
+/// Whether a task was spawned or woken since the static executor last looked. See [`Static::is_woken`].
+//
+pub fn is_woken() -> bool
+{
+	Static.is_woken()
+}
@@ -1,80 +1,174 @@
-use crate :: { self as rt, import::*, Error, ErrorKind };
+use
+{
+	crate :: { self as rt, import::*, Error, ErrorKind, JoinHandle, ThreadPoolConfig } ,
+	super :: { shutdown::Shutdown, pool_ref::PoolRef } ,
+};
 
 
 
 /// A threapool from the futures library.
+///
+/// You can create one yourself and pass it to libraries that take a `T: Spawn`, or install it as the executor for
+/// the current thread with [`init_with`](crate::init_with). The worker threads will be set up to spawn on the
+/// same pool. Clones refer to the same pool.
+///
+/// The worker threads stop when all clones are dropped and the tasks in flight have finished. The pool installed
+/// on the worker threads doesn't keep it alive. Once you dropped the pool, tasks that still run on it can no
+/// longer spawn on it.
 //
-#[ derive( Debug, Default, Clone ) ]
+#[ derive( Debug, Clone ) ]
 //
-pub(crate) struct ThreadPool {}
+pub struct ThreadPool
+{
+	pool    : PoolRef<FutThreadPool> ,
+	config  : ThreadPoolConfig       ,
+	shutdown: Arc<Shutdown>          ,
+}
 
-static THREADPOOL: SyncOnceCell<FutThreadPool> = SyncOnceCell::new();
+
+// All threads that init with the same configuration share a threadpool.
+//
+static THREADPOOLS: SyncLazy< Mutex< HashMap<ThreadPoolConfig, ThreadPool> > > = SyncLazy::new( Default::default );
 
 
 impl ThreadPool
 {
-	/// Create a new ThreadPool from a [Config](rt::Config) configuration.
+	/// Create a new ThreadPool. This pool is independent of the pools used by [`init`](crate::init), even if the
+	/// configuration is the same.
+	///
+	/// ### Panics
+	///
+	/// When the operating system fails to create the worker threads.
 	//
-	pub(crate) fn new() -> Self
+	pub fn new( config: ThreadPoolConfig ) -> Self
 	{
-		// We create one global threadpool executor. Then we set the worker threads of this executor
-		// to spawn on the same ThreadPool again. This means that while constructing the global ThreadPool,
-		// we will re-enter this constructor for each worker thread.
+		// The worker threads need to be initialized with the pool, but it does not exist yet when we
+		// set up the builder. The worker threads wait for it to be created.
 		//
-		THREADPOOL.get_or_init( ||
+		let cell     = Arc::new( SyncOnceCell::<PoolRef<FutThreadPool>>::new() );
+		let worker   = cell.clone();
+		let wconfig  = config.clone();
+		let shutdown = Arc::new( Shutdown::default() );
+		let wshut    = shutdown.clone();
+
+		let mut builder = FutThreadPool::builder();
 
-			FutThreadPool::builder()
+		builder
 
-				.name_prefix( "async_runtime_threadpool_worker" )
+			.name_prefix( config.name_prefix_or( "async_runtime_threadpool_worker" ) )
 
-				.after_start( |_|
-				{
-					rt::init( rt::Config::ThreadPool ).expect( "set executor on juliex working thread" );
-				})
+			.after_start( move |_|
+			{
+				let pool = Self { pool: worker.wait().clone(), config: wconfig.clone(), shutdown: wshut.clone() };
 
-				.create().expect( "Create futures threadpool" )
+				rt::init_with( pool ).expect( "set executor on threadpool working thread" );
+			})
+		;
 
-		);
+		if let Some( workers    ) = config.workers    { builder.pool_size ( workers.get() ); }
+		if let Some( stack_size ) = config.stack_size { builder.stack_size( stack_size    ); }
 
-		Self {}
+		let pool = PoolRef::new( builder.create().expect( "Create futures threadpool" ) );
+
+		// This cannot fail, nobody else has access to the cell yet.
+		//
+		let _ = cell.set( pool.downgrade() );
+
+		Self { pool, config, shutdown }
 	}
 
 
 
-	pub(crate) fn spawn( &self, fut: impl Future< Output = () > + 'static + Send ) -> Result< (), Error >
+	/// Get the threadpool shared by all threads that init with this configuration. It's created if it doesn't
+	/// exist yet.
+	//
+	pub(crate) fn shared( config: ThreadPoolConfig ) -> Self
 	{
-		// We can unwrap, since the constructor guarantees that the pool is created, we are sure it exists.
-		//
-		THREADPOOL.get().unwrap().spawn( fut ).map_err( |_| ErrorKind::Spawn.into() )
+		THREADPOOLS.lock().expect( "lock threadpools" )
+
+			.entry( config.clone() )
+			.or_insert_with( || Self::new( config ) )
+			.clone()
+	}
+
+
+
+	/// The configuration this threadpool was created with.
+	//
+	pub fn config( &self ) -> &ThreadPoolConfig
+	{
+		&self.config
 	}
 
 
 
-	pub(crate) fn spawn_local( &self, _: impl Future< Output = () > + 'static  ) -> Result< (), Error >
+	/// Stop accepting new tasks and wait until the tasks in flight finish or the timeout expires. Returns true
+	/// if all tasks finished in time. See [`shutdown`](crate::shutdown).
+	///
+	/// This affects all clones of this pool, including the ones installed on the worker threads.
+	//
+	pub fn shutdown( &self, timeout: Duration ) -> bool
+	{
+		self.shutdown.shutdown( timeout )
+	}
+
+
+
+	/// Spawn a future on this pool. See [`spawn`](crate::spawn).
+	//
+	pub fn spawn( &self, fut: impl Future< Output = () > + 'static + Send ) -> Result< (), Error >
+	{
+		let pool = self.pool.get()?;
+		let fut  = self.shutdown.track( fut )?;
+
+		pool.spawn( fut ).map_err( |_| ErrorKind::Spawn.into() )
+	}
+
+
+
+	/// This always returns [`ErrorKind::SpawnLocalOnThreadPool`]. It exists so the API is the same for all
+	/// executors.
+	//
+	pub fn spawn_local( &self, _: impl Future< Output = () > + 'static  ) -> Result< (), Error >
 	{
 		Err( ErrorKind::SpawnLocalOnThreadPool.into() )
 	}
 
 
 
-	pub(crate) fn spawn_handle<T: 'static + Send>( &self, fut: impl Future< Output=T > + Send + 'static )
+	/// Spawn a future on this pool and get a [`JoinHandle`] to it's output. See [`spawn_handle`](crate::spawn_handle).
+	//
+	pub fn spawn_handle<T: 'static + Send>( &self, fut: impl Future< Output=T > + Send + 'static )
 
-		-> Result< Box< dyn Future< Output=T > + Send + 'static + Unpin >, Error >
+		-> Result< JoinHandle<T>, Error >
 
 	{
-		let (fut, handle) = fut.remote_handle();
+		let (fut, handle) = JoinHandle::new( fut );
 
 		self.spawn( fut )?;
-		Ok(Box::new( handle ))
+		Ok( handle )
 	}
 
 
 
-	pub(crate) fn spawn_handle_local<T: 'static + Send>( &self, _: impl Future< Output=T > + 'static )
+	/// This always returns [`ErrorKind::SpawnLocalOnThreadPool`]. It exists so the API is the same for all
+	/// executors.
+	//
+	pub fn spawn_handle_local<T: 'static>( &self, _: impl Future< Output=T > + 'static )
 
-		-> Result< Box< dyn Future< Output=T > + 'static + Unpin >, Error >
+		-> Result< JoinHandle<T>, Error >
 
 	{
 		Err( ErrorKind::SpawnLocalOnThreadPool.into() )
 	}
 }
+
+
+
+impl Spawn for ThreadPool
+{
+	fn spawn_obj( &self, fut: FutureObj<'static, ()> ) -> Result< (), SpawnError >
+	{
+		self.spawn( fut ).map_err( |_| SpawnError::shutdown() )
+	}
+}
@@ -1,87 +1,161 @@
 //! Provides TokioCt executor specific functionality.
 
-use crate :: { self as rt, import::*, Error, ErrorKind };
+use crate :: { self as rt, import::*, Error, JoinHandle, ErrorKind, TokioCtConfig };
+
+// The clock tokio uses for it's timers, to set it in `TokioCtConfig::clock`.
+//
+pub use tokio_timer::clock::{ Clock, Now };
 
 
-/// An executor that uses [tokio::runtime::current_thread::Runtime]
+/// An executor that uses [tokio::runtime::current_thread::Runtime].
+///
+/// You can create one yourself and pass it to libraries that take a `T: Spawn` or `T: LocalSpawn`, or install it as
+/// the executor for the current thread with [`init_with`](crate::init_with). Clones refer to the same runtime.
+///
+/// Spawned futures will only be polled when you run the runtime.
 //
-#[ derive( Debug ) ]
+#[ derive( Debug, Clone ) ]
 //
-pub(crate) struct TokioCt
+pub struct TokioCt
 {
-	runtime: RefCell< TokioCtRuntime >,
+	runtime: Rc< RefCell< TokioCtRuntime > >,
+	config : TokioCtConfig                  ,
 }
 
 
 
 impl TokioCt
 {
-	/// Create a new TokioCt from an [Config](crate::Config) configuration.
+	/// Create a new tokio current thread runtime.
+	///
+	/// ### Panics
+	///
+	/// When tokio fails to create the runtime.
+	//
+	pub fn new( config: TokioCtConfig ) -> Self
+	{
+		let mut builder = TokioCtBuilder::new();
+
+		if let Some( clock ) = config.get_clock() { builder.clock( clock.clone() ); }
+
+		let runtime = builder.build().expect( "create tokio ct runtime" );
+
+		Self { runtime: Rc::new( RefCell::new( runtime ) ), config }
+	}
+
+
+	/// The configuration this runtime was created with.
 	//
-	pub(crate) fn new() -> Self
+	pub fn config( &self ) -> &TokioCtConfig
 	{
-		Self { runtime: RefCell::new( TokioCtRuntime::new().expect( "create tokio ct runtime" ) ) }
+		&self.config
 	}
 
 
-	/// Run all spawned futures to completion. Note that this does nothing for the threadpool,
-	/// but if you are using a local pool, you will need to run this or futures will not be polled.
-	/// This blocks the current thread.
+	/// Run all spawned futures to completion. This blocks the current thread.
+	///
+	/// ### Panics
+	///
+	/// Do not call this from within a task running on the same runtime, it will panic.
 	//
-	pub(crate) fn run( &self ) -> Result< (), Error >
+	pub fn run( &self ) -> Result< (), Error >
 	{
 		self.runtime.borrow_mut().run().map_err( |_| ErrorKind::Run.into() )
 	}
 
 
-	pub(crate) fn spawn( &self, fut: impl Future< Output = () > + 'static + Send ) -> Result< (), Error >
+	/// Spawn a future on this runtime. See [`spawn`](crate::spawn).
+	//
+	pub fn spawn( &self, fut: impl Future< Output = () > + 'static + Send ) -> Result< (), Error >
 	{
 		self.spawn_local( fut )
 	}
 
 
 
-	pub(crate) fn spawn_local( &self, fut: impl Future< Output = () > + 'static  ) -> Result< (), Error >
+	/// Spawn a `!Send` future on this runtime. See [`spawn_local`](crate::spawn_local).
+	//
+	pub fn spawn_local( &self, fut: impl Future< Output = () > + 'static  ) -> Result< (), Error >
 	{
-		self.runtime.borrow_mut().spawn( fut );
-		Ok(())
+		match self.runtime.try_borrow_mut()
+		{
+			Ok( mut runtime ) =>
+			{
+				runtime.spawn( fut );
+				Ok(())
+			}
+
+			// The runtime is running, so we are being called from within a task. Tokio let's us spawn on the
+			// running executor.
+			//
+			Err(_) =>
+			{
+				TokioCtExecutor::current().spawn_local( Box::pin( fut ) ).map_err( |_| ErrorKind::Spawn.into() )
+			}
+		}
 	}
 
 
 
-	pub(crate) fn spawn_handle<T: 'static + Send>( &self, fut: impl Future< Output=T > + Send + 'static )
+	/// Spawn a future on this runtime and get a [`JoinHandle`] to it's output. See [`spawn_handle`](crate::spawn_handle).
+	//
+	pub fn spawn_handle<T: 'static + Send>( &self, fut: impl Future< Output=T > + Send + 'static )
 
-		-> Result< Box< dyn Future< Output=T > + Send + 'static + Unpin >, Error >
+		-> Result< JoinHandle<T>, Error >
 
 	{
-		let (fut, handle) = fut.remote_handle();
+		let (fut, handle) = JoinHandle::new( fut );
 
 		self.spawn_local( fut )?;
-		Ok(Box::new( handle ))
+		Ok( handle )
 	}
 
 
 
-	pub(crate) fn spawn_handle_local<T: 'static + Send>( &self, fut: impl Future< Output=T > + 'static )
+	/// Spawn a `!Send` future on this runtime and get a [`JoinHandle`] to it's output.
+	/// See [`spawn_handle_local`](crate::spawn_handle_local).
+	//
+	pub fn spawn_handle_local<T: 'static>( &self, fut: impl Future< Output=T > + 'static )
 
-		-> Result< Box< dyn Future< Output=T > + 'static + Unpin >, Error >
+		-> Result< JoinHandle<T>, Error >
 	{
-		let (fut, handle) = fut.remote_handle();
+		let (fut, handle) = JoinHandle::new( fut );
 
 		self.spawn_local( fut )?;
-		Ok(Box::new( handle ))
+		Ok( handle )
 	}
 }
 
 
+
+impl Spawn for TokioCt
+{
+	fn spawn_obj( &self, fut: FutureObj<'static, ()> ) -> Result< (), SpawnError >
+	{
+		self.spawn_local( fut ).map_err( |_| SpawnError::shutdown() )
+	}
+}
+
+
+
+impl LocalSpawn for TokioCt
+{
+	fn spawn_local_obj( &self, fut: LocalFutureObj<'static, ()> ) -> Result< (), SpawnError >
+	{
+		self.spawn_local( fut ).map_err( |_| SpawnError::shutdown() )
+	}
+}
+
+
+
 /// Run all spawned futures to completion. You must run this after spawning on the local pool or
 /// futures won't be polled. Do not call it from within a spawned task, or your program will hang or panic.
 //
 pub fn run() -> Result< (), Error >
 {
-	rt::EXEC.with( |some|
+	rt::with_exec( |some|
 	{
-		match some.get()
+		match some
 		{
 			Some(super::Executor::TokioCt(e)) => e.run()                                       ,
 			None                              => Err( ErrorKind::NoExecutorInitialized.into() ),
@@ -0,0 +1,197 @@
+//! Provides TokioLocal executor specific functionality.
+
+use crate :: { self as rt, import::*, Error, JoinHandle, ErrorKind };
+
+
+/// An executor that uses the tokio 1.x current thread runtime, `tokio::runtime::Builder::new_current_thread`,
+/// combined with a `tokio::task::LocalSet`, so it can spawn `!Send` futures. Tasks run inside the runtime, so
+/// they can use tokio timers and IO, as long as those features of tokio are enabled.
+///
+/// You can create one yourself and pass it to libraries that take a `T: Spawn` or `T: LocalSpawn`, or install it as
+/// the executor for the current thread with [`init_with`](crate::init_with). Clones refer to the same runtime.
+///
+/// Spawned futures will only be polled when you run the runtime.
+//
+#[ derive( Debug, Clone ) ]
+//
+pub struct TokioLocal
+{
+	runtime: Rc< TokioLocalRuntime   >,
+	local  : Rc< RefCell< LocalSet > >,
+}
+
+
+
+impl TokioLocal
+{
+	/// Create a new tokio current thread runtime. All the drivers that are compiled into tokio are enabled.
+	///
+	/// ### Panics
+	///
+	/// When tokio fails to create the runtime.
+	//
+	pub fn new() -> Self
+	{
+		let runtime = TokioLocalBuilder::new_current_thread().enable_all().build().expect( "create tokio local runtime" );
+
+		Self { runtime: Rc::new( runtime ), local: Rc::new( RefCell::new( LocalSet::new() ) ) }
+	}
+
+
+	/// Run all spawned futures to completion. This blocks the current thread.
+	///
+	/// ### Panics
+	///
+	/// Do not call this from within a task running on the same runtime, it will panic.
+	//
+	pub fn run( &self )
+	{
+		let mut local = self.local.borrow_mut();
+
+		self.runtime.block_on( &mut *local )
+	}
+
+
+	/// Runs all the tasks until the given future completes.
+	///
+	/// ### Panics
+	///
+	/// Do not call this from within a task running on the same runtime, it will panic.
+	//
+	pub fn run_until<F: Future>( &self, future: F ) -> <F as Future>::Output
+	{
+		let local = self.local.borrow();
+
+		self.runtime.block_on( local.run_until( future ) )
+	}
+
+
+	/// Spawn a future on this runtime. See [`spawn`](crate::spawn).
+	//
+	pub fn spawn( &self, fut: impl Future< Output = () > + 'static + Send ) -> Result< (), Error >
+	{
+		self.spawn_local( fut )
+	}
+
+
+
+	/// Spawn a `!Send` future on this runtime. See [`spawn_local`](crate::spawn_local).
+	//
+	pub fn spawn_local( &self, fut: impl Future< Output = () > + 'static  ) -> Result< (), Error >
+	{
+		match self.local.try_borrow()
+		{
+			Ok( local ) =>
+			{
+				// Dropping the tokio handle detaches the task.
+				//
+				local.spawn_local( fut );
+				Ok(())
+			}
+
+			// `run` is running, so we are being called from within a task. Tokio let's us spawn on the
+			// running LocalSet.
+			//
+			Err(_) =>
+			{
+				tokio_spawn_local( fut );
+				Ok(())
+			}
+		}
+	}
+
+
+
+	/// Spawn a future on this runtime and get a [`JoinHandle`] to it's output. See [`spawn_handle`](crate::spawn_handle).
+	//
+	pub fn spawn_handle<T: 'static + Send>( &self, fut: impl Future< Output=T > + Send + 'static )
+
+		-> Result< JoinHandle<T>, Error >
+
+	{
+		let (fut, handle) = JoinHandle::new( fut );
+
+		self.spawn_local( fut )?;
+		Ok( handle )
+	}
+
+
+
+	/// Spawn a `!Send` future on this runtime and get a [`JoinHandle`] to it's output.
+	/// See [`spawn_handle_local`](crate::spawn_handle_local).
+	//
+	pub fn spawn_handle_local<T: 'static>( &self, fut: impl Future< Output=T > + 'static )
+
+		-> Result< JoinHandle<T>, Error >
+	{
+		let (fut, handle) = JoinHandle::new( fut );
+
+		self.spawn_local( fut )?;
+		Ok( handle )
+	}
+}
+
+
+
+impl Default for TokioLocal
+{
+	fn default() -> Self
+	{
+		Self::new()
+	}
+}
+
+
+
+impl Spawn for TokioLocal
+{
+	fn spawn_obj( &self, fut: FutureObj<'static, ()> ) -> Result< (), SpawnError >
+	{
+		self.spawn_local( fut ).map_err( |_| SpawnError::shutdown() )
+	}
+}
+
+
+
+impl LocalSpawn for TokioLocal
+{
+	fn spawn_local_obj( &self, fut: LocalFutureObj<'static, ()> ) -> Result< (), SpawnError >
+	{
+		self.spawn_local( fut ).map_err( |_| SpawnError::shutdown() )
+	}
+}
+
+
+
+/// Run all spawned futures to completion. You must run this after spawning on the tokio local runtime or
+/// futures won't be polled. Do not call it from within a spawned task, or your program will panic.
+//
+pub fn run() -> Result< (), Error >
+{
+	rt::with_exec( |some|
+	{
+		match some
+		{
+			Some(super::Executor::TokioLocal(e)) => { e.run(); Ok(()) }
+			None                                 => Err( ErrorKind::NoExecutorInitialized.into() ),
+			_                                    => Err( ErrorKind::WrongExecutor.into()         ),
+		}
+	})
+}
+
+
+/// Runs all the tasks until the given future completes. The future runs inside the runtime and the `LocalSet`, so it
+/// can use tokio timers and call `tokio::task::spawn_local`.
+//
+pub fn run_until<F: Future>( future: F ) -> Result< <F as Future>::Output, Error >
+{
+	rt::with_exec( |some|
+	{
+		match some
+		{
+			Some(super::Executor::TokioLocal(e)) => Ok ( e.run_until( future )                   ),
+			None                                 => Err( ErrorKind::NoExecutorInitialized.into() ),
+			_                                    => Err( ErrorKind::WrongExecutor.into()         ),
+		}
+	})
+}
@@ -0,0 +1,226 @@
+use
+{
+	crate :: { self as rt, import::*, Error, ErrorKind, JoinHandle, TokioTpConfig } ,
+	super :: { shutdown::Shutdown, pool_ref::PoolRef                               } ,
+	std   :: { sync::atomic::AtomicUsize                                           } ,
+};
+
+
+
+/// The tokio 1.x threadpool runtime, `tokio::runtime::Builder::new_multi_thread`. Tasks run inside the runtime, so
+/// they can use the tokio reactor and timers. All the drivers that are compiled into tokio are enabled.
+///
+/// You can create one yourself and pass it to libraries that take a `T: Spawn`, or install it as the executor for
+/// the current thread with [`init_with`](crate::init_with). The worker threads will be set up to spawn on the
+/// same runtime. Clones refer to the same runtime.
+///
+/// The worker threads stop when all clones are dropped. Tasks that haven't finished by then are dropped. The runtime
+/// installed on the worker threads doesn't keep it alive.
+//
+#[ derive( Debug, Clone ) ]
+//
+pub struct TokioTp
+{
+	runtime : PoolRef<Runtime> ,
+	config  : TokioTpConfig    ,
+	shutdown: Arc<Shutdown>    ,
+}
+
+
+// Tokio panics when a runtime is dropped from within an async context, which happens when a task drops the last
+// clone. Shutting down in the background works from anywhere.
+//
+#[ derive( Debug ) ]
+//
+struct Runtime( Option<TokioTpRuntime> );
+
+impl Runtime
+{
+	fn get( &self ) -> &TokioTpRuntime
+	{
+		self.0.as_ref().expect( "the tokio runtime is only taken out on drop" )
+	}
+}
+
+impl Drop for Runtime
+{
+	fn drop( &mut self )
+	{
+		if let Some( runtime ) = self.0.take()
+		{
+			runtime.shutdown_background();
+		}
+	}
+}
+
+
+// All threads that init with the same configuration share a runtime.
+//
+static TOKIO_RUNTIMES: SyncLazy< Mutex< HashMap<TokioTpConfig, TokioTp> > > = SyncLazy::new( Default::default );
+
+
+impl TokioTp
+{
+	/// Create a new tokio threadpool runtime. This runtime is independent of the ones used by
+	/// [`init`](crate::init), even if the configuration is the same.
+	///
+	/// ### Panics
+	///
+	/// When tokio fails to create the runtime.
+	//
+	pub fn new( config: TokioTpConfig ) -> Self
+	{
+		// The worker threads need to be initialized with the runtime, but it does not exist yet when we
+		// set up the builder. The worker threads wait for it to be created.
+		//
+		let cell     = Arc::new( SyncOnceCell::<PoolRef<Runtime>>::new() );
+		let worker   = cell.clone();
+		let wconfig  = config.clone();
+		let shutdown = Arc::new( Shutdown::default() );
+		let wshut    = shutdown.clone();
+		let prefix   = config.name_prefix_or( "async_runtime_tokio_worker" ).to_string();
+		let index    = AtomicUsize::new( 0 );
+
+		let mut builder = TokioTpBuilder::new_multi_thread();
+
+		builder
+
+			.enable_all()
+
+			.thread_name_fn( move || format!( "{}{}", prefix, index.fetch_add( 1, Ordering::Relaxed ) ) )
+
+			.on_thread_start( move ||
+			{
+				let runtime = Self { runtime: worker.wait().clone(), config: wconfig.clone(), shutdown: wshut.clone() };
+
+				rt::init_with( runtime ).expect( "set executor on tokio working thread" );
+			})
+		;
+
+		if let Some( workers    ) = config.workers    { builder.worker_threads   ( workers.get() ); }
+		if let Some( stack_size ) = config.stack_size { builder.thread_stack_size( stack_size    ); }
+
+		let runtime = PoolRef::new( Runtime( Some( builder.build().expect( "create tokio threadpool runtime" ) ) ) );
+
+		// This cannot fail, nobody else has access to the cell yet.
+		//
+		let _ = cell.set( runtime.downgrade() );
+
+		Self { runtime, config, shutdown }
+	}
+
+
+
+	/// Get the runtime shared by all threads that init with this configuration. It's created if it doesn't
+	/// exist yet.
+	//
+	pub(crate) fn shared( config: TokioTpConfig ) -> Self
+	{
+		TOKIO_RUNTIMES.lock().expect( "lock tokio runtimes" )
+
+			.entry( config.clone() )
+			.or_insert_with( || Self::new( config ) )
+			.clone()
+	}
+
+
+
+	/// The configuration this runtime was created with.
+	//
+	pub fn config( &self ) -> &TokioTpConfig
+	{
+		&self.config
+	}
+
+
+
+	/// Stop accepting new tasks and wait until the tasks in flight finish or the timeout expires. Returns true
+	/// if all tasks finished in time. See [`shutdown`](crate::shutdown).
+	///
+	/// This affects all clones of this runtime, including the ones installed on the worker threads.
+	//
+	pub fn shutdown( &self, timeout: Duration ) -> bool
+	{
+		self.shutdown.shutdown( timeout )
+	}
+
+
+
+	/// Spawn a future on this runtime. See [`spawn`](crate::spawn).
+	//
+	pub fn spawn( &self, fut: impl Future< Output = () > + 'static + Send ) -> Result< (), Error >
+	{
+		let runtime = self.runtime.get()?;
+		let fut     = self.shutdown.track( fut )?;
+
+		// Dropping the tokio handle detaches the task.
+		//
+		runtime.get().spawn( fut );
+		Ok(())
+	}
+
+
+
+	/// This always returns [`ErrorKind::SpawnLocalOnThreadPool`]. It exists so the API is the same for all
+	/// executors.
+	//
+	pub fn spawn_local( &self, _: impl Future< Output = () > + 'static  ) -> Result< (), Error >
+	{
+		Err( ErrorKind::SpawnLocalOnThreadPool.into() )
+	}
+
+
+
+	/// Spawn a future on this runtime and get tokio's own `JoinHandle` to it's output. Unlike
+	/// [`spawn_handle`](crate::spawn_handle), dropping the handle detaches the task, and a panic in the task is
+	/// reported as a `tokio::task::JoinError`.
+	//
+	pub fn spawn_handle<T: 'static + Send>( &self, fut: impl Future< Output=T > + Send + 'static )
+
+		-> Result< TokioJoinHandle<T>, Error >
+
+	{
+		let runtime = self.runtime.get()?;
+		let fut     = self.shutdown.track( fut )?;
+
+		Ok( runtime.get().spawn( fut ) )
+	}
+
+
+
+	// `rt::spawn_handle` returns the same JoinHandle on all executors, so it doesn't use the tokio one.
+	//
+	pub(crate) fn spawn_rt_handle<T: 'static + Send>( &self, fut: impl Future< Output=T > + Send + 'static )
+
+		-> Result< JoinHandle<T>, Error >
+
+	{
+		let (fut, handle) = JoinHandle::new( fut );
+
+		self.spawn( fut )?;
+		Ok( handle )
+	}
+
+
+
+	/// This always returns [`ErrorKind::SpawnLocalOnThreadPool`]. It exists so the API is the same for all
+	/// executors.
+	//
+	pub fn spawn_handle_local<T: 'static>( &self, _: impl Future< Output=T > + 'static )
+
+		-> Result< JoinHandle<T>, Error >
+
+	{
+		Err( ErrorKind::SpawnLocalOnThreadPool.into() )
+	}
+}
+
+
+
+impl Spawn for TokioTp
+{
+	fn spawn_obj( &self, fut: FutureObj<'static, ()> ) -> Result< (), SpawnError >
+	{
+		self.spawn( fut ).map_err( |_| SpawnError::shutdown() )
+	}
+}
@@ -0,0 +1,186 @@
+use crate :: { import::*, Error, ErrorKind, Executor, JoinHandle, task::Task };
+
+
+type Job = Box< dyn FnOnce( &Executor ) + Send + 'static >;
+
+
+
+/// A handle to the executor of a thread, which you can send to other threads to spawn on it. Get one with
+/// [`handle`](crate::handle). Clones refer to the same executor.
+///
+/// This lets worker threads post work to a thread that runs a single threaded executor, like the main thread
+/// of a GUI running a _localpool_. The handle can only spawn `Send` futures, but once they run, they are on the
+/// thread of the executor, so they can use [`spawn_local`](crate::spawn_local).
+///
+/// For single threaded executors, the handle sends the futures to a task on the executor, which spawns them.
+/// Sending wakes up that task, so an executor that waits for work gets woken up. The executor must be running
+/// for the futures to be spawned, and as long as handles exist, `run` on that executor will not return, since
+/// more work might come in. Use `run_until` if you want to stop earlier. For threadpools, the handle spawns on
+/// the threadpool directly.
+///
+/// Like a clone of the executor, the handle keeps the executor alive until all handles are dropped.
+///
+/// ### Example
+#[ cfg_attr( feature = "localpool", doc = r##"
+```
+use { async_runtime as rt, std::thread };
+
+rt::init( rt::Config::LocalPool ).expect( "no double executor init" );
+
+let handle = rt::handle().expect( "get executor handle" );
+
+let worker = thread::spawn( move ||
+{
+   handle.spawn_handle( async { thread::current().id() } ).expect( "spawn on the main thread" )
+});
+
+let task = worker.join().expect( "join worker" );
+let id   = rt::localpool::run_until( task ).expect( "run localpool" ).expect( "join task" );
+
+assert_eq!( thread::current().id(), id );
+```
+"##)]
+//
+#[ derive( Clone ) ]
+//
+pub struct ExecutorHandle
+{
+	target: Target,
+}
+
+
+#[ derive( Clone ) ]
+//
+enum Target
+{
+	// Executors that can be used from any thread. We create an `Executor` from a clone each time we spawn.
+	//
+	Shared( Arc< dyn Fn() -> Executor + Send + Sync > ),
+
+	// Executors that are tied to their thread. A task on the executor spawns the jobs sent here.
+	//
+	Remote( mpsc::UnboundedSender<Job> ),
+}
+
+
+
+impl ExecutorHandle
+{
+	// A handle to an executor that can be used from any thread.
+	//
+	pub(crate) fn shared<E>( exec: E ) -> Self
+
+		where E: Into<Executor> + Clone + Send + Sync + 'static
+
+	{
+		Self { target: Target::Shared( Arc::new( move || exec.clone().into() ) ) }
+	}
+
+
+	// A handle to an executor that only runs on it's own thread. This spawns a task on the executor that
+	// receives the futures and spawns them. The task ends when all handles are dropped.
+	//
+	pub(crate) fn remote( exec: &Executor, location: &'static Location<'static> ) -> Result< Self, Error >
+	{
+		let (tx, rx) = mpsc::unbounded::<Job>();
+		let owner    = exec.clone();
+
+		let forward = rx.for_each( move |job|
+		{
+			job( &owner );
+			future::ready(())
+		});
+
+		exec.spawn_local( Task::new( None, location ), forward )?;
+
+		Ok( Self { target: Target::Remote( tx ) } )
+	}
+
+
+	/// Spawn a future on the executor this handle refers to. See [`spawn`](crate::spawn).
+	///
+	/// ### Errors
+	///
+	/// - Returns the same errors as spawning on the executor directly.
+	/// - For single threaded executors, returns [`ErrorKind::Spawn`] if the executor dropped the task that receives
+	///   the futures. Errors that happen when the future is spawned on the other thread can not be reported. With
+	///   [`spawn_handle`](ExecutorHandle::spawn_handle), the handle will tell you the task was cancelled.
+	//
+	#[ track_caller ]
+	//
+	pub fn spawn( &self, fut: impl Future< Output=() > + Send + 'static ) -> Result< (), Error >
+	{
+		self.spawn_task( Task::new( None, Location::caller() ), fut )
+	}
+
+
+	/// Spawn a future on the executor this handle refers to and get a [`JoinHandle`] to it's output. See
+	/// [`spawn_handle`](crate::spawn_handle).
+	///
+	/// ### Errors
+	///
+	/// See [`spawn`](ExecutorHandle::spawn).
+	//
+	#[ track_caller ]
+	//
+	pub fn spawn_handle<T: Send + 'static>( &self, fut: impl Future< Output=T > + Send + 'static )
+
+		-> Result< JoinHandle<T>, Error >
+
+	{
+		let task          = Task::new( None, Location::caller() );
+		let info          = task.clone();
+		let (fut, handle) = JoinHandle::new( fut );
+
+		self.spawn_task( task, fut )?;
+
+		Ok( handle.with_task( info ) )
+	}
+
+
+	fn spawn_task( &self, task: Task, fut: impl Future< Output=() > + Send + 'static ) -> Result< (), Error >
+	{
+		match &self.target
+		{
+			Target::Shared( exec ) => exec().spawn( task, fut ),
+
+			// If the executor drops the job or fails to spawn, the future is dropped, so a JoinHandle reports
+			// the task as cancelled.
+			//
+			Target::Remote( tx ) =>
+			{
+				let job: Job = Box::new( move |exec| { let _ = exec.spawn( task, fut ); } );
+
+				tx.unbounded_send( job ).map_err( |_| ErrorKind::Spawn.into() )
+			}
+		}
+	}
+}
+
+
+
+impl Spawn for ExecutorHandle
+{
+	#[ track_caller ]
+	//
+	fn spawn_obj( &self, fut: FutureObj<'static, ()> ) -> Result< (), SpawnError >
+	{
+		self.spawn( fut ).map_err( |_| SpawnError::shutdown() )
+	}
+}
+
+
+
+impl fmt::Debug for ExecutorHandle
+{
+	fn fmt( &self, f: &mut fmt::Formatter<'_> ) -> fmt::Result
+	{
+		let target = match self.target
+		{
+			Target::Shared(_) => "shared" ,
+			Target::Remote(_) => "remote" ,
+		};
+
+		f.debug_struct( "ExecutorHandle" ).field( "target", &target ).finish()
+	}
+}
@@ -0,0 +1,412 @@
+use crate :: { import::*, task::Task };
+
+
+// The output of the task, or the panic payload.
+//
+type Output<T> = Result< T, Box<dyn Any + Send + 'static> >;
+
+
+
+/// A handle to a task spawned with [`spawn_handle`](crate::spawn_handle) or
+/// [`spawn_handle_local`](crate::spawn_handle_local). This is the same type whatever executor is configured
+/// for the thread.
+///
+/// Await the handle to recover the output of the task. If the task panicked or was cancelled, either by
+/// [`JoinHandle::abort`] or because the executor dropped it, you will get a [`JoinError`] instead.
+///
+/// When the handle is dropped, the task is cancelled. This is true on all executors, including _async-std_.
+/// If you want the task to keep running without holding on to the handle, call [`JoinHandle::detach`].
+///
+/// The handle is `Send` when the output is. With [`spawn_handle_local`](crate::spawn_handle_local) the output may be
+/// `!Send`, like an `Rc`, and the handle must then be awaited on the same thread.
+//
+#[ must_use = "JoinHandle cancels the task when dropped, use `detach` to let it run in the background" ]
+//
+pub struct JoinHandle<T>
+{
+	rx      : oneshot::Receiver< Output<T> > ,
+	abort   : AbortHandle                  ,
+	finished: Arc<AtomicBool>              ,
+	detached: bool                         ,
+	joined  : bool                         ,
+	task    : Option<Task>                 ,
+}
+
+
+
+impl<T> JoinHandle<T>
+{
+	/// Wrap a future so it can be spawned. The returned future must be spawned on an executor. The handle
+	/// will receive it's output.
+	//
+	// For the case where we compile without an executor enabled, this will be unused.
+	//
+	#[ allow( dead_code ) ]
+	//
+	pub(crate) fn new( fut: impl Future< Output=T > ) -> ( impl Future< Output=() >, Self )
+	{
+		let (slot, handle) = Self::pair();
+
+		( slot.wrap( fut ), handle )
+	}
+
+
+
+	// Create the handle before the future exists. The slot can be sent to the thread that will create and
+	// spawn the future, so the future itself doesn't have to be `Send`.
+	//
+	pub(crate) fn pair() -> ( TaskSlot<T>, Self )
+	{
+		let (tx, rx)              = oneshot::channel();
+		let (abort, registration) = AbortHandle::new_pair();
+		let finished              = Arc::new( AtomicBool::new( false ) );
+		let guard                 = FinishGuard( finished.clone() );
+
+		let slot   = TaskSlot { tx, registration, guard };
+		let handle = Self { rx, abort, finished, detached: false, joined: false, task: None };
+
+		( slot, handle )
+	}
+
+
+
+	// Remember which task this is a handle to, so we can report it in the JoinError.
+	//
+	pub(crate) fn with_task( mut self, task: Task ) -> Self
+	{
+		self.task = Some( task );
+		self
+	}
+
+
+
+	/// The task this is a handle to. This is `None` for tasks spawned directly on an executor object, like
+	/// `ThreadPool::spawn_handle`.
+	//
+	pub fn task( &self ) -> Option<&Task>
+	{
+		self.task.as_ref()
+	}
+
+
+
+	/// Cancel the task. The task will not be polled again and will be dropped by the executor as soon as
+	/// possible. If it has already finished, this does nothing.
+	//
+	pub fn abort( &self )
+	{
+		self.abort.abort();
+	}
+
+
+
+	/// Drop the handle without cancelling the task. It will run to completion in the background and it's
+	/// output will be dropped.
+	//
+	pub fn detach( mut self )
+	{
+		self.detached = true;
+	}
+
+
+
+	/// Whether the task has finished. This is also true if the task was cancelled. Note that this does
+	/// not mean that the output has been recovered yet.
+	//
+	pub fn is_finished( &self ) -> bool
+	{
+		self.finished.load( Ordering::Acquire )
+	}
+
+
+
+	/// Recover the output of the task without blocking. Returns `None` if the task has not finished yet.
+	/// Once the output has been recovered, this will always return `None`.
+	//
+	pub fn try_join( &mut self ) -> Option< Result<T, JoinError> >
+	{
+		if self.joined
+		{
+			return None;
+		}
+
+		let output = match self.rx.try_recv()
+		{
+			Ok (None        ) => return None                        ,
+			Ok (Some(output)) => output.map_err( |p| JoinError::panic( p, self.task.clone() ) ) ,
+			Err(_           ) => Err( JoinError::cancelled( self.task.clone() ) )           ,
+		};
+
+		self.joined = true;
+		Some( output )
+	}
+}
+
+
+
+impl<T> Future for JoinHandle<T>
+{
+	type Output = Result<T, JoinError>;
+
+	/// ### Panics
+	///
+	/// Polling the handle after it returned `Poll::Ready` will panic.
+	//
+	fn poll( mut self: Pin<&mut Self>, cx: &mut Context<'_> ) -> Poll<Self::Output>
+	{
+		assert!( !self.joined, "async_runtime::JoinHandle polled after completion." );
+
+		let output = match ready!( Pin::new( &mut self.rx ).poll( cx ) )
+		{
+			Ok (output) => output.map_err( |p| JoinError::panic( p, self.task.clone() ) ) ,
+			Err(_     ) => Err( JoinError::cancelled( self.task.clone() ) )           ,
+		};
+
+		self.joined = true;
+		Poll::Ready( output )
+	}
+}
+
+
+
+impl<T> Drop for JoinHandle<T>
+{
+	fn drop( &mut self )
+	{
+		if !self.detached
+		{
+			self.abort.abort();
+		}
+	}
+}
+
+
+
+impl<T> fmt::Debug for JoinHandle<T>
+{
+	fn fmt( &self, f: &mut fmt::Formatter<'_> ) -> fmt::Result
+	{
+		f.debug_struct( "JoinHandle" )
+
+			.field( "finished", &self.is_finished() )
+			.field( "detached", &self.detached      )
+			.field( "task"    , &self.task          )
+
+		.finish()
+	}
+}
+
+
+
+/// The error returned by a [`JoinHandle`] when the task did not run to completion.
+//
+pub struct JoinError
+{
+	kind : JoinErrorKind                           ,
+	panic: Option< Box<dyn Any + Send + 'static> > ,
+	task : Option<Task>                            ,
+}
+
+
+
+/// The reason why a task did not run to completion.
+//
+#[ derive( Copy, Clone, PartialEq, Eq, Debug ) ]
+//
+pub enum JoinErrorKind
+{
+	/// The task panicked. The panic payload can be recovered with [`JoinError::into_panic`].
+	//
+	Panic,
+
+	/// The task was cancelled, either with [`JoinHandle::abort`] or because the executor dropped it
+	/// before it finished.
+	//
+	Cancelled,
+
+	/// Protect against adding other options being breaking changes.
+	//
+	__Nonexhaustive,
+}
+
+
+
+impl JoinError
+{
+	fn panic( payload: Box<dyn Any + Send + 'static>, task: Option<Task> ) -> Self
+	{
+		Self { kind: JoinErrorKind::Panic, panic: Some( payload ), task }
+	}
+
+
+	fn cancelled( task: Option<Task> ) -> Self
+	{
+		Self { kind: JoinErrorKind::Cancelled, panic: None, task }
+	}
+
+
+	/// Allows matching on the error kind.
+	//
+	pub fn kind( &self ) -> &JoinErrorKind
+	{
+		&self.kind
+	}
+
+
+	/// The task that failed, with it's name and where it was spawned. See [`JoinHandle::task`].
+	//
+	pub fn task( &self ) -> Option<&Task>
+	{
+		self.task.as_ref()
+	}
+
+
+	/// Whether the task panicked.
+	//
+	pub fn is_panic( &self ) -> bool
+	{
+		self.kind == JoinErrorKind::Panic
+	}
+
+
+	/// Whether the task was cancelled.
+	//
+	pub fn is_cancelled( &self ) -> bool
+	{
+		self.kind == JoinErrorKind::Cancelled
+	}
+
+
+	/// Recover the panic payload. You can pass it to [`std::panic::resume_unwind`] to continue
+	/// unwinding in the current task. Returns `None` if the task was cancelled.
+	//
+	pub fn into_panic( self ) -> Option< Box<dyn Any + Send + 'static> >
+	{
+		self.panic
+	}
+
+
+	// Panic payloads are usually a `&str` or a `String`.
+	//
+	fn panic_message( &self ) -> Option<&str>
+	{
+		let payload = self.panic.as_ref()?;
+
+		payload.downcast_ref::<&str>().copied()
+
+			.or_else( || payload.downcast_ref::<String>().map( String::as_str ) )
+	}
+}
+
+
+
+#[ cfg( feature = "std" ) ]
+//
+impl StdError for JoinError {}
+
+
+
+impl fmt::Display for JoinError
+{
+	fn fmt( &self, f: &mut fmt::Formatter<'_> ) -> fmt::Result
+	{
+		match self.kind
+		{
+			JoinErrorKind::Panic => match self.panic_message()
+			{
+				Some(msg) => write!( f, "JoinError: The task panicked: {}", msg )?,
+				None      => write!( f, "JoinError: The task panicked." )?,
+			}
+
+			JoinErrorKind::Cancelled => write!( f, "JoinError: The task was cancelled before it finished." )?,
+
+			_ => unreachable!(),
+		}
+
+		match &self.task
+		{
+			Some(task) => write!( f, " ({})", task ),
+			None       => Ok(())                    ,
+		}
+	}
+}
+
+
+
+impl fmt::Debug for JoinError
+{
+	fn fmt( &self, f: &mut fmt::Formatter<'_> ) -> fmt::Result
+	{
+		f.debug_struct( "JoinError" )
+
+			.field( "kind" , &self.kind            )
+			.field( "panic", &self.panic_message() )
+			.field( "task" , &self.task            )
+
+		.finish()
+	}
+}
+
+
+
+// The side of a JoinHandle that goes with the task. See `JoinHandle::pair`.
+//
+pub(crate) struct TaskSlot<T>
+{
+	tx          : oneshot::Sender< Output<T> > ,
+	registration: AbortRegistration            ,
+	guard       : FinishGuard                  ,
+}
+
+
+impl<T> TaskSlot<T>
+{
+	// Wrap the future so it's output goes to the handle.
+	//
+	pub(crate) fn wrap( self, fut: impl Future< Output=T > ) -> impl Future< Output=() >
+	{
+		let Self { tx, registration, guard } = self;
+
+		// The guard is dropped when the task completes, but also when it get's aborted or when the executor
+		// drops it.
+		//
+		// We catch panics so they don't take down worker threads of threadpools and so they can be
+		// reported to the handle rather than being swallowed.
+		//
+		let task = Abortable::new( async move
+		{
+			let _guard = guard;
+
+			#[ cfg( feature = "std" ) ]
+			//
+			let output = AssertUnwindSafe( fut ).catch_unwind().await;
+
+			// Without std we can not catch panics.
+			//
+			#[ cfg(not( feature = "std" )) ]
+			//
+			let output = Ok( fut.await );
+
+			// If the handle was dropped, nobody is interested in the output.
+			//
+			let _ = tx.send( output );
+
+		}, registration );
+
+		task.map( |_| () )
+	}
+}
+
+
+
+// Marks the task as finished when dropped.
+//
+struct FinishGuard( Arc<AtomicBool> );
+
+impl Drop for FinishGuard
+{
+	fn drop( &mut self )
+	{
+		self.0.store( true, Ordering::Release );
+	}
+}
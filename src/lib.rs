@@ -6,6 +6,7 @@
 
 
 
+#![ cfg_attr( not( feature = "std" ), no_std ) ]
 #![ doc    ( html_root_url = "https://docs.rs/naja_async_runtime" ) ]
 #![ deny   ( missing_docs                                         ) ]
 #![ forbid ( unsafe_code                                          ) ]
@@ -27,24 +28,107 @@
 )]
 
 
+extern crate alloc;
+
+
 mod import
 {
 	pub(crate) use
 	{
-		once_cell :: { unsync::OnceCell                                   } ,
-		std       :: { cfg, fmt, future::Future, error::Error as StdError } ,
+		core      :: { fmt, future::Future, hash::{ Hash, Hasher }        } ,
+		core      :: { pin::Pin, task::{ Context, Poll }                  } ,
+		core      :: { sync::atomic::{ AtomicBool, Ordering }             } ,
+		core      :: { any::Any, panic::Location, time::Duration          } ,
+		alloc     :: { sync::Arc, boxed::Box, vec::Vec, string::String    } ,
+		futures   :: { ready, channel::oneshot                            } ,
+		futures   :: { future::{ FutureExt, AbortHandle, Abortable }      } ,
+		futures   :: { future::AbortRegistration                          } ,
+		futures   :: { stream::{ Stream, FuturesUnordered }               } ,
+		futures   :: { task::{ Spawn, SpawnError, FutureObj }             } ,
+		futures   :: { task::{ LocalSpawn, LocalFutureObj }               } ,
+	};
+
+
+
+	#[ cfg( feature = "std" ) ]
+	//
+	pub(crate) use
+	{
+		once_cell :: { unsync::OnceCell, sync::OnceCell as SyncOnceCell   } ,
+		std       :: { cfg, error::Error as StdError                      } ,
+		std       :: { panic::AssertUnwindSafe, thread                    } ,
+		std       :: { cell::RefCell, rc::Rc, task::Waker                 } ,
+		std       :: { sync::{ Mutex, MutexGuard }                        } ,
+		std       :: { collections::BTreeMap, string::ToString            } ,
+		futures   :: { future::{ self, Either }                           } ,
+		futures   :: { channel::mpsc, StreamExt                           } ,
 	};
 
 
-	#[ cfg(any( feature = "bindgen", feature = "localpool", feature = "juliex", feature = "threadpool", feature = "tokio_ct" )) ]
+	// Targets like thumbv7m don't have 64 bit atomics.
+	//
+	#[ cfg( target_has_atomic = "64" ) ]
 	//
-	pub(crate) use	futures::future::FutureExt;
+	pub(crate) use
+	{
+		core :: { sync::atomic::AtomicU64 } ,
+	};
 
 
-	#[ cfg(any( feature = "localpool", feature = "tokio_ct" )) ]
+	#[ cfg(not( target_has_atomic = "64" )) ]
 	//
-	pub(crate) use	std::cell::RefCell;
+	pub(crate) use
+	{
+		core :: { sync::atomic::AtomicUsize } ,
+	};
+
 
+	#[ cfg( feature = "static_exec" ) ]
+	//
+	pub(crate) use
+	{
+		spin :: { mutex::SpinMutex } ,
+	};
+
+
+	#[ cfg( not( feature = "std" ) ) ]
+	//
+	pub(crate) use
+	{
+		once_cell :: { race::OnceBox } ,
+	};
+
+
+	#[ cfg(all( feature = "std", any( not( target_arch = "wasm32" ), feature = "metrics" ) )) ]
+	//
+	pub(crate) use
+	{
+		once_cell :: { sync::Lazy as SyncLazy } ,
+	};
+
+
+	#[ cfg(all( feature = "std", not( target_arch = "wasm32" ) )) ]
+	//
+	pub(crate) use
+	{
+		std :: { collections::VecDeque, sync::Condvar } ,
+	};
+
+
+	#[ cfg(any( feature = "threadpool", feature = "tokio_tp", feature = "smol", feature = "metrics" )) ]
+	//
+	pub(crate) use
+	{
+		std :: { collections::HashMap } ,
+	};
+
+
+	#[ cfg( feature = "metrics" ) ]
+	//
+	pub(crate) use
+	{
+		std :: { thread::ThreadId } ,
+	};
 
 
 	#[ cfg( feature = "bindgen" ) ]
@@ -60,6 +144,7 @@ mod import
 	pub(crate) use
 	{
 		futures :: { task::LocalSpawnExt, executor::{ LocalPool as FutLocalPool, LocalSpawner } } ,
+		std     :: { cell::Cell                                                                 } ,
 	};
 
 
@@ -67,15 +152,31 @@ mod import
 	//
 	pub(crate) use
 	{
-		futures :: { task::SpawnExt, executor::{ ThreadPool as FutThreadPool } } ,
+		futures   :: { task::SpawnExt, executor::{ ThreadPool as FutThreadPool } } ,
+	};
+
+
+	#[ cfg(any( feature = "juliex", feature = "threadpool", feature = "tokio_tp", feature = "smol" )) ]
+	//
+	pub(crate) use
+	{
+		std :: { time::Instant } ,
+	};
+
+
+	#[ cfg(any( feature = "localpool", feature = "deterministic", feature = "static_exec" )) ]
+	//
+	pub(crate) use
+	{
+		futures :: { task::{ ArcWake, waker } } ,
 	};
 
 
-	#[ cfg(any( feature = "juliex", feature = "threadpool" )) ]
+	#[ cfg(all( feature = "static_exec", feature = "std" )) ]
 	//
 	pub(crate) use
 	{
-		once_cell :: { sync::OnceCell as SyncOnceCell } ,
+		futures :: { task::AtomicWaker } ,
 	};
 
 
@@ -83,7 +184,36 @@ mod import
 	//
 	pub(crate) use
 	{
-		tokio :: { runtime::current_thread::Runtime as TokioCtRuntime } ,
+		tokio :: { runtime::current_thread::{ Runtime as TokioCtRuntime, Builder as TokioCtBuilder } } ,
+		tokio :: { runtime::current_thread::TaskExecutor as TokioCtExecutor                          } ,
+		tokio_timer :: { clock::Clock as TokioClock                                                   } ,
+	};
+
+
+	#[ cfg( feature = "tokio_local" ) ]
+	//
+	pub(crate) use
+	{
+		tokio1 :: { runtime::{ Runtime as TokioLocalRuntime, Builder as TokioLocalBuilder } } ,
+		tokio1 :: { task::{ LocalSet, spawn_local as tokio_spawn_local }                    } ,
+	};
+
+
+	#[ cfg( feature = "tokio_tp" ) ]
+	//
+	pub(crate) use
+	{
+		tokio1 :: { runtime::{ Runtime as TokioTpRuntime, Builder as TokioTpBuilder } } ,
+		tokio1 :: { task::JoinHandle as TokioJoinHandle                             } ,
+	};
+
+
+	#[ cfg( feature = "smol" ) ]
+	//
+	pub(crate) use
+	{
+		async_executor :: { Executor as AsyncExecutor, LocalExecutor as AsyncLocalExecutor } ,
+		async_io       :: { block_on as io_block_on                                        } ,
 	};
 }
 
@@ -92,17 +222,70 @@ mod import
 // --- API --- //
 /////////////////
 
-mod error    ;
-mod config   ;
-mod executor ;
+mod error       ;
+mod config      ;
+mod backend     ;
+mod executor    ;
+mod join_handle ;
+mod nursery     ;
+
+pub mod task;
+
+#[ cfg( feature = "std"                                       ) ] pub mod time;
+#[ cfg(all( feature = "std", not( target_arch = "wasm32" ) )) ]     mod blocking;
+#[ cfg( feature = "localpool"                                 ) ]     mod pinned;
+#[ cfg( feature = "std"                                       ) ]     mod handle;
+#[ cfg(all( feature = "std", not( target_arch = "wasm32" ) )) ] pub mod thread;
+#[ cfg( feature = "metrics"                                   ) ] pub mod metrics;
+#[ cfg( feature = "tracing"                                   ) ]     mod trace;
+#[ cfg(all( feature = "std", not( target_arch = "wasm32" ) )) ] pub use blocking::spawn_blocking;
+#[ cfg( feature = "localpool"                                 ) ] pub use pinned::spawn_pinned;
+#[ cfg( feature = "std"                                       ) ] pub use handle::ExecutorHandle;
 
 pub use error::*;
 pub use config::*;
+pub use backend    ::{ Backend, Capabilities                   };
+pub use join_handle::{ JoinHandle, JoinError, JoinErrorKind };
+pub use nursery    ::{ Nursery, CollectAll                     };
+pub use task       ::{ Builder                                  };
 
 
 #[ cfg( feature = "localpool" ) ] pub use executor::localpool ;
 #[ cfg( feature = "async_std" ) ] pub use executor::async_std ;
 #[ cfg( feature = "tokio_ct"  ) ] pub use executor::tokio_ct  ;
+#[ cfg( feature = "tokio_local") ] pub use executor::tokio_local;
+#[ cfg( feature = "smol"      ) ] pub use executor::smol_local;
+#[ cfg( feature = "static_exec") ] pub use executor::static_exec;
+
+#[ cfg( feature = "deterministic" ) ] pub use executor::deterministic ;
+
+pub use executor::{ Executor, CustomBackend };
+
+
+// Paths used by our exported macros, so they don't depend on how the caller names `std`.
+//
+#[ cfg( feature = "std" ) ]
+//
+#[ doc( hidden ) ]
+//
+pub mod __private
+{
+	pub use std :: { thread_local, cell::RefCell };
+}
+
+#[ cfg( feature = "localpool"  ) ] pub use executor::LocalPool  ;
+#[ cfg( feature = "threadpool" ) ] pub use executor::ThreadPool ;
+#[ cfg( feature = "juliex"     ) ] pub use executor::Juliex     ;
+#[ cfg( feature = "async_std"  ) ] pub use executor::AsyncStd   ;
+#[ cfg( feature = "tokio_ct"   ) ] pub use executor::TokioCt    ;
+#[ cfg( feature = "tokio_local") ] pub use executor::TokioLocal ;
+#[ cfg( feature = "tokio_tp"   ) ] pub use executor::TokioTp    ;
+#[ cfg( feature = "smol"       ) ] pub use executor::Smol       ;
+#[ cfg( feature = "smol"       ) ] pub use executor::SmolLocal  ;
+#[ cfg( feature = "bindgen"    ) ] pub use executor::Bindgen    ;
+#[ cfg( feature = "static_exec") ] pub use executor::Static     ;
+
+#[ cfg( feature = "deterministic" ) ] pub use executor::Deterministic ;
 
 
 #[ cfg(all( feature = "macros", feature = "juliex"     )) ] pub use naja_runtime_macros::juliex     ;
@@ -111,27 +294,156 @@ pub use config::*;
 #[ cfg(all( feature = "macros", feature = "localpool"  )) ] pub use naja_runtime_macros::localpool  ;
 #[ cfg(all( feature = "macros", feature = "bindgen"    )) ] pub use naja_runtime_macros::bindgen    ;
 #[ cfg(all( feature = "macros", feature = "tokio_ct"   )) ] pub use naja_runtime_macros::tokio_ct   ;
+#[ cfg(all( feature = "macros", feature = "tokio_local")) ] pub use naja_runtime_macros::tokio_local;
+#[ cfg(all( feature = "macros", feature = "smol"       )) ] pub use naja_runtime_macros::smol       ;
+#[ cfg(all( feature = "macros", feature = "smol"       )) ] pub use naja_runtime_macros::smol_local ;
 
 
-use
-{
-	import   :: { *        } ,
-	executor :: { Executor } ,
-};
+use import::*;
 
 
+#[ cfg( feature = "std" ) ]
+//
 std::thread_local!
 (
 	pub(crate) static EXEC: OnceCell<Executor> = OnceCell::new();
+
+	// Executors set with `exec`. The last one takes precedence over EXEC.
+	//
+	static SCOPES: RefCell< Vec< Rc<Executor> > > = const { RefCell::new( Vec::new() ) };
+
+	// The executor of `init_global`, created on this thread the first time it is needed.
+	//
+	static FALLBACK: OnceCell<Executor> = const { OnceCell::new() };
 );
 
 
+// The configuration set with `init_global`, for threads that don't have an executor of their own.
+//
+#[ cfg( feature = "std" ) ]
+//
+static GLOBAL: SyncOnceCell<Config> = SyncOnceCell::new();
+
+
+// Without std there are no threads to configure, so there is one executor for the whole program. OnceBox
+// doesn't lock, so it can be read from interrupt handlers.
+//
+#[ cfg(not( feature = "std" )) ]
+//
+static EXEC: OnceBox<Executor> = OnceBox::new();
+
+
+
+// Run a closure with the executor currently in use on this thread, which is the innermost scope from
+// `exec` if any, otherwise the one set with `init`, otherwise the one set with `init_global`.
+//
+// We clone the Rc so the borrow on SCOPES is released before running the closure. That way a task
+// polled from within the closure (eg. by localpool::run) can call `exec` again.
+//
+#[ cfg( feature = "std" ) ]
+//
+pub(crate) fn with_exec<R>( f: impl FnOnce( Option<&Executor> ) -> R ) -> R
+{
+	match SCOPES.with( |scopes| scopes.borrow().last().cloned() )
+	{
+		Some(e) => f( Some( &e ) ),
+
+		None => EXEC.with( |exec| match exec.get()
+		{
+			Some(e) => f( Some(e) )                                ,
+			None    => FALLBACK.with( |fb| f( global_exec( fb ) ) ),
+		}),
+	}
+}
+
+
+// The executor set with `init_global`, created for this thread if it doesn't exist yet. For the shared
+// executors this allows, creating it is like calling `init` with the same config on several threads, they
+// all get the same pool.
+//
+#[ cfg( feature = "std" ) ]
+//
+fn global_exec( fallback: &OnceCell<Executor> ) -> Option<&Executor>
+{
+	let config = GLOBAL.get()?;
+
+	Some( fallback.get_or_init( || Executor::new( config.clone() ) ) )
+}
+
+
+#[ cfg(not( feature = "std" )) ]
+//
+pub(crate) fn with_exec<R>( f: impl FnOnce( Option<&Executor> ) -> R ) -> R
+{
+	f( EXEC.get() )
+}
+
+
+
+// Set the executor of this thread, or of the program without std.
+//
+#[ cfg( feature = "std" ) ]
+//
+fn set_exec( exec: Executor ) -> Result< (), Error >
+{
+	EXEC.with( |cell| cell.set( exec ).map_err( |_| ErrorKind::DoubleExecutorInit.into() ) )
+}
+
+
+#[ cfg(not( feature = "std" )) ]
+//
+fn set_exec( exec: Executor ) -> Result< (), Error >
+{
+	EXEC.set( Box::new( exec ) ).map_err( |_| ErrorKind::DoubleExecutorInit.into() )
+}
+
+
+
+// The config of the executor set with `init`, ignoring the scopes of `exec`.
+//
+#[ cfg( feature = "std" ) ]
+//
+fn init_config() -> Option<Config>
+{
+	EXEC.with( |exec| exec.get().map( |e| e.config() ) )
+}
+
+
+#[ cfg(not( feature = "std" )) ]
+//
+fn init_config() -> Option<Config>
+{
+	EXEC.get().map( |e| e.config() )
+}
+
+
+
+// Pops the scope of `exec` when dropped, so the previous executor is restored even if the closure panics.
+//
+#[ cfg( feature = "std" ) ]
+//
+struct ScopeGuard;
+
+#[ cfg( feature = "std" ) ]
+//
+impl Drop for ScopeGuard
+{
+	fn drop( &mut self )
+	{
+		SCOPES.with( |scopes| scopes.borrow_mut().pop() );
+	}
+}
+
+
 
 /// Set the executor to use by on this thread. Run this before calls to [`spawn`]\(_*\).
 ///
 /// If you are a library author, don't call this unless you create the thread, otherwise it's up to client code to
 /// decide which executor to use. Just call [`spawn`].
 ///
+/// Without the `std` feature there are no threads to configure. The executor set here is used by the whole
+/// program, including interrupt handlers.
+///
 /// ### Errors
 ///
 /// This method will fail with [`ErrorKind::DoubleExecutorInit`] if you call it twice on the same thread. There is
@@ -155,10 +467,102 @@ rt::localpool::run();
 //
 pub fn init( config: Config ) -> Result< (), Error >
 {
-	EXEC.with( |exec| -> Result< (), Error >
+	set_exec( Executor::new( config ) )
+}
+
+
+/// Set an executor you created yourself as the executor for this thread. This let's you share an executor
+/// you also pass to other libraries as a `T: Spawn`, or use a threadpool that is independent of the one
+/// used by [`init`].
+///
+/// ### Errors
+///
+/// This method will fail with [`ErrorKind::DoubleExecutorInit`] if this thread already has an executor.
+///
+/// ### Example
+#[cfg_attr(feature = "localpool", doc = r##"
+```rust
+use async_runtime as rt;
+
+let pool = rt::LocalPool::new();
+
+rt::init_with( pool.clone() ).expect( "Set thread executor" );
+
+rt::spawn( async {} ).expect( "spawn future" );
+
+pool.run();
+```
+"##)]
+//
+pub fn init_with( exec: impl Into<Executor> ) -> Result< (), Error >
+{
+	set_exec( exec.into() )
+}
+
+
+/// Use an executor you plugged in yourself by implementing [`Backend`] as the executor for this thread. All calls to
+/// [`spawn`] and friends on this thread, including the ones made by libraries, go to your backend. This is a
+/// shorthand for `init( Config::Custom( CustomBackend::new( backend ) ) )`.
+///
+/// If you need to install the same backend on several threads, create a [`CustomBackend`] and pass clones of it
+/// to [`init_with`].
+///
+/// ### Errors
+///
+/// This method will fail with [`ErrorKind::DoubleExecutorInit`] if this thread already has an executor.
+//
+pub fn init_custom( backend: impl Backend ) -> Result< (), Error >
+{
+	init( Config::Custom( CustomBackend::new( backend ) ) )
+}
+
+
+/// Set a default executor for the whole process. Threads that never called [`init`] use it when they spawn,
+/// so libraries can spawn from threads you don't control, like callbacks from a C library, rayon workers or the
+/// threads of a test harness. A thread that calls [`init`] uses it's own executor instead, and [`exec`] still
+/// takes precedence. On threads that use it, [`current_rt`] returns it's configuration.
+///
+/// Since every thread can end up using it, the global executor has to be one that can be spawned on from any
+/// thread: _threadpool_, _juliex_, _async-std_, _smol_, _tokio_tp_, _static_exec_ or a custom backend that
+/// doesn't spawn `!Send` futures. Calling [`spawn_local`] on a thread that uses the global executor returns
+/// [`ErrorKind::SpawnLocalOnThreadPool`].
+///
+/// This can only be called once per process.
+///
+/// ### Errors
+///
+/// - [`ErrorKind::GlobalExecutorNotSend`] if the executor only runs on it's own thread, like _localpool_.
+/// - [`ErrorKind::DoubleExecutorInit`] if the global executor was already set.
+///
+/// ### Example
+#[ cfg_attr( feature = "threadpool", doc = r##"
+```rust
+use { async_runtime as rt, std::thread };
+
+rt::init_global( rt::Config::ThreadPool( Default::default() ) ).expect( "set global executor" );
+
+// A thread started by code we don't control.
+//
+let handle = thread::spawn( ||
+{
+   rt::spawn_handle( async { 5 } ).expect( "spawn on the global executor" )
+
+}).join().expect( "join thread" );
+
+assert_eq!( 5, rt::block_on( handle ).expect( "join task" ) );
+```
+"##)]
+//
+#[ cfg( feature = "std" ) ]
+//
+pub fn init_global( config: Config ) -> Result< (), Error >
+{
+	if !config.is_shared()
 	{
-		exec.set( Executor::new( config ) ).map_err( |_| ErrorKind::DoubleExecutorInit.into() )
-	})
+		return Err( ErrorKind::GlobalExecutorNotSend.into() );
+	}
+
+	GLOBAL.set( config ).map_err( |_| ErrorKind::DoubleExecutorInit.into() )
 }
 
 
@@ -171,7 +575,9 @@ pub fn init( config: Config ) -> Result< (), Error >
 //
 pub fn init_allow_same( config: Config ) -> Result< (), Error >
 {
-	if let Some(cfg) = current_rt() {
+	// Compare with the executor set by init, not with the one of the current scope.
+	//
+	if let Some(cfg) = init_config() {
 	if config == cfg
 	{
 		return Ok(())
@@ -181,7 +587,55 @@ pub fn init_allow_same( config: Config ) -> Result< (), Error >
 }
 
 
-/// Spawn a future to be run on the thread specified executor (set with [`init`]).
+/// Use a different executor for all code that runs in the closure. Calls to [`spawn`] and friends from within
+/// the closure go to this executor, including calls made by libraries. When the closure returns, the
+/// previous executor for this thread comes back. Calls to `exec` can be nested.
+///
+/// You can pass a [`Config`] or an executor you created yourself. The closure gets a reference to the
+/// executor, which implements `futures::task::Spawn`, so you can also pass it to code that takes a `T: Spawn`.
+///
+/// This allows isolating some code on it's own executor without dedicating a thread to it. Note that only
+/// code running on this thread during the closure sees the scoped executor. Tasks spawned on a threadpool run
+/// on the worker threads which have the threadpool itself as their executor. For a single threaded executor,
+/// like _localpool_, you need to run it before the scope ends, or keep a clone around and run it later.
+///
+/// ### Example
+#[cfg_attr(feature = "localpool", doc = r##"
+```rust
+use async_runtime as rt;
+
+rt::init( rt::Config::LocalPool ).expect( "Set thread executor" );
+
+let isolated = rt::LocalPool::new();
+
+rt::exec( isolated.clone(), |_|
+{
+   // Some library code that spawns.
+   //
+   rt::spawn( async {} ).expect( "spawn future" );
+});
+
+// Only run the futures spawned inside the scope.
+//
+isolated.run();
+```
+"##)]
+//
+#[ cfg( feature = "std" ) ]
+//
+pub fn exec<R>( exec: impl Into<Executor>, f: impl FnOnce( &Executor ) -> R ) -> R
+{
+	let exec = Rc::new( exec.into() );
+
+	SCOPES.with( |scopes| scopes.borrow_mut().push( exec.clone() ) );
+
+	let _guard = ScopeGuard;
+
+	f( &exec )
+}
+
+
+/// Spawn a future to be run on the thread specified executor (set with [`init`] or [`exec`]).
 ///
 /// This method returns a result. I understand that this is an inconveniece, but this is a interface that
 /// abstracts out over all supported executors. Some of them don't have an infallible spawn method, so we return
@@ -198,6 +652,8 @@ pub fn init_allow_same( config: Config ) -> Result< (), Error >
 /// [`spawn`] requires a `()` Output on the future. If you need to wait for the future to finish and/or recover
 /// a result from the computation, see: [`spawn_handle`].
 ///
+/// To give the task a name, use a [`Builder`]. See the [`task`] module for what is known about the task.
+///
 /// ### Errors
 ///
 /// - This method is infallible on: _juliex_, _async-std_, _bindgen_.
@@ -221,16 +677,11 @@ rt::spawn( async
 ```
 "##)]
 //
+#[ track_caller ]
+//
 pub fn spawn( fut: impl Future< Output=() > + 'static + Send ) -> Result< (), Error >
 {
-	EXEC.with( move |exec| -> Result< (), Error >
-	{
-		match exec.get()
-		{
-			Some(e) => e.spawn( fut )                                 ,
-			None    => Err( ErrorKind::NoExecutorInitialized.into() ) ,
-		}
-	})
+	Builder::new().spawn( fut )
 }
 
 
@@ -255,30 +706,27 @@ pub fn spawn( fut: impl Future< Output=() > + 'static + Send ) -> Result< (), Er
 ///   You can call [localpool::run] and spawn again afterwards.
 /// - If you call this without an initialized executor, [`ErrorKind::NoExecutorInitialized`] is returned.
 //
+#[ track_caller ]
+//
 pub fn spawn_local( fut: impl Future< Output=() > + 'static ) -> Result< (), Error >
 {
-	EXEC.with( move |exec|
-	{
-		match exec.get()
-		{
-			Some(e) => e.spawn_local( fut )                           ,
-			None    => Err( ErrorKind::NoExecutorInitialized.into() ) ,
-		}
-	})
+	Builder::new().spawn_local( fut )
 }
 
 
 /// Spawn a future and recover the output or just `.await` it to make sure it's finished.
-/// Since different executors return different types, we have to Box the returned future.
+/// This returns a [`JoinHandle`], which is the same type for all executors.
 ///
-/// Note that if you drop the handle, your future will not be polled.
+/// Note that if you drop the handle, your future will be cancelled. This is true on all executors,
+/// including async-std. Use [`JoinHandle::detach`] if you want the task to keep running in the background.
 ///
-/// If on a threadpool and your future panics, the panic will be swallowed.
+/// If your future panics, the panic is caught and awaiting the handle will return a [`JoinError`], which carries
+/// the panic payload. This works the same on all executors.
 ///
-/// async-std always
-/// returns a `JoinHandle`. You could call async-std's spawn method directly, knowing that worker threads might
-/// not be set up to end further calls to [`spawn`] to the async-std executor. Only do this if the spawned future
-/// will not call [`spawn`] and friends.
+/// async-std always returns a `JoinHandle`. You could call async-std's spawn method directly, knowing that worker
+/// threads might not be set up to end further calls to [`spawn`] to the async-std executor. Only do this if the
+/// spawned future will not call [`spawn`] and friends.
+#[ cfg_attr( feature = "async_std", doc = "See also [`async_std::spawn_handle`]." ) ]
 ///
 /// ### Example
 #[ cfg_attr( all( feature = "juliex", feature = "macros" ), doc = r##"
@@ -295,7 +743,7 @@ async fn main()
 
    }).expect( "spawn on localpool" );
 
-   assert_eq!( "hello", handle.await );
+   assert_eq!( "hello", handle.await.expect( "task panicked" ) );
 }
 
 ```
@@ -304,17 +752,87 @@ async fn main()
 /// ### Errors
 /// - If you call this without an initialized executor, [`ErrorKind::NoExecutorInitialized`] is returned.
 
+//
+#[ track_caller ]
 //
 pub fn spawn_handle<T: Send + 'static>( fut: impl Future< Output=T > + Send + 'static )
 
-	-> Result< Box< dyn Future< Output=T > + Unpin + Send + 'static >, Error >
+	-> Result< JoinHandle<T>, Error >
+
+{
+	Builder::new().spawn_handle( fut )
+}
+
+
+
+/// Spawn a future and recover the output for `!Send` futures. This does the same as [`spawn_handle`]
+/// except neither the future nor it's output have to be `Send`, so a local task can return an `Rc` or, on WASM,
+/// a JS value. The [`JoinHandle`] is then `!Send` as well.
+///
+/// Note that if you drop the handle, your future will be cancelled. Use [`JoinHandle::detach`] if you want the
+/// task to keep running in the background.
+///
+/// ### Errors
+/// - If you call this without an initialized executor, [`ErrorKind::NoExecutorInitialized`] is returned.
+//
+#[ track_caller ]
+//
+pub fn spawn_handle_local<T: 'static>( fut: impl Future< Output=T > + 'static )
+
+	-> Result< JoinHandle<T>, Error >
+
+{
+	Builder::new().spawn_handle_local( fut )
+}
+
+
+
+
+/// Shut down the executor of the current thread. It stops accepting new tasks and waits until the tasks
+/// in flight finish or the timeout expires. This blocks the current thread. It returns `true` if all tasks
+/// finished in time. Pass `Duration::MAX` to wait without a timeout.
+///
+/// Call this at the end of your program if you want to be sure your tasks finish their work before the
+/// process exits. After this, spawning on the executor, from any thread that uses it, returns
+/// [`ErrorKind::ExecutorShutdown`]. Tasks in flight that try to spawn will get that error too.
+///
+/// This only works on _threadpool_, _juliex_, _tokio_tp_ and _smol_. Remember that threads that were initialized with the same
+/// configuration share a threadpool, so this affects all of them.
+///
+/// Do not call this from within a task running on the threadpool, since it will wait for that task to finish.
+///
+/// ### Errors
+///
+/// - If you call this without an initialized executor, [`ErrorKind::NoExecutorInitialized`] is returned.
+/// - On other executors, [`ErrorKind::WrongExecutor`] is returned.
+///
+/// ### Example
+#[ cfg_attr( feature = "threadpool", doc = r##"
+```
+use async_runtime as rt;
+
+rt::init( rt::Config::ThreadPool( Default::default() ) ).expect( "no double executor init" );
 
+rt::spawn( async
 {
-	EXEC.with( move |exec|
+   // Flush some buffers.
+
+}).expect( "spawn on threadpool" );
+
+let finished = rt::shutdown( std::time::Duration::from_secs( 5 ) ).expect( "shutdown threadpool" );
+
+assert!( finished );
+assert_eq!( &rt::ErrorKind::ExecutorShutdown, rt::spawn( async {} ).unwrap_err().kind() );
+```
+"##)]
+//
+pub fn shutdown( timeout: Duration ) -> Result< bool, Error >
+{
+	with_exec( move |exec|
 	{
-		match exec.get()
+		match exec
 		{
-			Some(e) => e.spawn_handle( fut )                          ,
+			Some(e) => e.shutdown( timeout )                          ,
 			None    => Err( ErrorKind::NoExecutorInitialized.into() ) ,
 		}
 	})
@@ -322,26 +840,28 @@ pub fn spawn_handle<T: Send + 'static>( fut: impl Future< Output=T > + Send + 's
 
 
 
-/// Spawn a future and recover the output for `!Send` futures. This does the same as [`spawn_handle`]
-/// except the future does not have to be `Send`. Note that `Future::Output` still has to be `Send`.
-/// We use [`FutureExt::remote_handle`](https://rust-lang-nursery.github.io/futures-api-docs/0.3.0-alpha.18/futures/future/trait.FutureExt.html#method.remote_handle) behind the scenes, and that requires the output to be Send,
-/// even though it shoulnd't have to be.
+/// Get a handle to the executor of the current thread, which you can send to other threads to spawn on it.
+/// Inside [`exec`], this is the scoped executor. See [`ExecutorHandle`].
 ///
-/// Note that if you drop the handle, your future will not be polled.
+/// For single threaded executors this spawns a task on the executor, so call it once and clone the handle.
 ///
 /// ### Errors
+///
 /// - If you call this without an initialized executor, [`ErrorKind::NoExecutorInitialized`] is returned.
+/// - For single threaded executors, spawning the task that receives the futures can fail, see [`spawn`].
 //
-pub fn spawn_handle_local<T: 'static + Send>( fut: impl Future< Output=T > + 'static )
-
-	-> Result< Box< dyn Future< Output=T > + 'static + Unpin >, Error >
-
+#[ cfg( feature = "std" ) ]
+#[ track_caller ]
+//
+pub fn handle() -> Result< ExecutorHandle, Error >
 {
-	EXEC.with( move |exec|
+	let location = Location::caller();
+
+	with_exec( move |exec|
 	{
-		match exec.get()
+		match exec
 		{
-			Some(e) => e.spawn_handle_local( fut )                    ,
+			Some(e) => e.handle( location )                           ,
 			None    => Err( ErrorKind::NoExecutorInitialized.into() ) ,
 		}
 	})
@@ -349,17 +869,17 @@ pub fn spawn_handle_local<T: 'static + Send>( fut: impl Future< Output=T > + 'st
 
 
 
-
-/// Which executor is configured for the current thread?
+/// Which executor is configured for the current thread? Inside [`exec`], this returns the config of the scoped
+/// executor.
 ///
 /// If you are a library author you can use this to generate a clean error message
 /// if you have a hard requirement for a certain executor.
 //
 pub fn current_rt() -> Option<Config>
 {
-	EXEC.with( move |exec|
+	with_exec( move |exec|
 	{
-		exec.get().map( |e| e.config() )
+		exec.map( |e| e.config() )
 	})
 }
 
@@ -375,14 +895,15 @@ pub fn current_rt() -> Option<Config>
 /// If you use juliex or async_std, the threadpool will continue working, but block_on will not wait
 /// until all your futures have finished. As soon as the future you block on finishes,
 /// if `block_on` is the last statement of your program, the program will just end, regardless
-/// of other futures still on the threadpool.
+/// of other futures still on the threadpool. On _threadpool_, _juliex_, _tokio_tp_ and _smol_ you
+/// can use [`shutdown`] to wait for them.
 ///
 /// In general you shouldn't block the thread when you are in an async context.
 ///
 /// **Note:** This method is not available on WASM, since WASM currently does not allow blocking
 /// the current thread.
 //
-#[ cfg(not( target_arch = "wasm32" )) ]
+#[ cfg(all( feature = "std", not( target_arch = "wasm32" ) )) ]
 //
 pub fn block_on< F: Future >( fut: F ) -> F::Output
 {
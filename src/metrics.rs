@@ -0,0 +1,283 @@
+//! Task metrics. Only available with the `metrics` feature.
+//!
+//! Every task spawned through [`spawn`](crate::spawn), [`spawn_local`](crate::spawn_local),
+//! [`spawn_handle`](crate::spawn_handle) and [`spawn_handle_local`](crate::spawn_handle_local) is counted.
+//! The counters are kept per executor and per thread that spawned the task. Tasks spawned directly on an
+//! executor object, like [`ThreadPool::spawn`](crate::ThreadPool::spawn), are not counted.
+//!
+//! The counters of a thread are dropped once the thread has exited and all the tasks it spawned are gone, so
+//! short lived threads don't make the metrics grow forever. Until then they are reported as usual.
+//!
+//! Call [`snapshot`] to get the current values, eg. to send them to your monitoring.
+//!
+//! ### Example
+#![cfg_attr(feature = "localpool", doc = r##"
+```
+use async_runtime as rt;
+
+rt::init( rt::Config::LocalPool ).expect( "no double executor init" );
+
+rt::spawn( async {} ).expect( "spawn" );
+rt::localpool::run().expect( "run localpool" );
+
+let snapshot = rt::metrics::snapshot();
+let metrics  = snapshot.iter().find( |m| m.thread == std::thread::current().id() ).expect( "metrics" );
+
+assert_eq!( "LocalPool", metrics.executor );
+assert_eq!( 1, metrics.spawned   );
+assert_eq!( 1, metrics.completed );
+assert_eq!( 0, metrics.alive     );
+```
+"##)]
+
+use crate :: { import::*  };
+use std   :: { sync::Weak };
+
+
+// The counters of all threads, so we can take a snapshot. They are owned by the thread and by the tasks it
+// spawned. Entries for counters that have been dropped are removed when we come across them.
+//
+static REGISTRY: SyncLazy< Mutex< Vec< Weak<Counters> > > > = SyncLazy::new( Default::default );
+
+
+std::thread_local!
+(
+	// Cache the counters for this thread, so we don't have to lock the registry on each spawn. This keeps them
+	// alive as long as the thread runs.
+	//
+	static COUNTERS: RefCell< HashMap< &'static str, Arc<Counters> > > = RefCell::new( HashMap::new() );
+);
+
+
+
+/// The metrics of one executor on one thread.
+//
+#[ derive( Debug, Clone, PartialEq, Eq ) ]
+//
+pub struct TaskMetrics
+{
+	/// The name of the [`Config`](crate::Config) variant of the executor, eg. `"ThreadPool"`.
+	//
+	pub executor: &'static str,
+
+	/// The thread that spawned the tasks.
+	//
+	pub thread: ThreadId,
+
+	/// The name of the thread that spawned the tasks, if it has one.
+	//
+	pub thread_name: Option<String>,
+
+	/// The number of tasks spawned.
+	//
+	pub spawned: u64,
+
+	/// The number of tasks that ran to completion.
+	//
+	pub completed: u64,
+
+	/// The number of tasks that currently exist. They are either running or waiting to be polled.
+	/// Tasks that are neither alive, completed or panicked were cancelled.
+	//
+	pub alive: u64,
+
+	/// The number of tasks that panicked.
+	//
+	pub panicked: u64,
+
+	/// The number of times the tasks have been polled.
+	//
+	pub polls: u64,
+}
+
+
+
+/// Get the current metrics of all executors on all threads that have spawned tasks. Threads that have exited
+/// are only included while some of the tasks they spawned still exist.
+//
+pub fn snapshot() -> Vec<TaskMetrics>
+{
+	let mut registry = REGISTRY.lock().expect( "lock metrics registry" );
+
+	registry.retain( |c| c.strong_count() > 0 );
+
+	registry.iter().filter_map( Weak::upgrade ).map( |c| c.snapshot() ).collect()
+}
+
+
+
+// Targets like mips32 don't have 64 bit atomics, so we count with a usize there.
+//
+#[ cfg(     target_has_atomic = "64"  ) ] type Counter = AtomicU64   ;
+#[ cfg(not( target_has_atomic = "64" )) ] type Counter = AtomicUsize ;
+
+
+#[ cfg( target_has_atomic = "64" ) ]
+//
+fn load( counter: &Counter ) -> u64
+{
+	counter.load( Ordering::Relaxed )
+}
+
+
+#[ cfg(not( target_has_atomic = "64" )) ]
+//
+fn load( counter: &Counter ) -> u64
+{
+	counter.load( Ordering::Relaxed ) as u64
+}
+
+
+
+#[ derive( Debug ) ]
+//
+struct Counters
+{
+	executor   : &'static str   ,
+	thread     : ThreadId       ,
+	thread_name: Option<String> ,
+	spawned    : Counter        ,
+	completed  : Counter        ,
+	alive      : Counter        ,
+	panicked   : Counter        ,
+	polls      : Counter        ,
+}
+
+
+impl Counters
+{
+	fn new( executor: &'static str ) -> Self
+	{
+		let current = thread::current();
+
+		Self
+		{
+			executor                                        ,
+			thread     : current.id()                       ,
+			thread_name: current.name().map( String::from ) ,
+			spawned    : Counter::new( 0 )                  ,
+			completed  : Counter::new( 0 )                  ,
+			alive      : Counter::new( 0 )                  ,
+			panicked   : Counter::new( 0 )                  ,
+			polls      : Counter::new( 0 )                  ,
+		}
+	}
+
+
+	fn snapshot( &self ) -> TaskMetrics
+	{
+		TaskMetrics
+		{
+			executor   : self.executor              ,
+			thread     : self.thread                ,
+			thread_name: self.thread_name.clone()   ,
+			spawned    : load( &self.spawned   )    ,
+			completed  : load( &self.completed )    ,
+			alive      : load( &self.alive     )    ,
+			panicked   : load( &self.panicked  )    ,
+			polls      : load( &self.polls     )    ,
+		}
+	}
+
+
+	// Get the counters for this executor on the current thread.
+	//
+	fn current( executor: &'static str ) -> Arc<Self>
+	{
+		COUNTERS.with( |counters|
+		{
+			counters.borrow_mut().entry( executor ).or_insert_with( ||
+			{
+				let new          = Arc::new( Self::new( executor ) );
+				let mut registry = REGISTRY.lock().expect( "lock metrics registry" );
+
+				registry.retain( |c| c.strong_count() > 0 );
+				registry.push( Arc::downgrade( &new ) );
+
+				new
+
+			}).clone()
+		})
+	}
+}
+
+
+
+/// Wrap a future so it's counted in the metrics of the given executor on the current thread.
+//
+pub(crate) fn instrument<F: Future>( executor: &'static str, fut: F ) -> impl Future< Output = F::Output >
+{
+	let counters = Counters::current( executor );
+
+	counters.spawned.fetch_add( 1, Ordering::Relaxed );
+	counters.alive  .fetch_add( 1, Ordering::Relaxed );
+
+	let mut task = TaskGuard { counters, completed: false };
+
+	async move
+	{
+		let mut fut = std::pin::pin!( fut );
+
+		let out = future::poll_fn( |cx|
+		{
+			task.counters.polls.fetch_add( 1, Ordering::Relaxed );
+
+			let _guard = PollGuard( &task.counters );
+
+			fut.as_mut().poll( cx )
+
+		}).await;
+
+		task.complete();
+
+		out
+	}
+}
+
+
+
+// Counts the task as no longer alive when it's dropped, and as completed if it ran to completion.
+//
+struct TaskGuard
+{
+	counters : Arc<Counters> ,
+	completed: bool          ,
+}
+
+impl TaskGuard
+{
+	fn complete( &mut self )
+	{
+		self.completed = true;
+	}
+}
+
+impl Drop for TaskGuard
+{
+	fn drop( &mut self )
+	{
+		self.counters.alive.fetch_sub( 1, Ordering::Relaxed );
+
+		if self.completed
+		{
+			self.counters.completed.fetch_add( 1, Ordering::Relaxed );
+		}
+	}
+}
+
+
+
+// Counts a panic if the poll unwinds.
+//
+struct PollGuard<'a>( &'a Counters );
+
+impl Drop for PollGuard<'_>
+{
+	fn drop( &mut self )
+	{
+		if thread::panicking()
+		{
+			self.0.panicked.fetch_add( 1, Ordering::Relaxed );
+		}
+	}
+}
@@ -0,0 +1,289 @@
+use crate :: { import::*, self as rt, Error, JoinHandle, JoinError };
+
+
+/// A nursery lets you spawn a group of child tasks and wait for all of them. It spawns the children
+/// on the executor of the current thread, with [`spawn_handle`](crate::spawn_handle).
+///
+/// Children return a `Result<T, E>`. If a child panics or gets cancelled, the [`JoinError`] is converted
+/// into `E`, so `E` needs to implement `From<JoinError>`. [`Error`] does.
+///
+/// There are two ways of waiting for the children:
+///
+/// - Await the nursery itself. This is fail-fast: on the first error or panic, the remaining children are cancelled
+///   and you get the error. Otherwise you get the output of all children in the order they were spawned.
+/// - Await [`Nursery::collect_all`]. This waits for all children, even if some of them fail, and gives you the
+///   result of each child in the order they were spawned.
+///
+/// When the nursery is dropped, all children that haven't finished yet are cancelled, so no task outlives it.
+///
+/// ### Example
+#[cfg_attr(feature = "localpool", doc = r##"
+```rust
+use async_runtime as rt;
+
+rt::init( rt::Config::LocalPool ).expect( "no double executor init" );
+
+let mut nursery = rt::Nursery::new();
+
+nursery.nurse( async { Ok::<_, rt::Error>( 1 ) } ).expect( "spawn child" );
+nursery.nurse( async { Ok::<_, rt::Error>( 2 ) } ).expect( "spawn child" );
+
+let results = rt::localpool::run_until( nursery ).expect( "run localpool" );
+
+assert_eq!( vec![ 1, 2 ], results.expect( "no child failed" ) );
+```
+"##)]
+//
+#[ must_use = "Nursery cancels the children when dropped, await it to wait for them" ]
+//
+pub struct Nursery<T, E>
+{
+	children: FuturesUnordered< Child<T, E> > ,
+	results : Vec< Option< Result<T, E> > >   ,
+}
+
+
+
+impl<T, E> Nursery<T, E>
+
+	where T: Send + 'static,
+	      E: Send + 'static,
+{
+	/// Spawn a child task on the executor of the current thread.
+	///
+	/// ### Errors
+	///
+	/// The same errors as [`spawn_handle`](crate::spawn_handle).
+	//
+	#[ track_caller ]
+	//
+	pub fn nurse( &mut self, fut: impl Future< Output = Result<T, E> > + Send + 'static ) -> Result< (), Error >
+	{
+		let handle = rt::spawn_handle( fut )?;
+
+		self.push( handle );
+		Ok(())
+	}
+}
+
+
+
+// A local nursery can collect `!Send` outputs, like `Rc`, so only `nurse` requires `Send`.
+//
+impl<T, E> Nursery<T, E>
+{
+	/// Create a new empty nursery.
+	//
+	pub fn new() -> Self
+	{
+		Self
+		{
+			children: FuturesUnordered::new(),
+			results : Vec::new()             ,
+		}
+	}
+
+
+	/// Spawn a `!Send` child task on the executor of the current thread. The output doesn't have to be `Send`
+	/// either, but then the nursery must be awaited on the same thread.
+	///
+	/// ### Errors
+	///
+	/// The same errors as [`spawn_handle_local`](crate::spawn_handle_local).
+	//
+	#[ track_caller ]
+	//
+	pub fn nurse_local( &mut self, fut: impl Future< Output = Result<T, E> > + 'static ) -> Result< (), Error >
+
+		where T: 'static,
+		      E: 'static,
+	{
+		let handle = rt::spawn_handle_local( fut )?;
+
+		self.push( handle );
+		Ok(())
+	}
+
+
+	fn push( &mut self, handle: JoinHandle< Result<T, E> > )
+	{
+		self.children.push( Child { index: self.results.len(), handle } );
+		self.results .push( None );
+	}
+
+
+	/// The number of children that haven't finished yet, or that have finished, but that weren't
+	/// polled yet through this nursery.
+	//
+	pub fn len( &self ) -> usize
+	{
+		self.children.len()
+	}
+
+
+	/// Whether all children have been collected. This is true for a new nursery.
+	//
+	pub fn is_empty( &self ) -> bool
+	{
+		self.children.is_empty()
+	}
+
+
+	/// Wait for all children, even if some of them fail. The output has the result of each child, in the order
+	/// they were spawned.
+	//
+	pub fn collect_all( self ) -> CollectAll<T, E>
+	{
+		CollectAll { nursery: self }
+	}
+}
+
+
+
+impl<T, E: From<JoinError>> Nursery<T, E>
+{
+	// Poll the children and store their results. If fail_fast is set, the first error is returned and the
+	// remaining children are cancelled.
+	//
+	fn poll_children( &mut self, cx: &mut Context<'_>, fail_fast: bool ) -> Poll< Result<(), E> >
+	{
+		while let Some( (index, result) ) = ready!( Pin::new( &mut self.children ).poll_next( cx ) )
+		{
+			let result = result.unwrap_or_else( |e| Err( e.into() ) );
+
+			if fail_fast
+			{
+				if let Err(e) = result
+				{
+					// Dropping the join handles cancels the children.
+					//
+					self.children.clear();
+					self.results .clear();
+
+					return Poll::Ready( Err(e) );
+				}
+			}
+
+			self.results[index] = Some( result );
+		}
+
+		Poll::Ready( Ok(()) )
+	}
+}
+
+
+
+impl<T, E: From<JoinError>> Future for Nursery<T, E>
+{
+	type Output = Result< Vec<T>, E >;
+
+	fn poll( mut self: Pin<&mut Self>, cx: &mut Context<'_> ) -> Poll<Self::Output>
+	{
+		ready!( self.poll_children( cx, true ) )?;
+
+		let results = self.results.drain(..).map( |result| match result
+		{
+			Some( Ok(out) ) => out,
+
+			// In fail-fast mode we return on the first error, and we only get here when all children are done.
+			//
+			_ => unreachable!(),
+
+		}).collect();
+
+		Poll::Ready( Ok( results ) )
+	}
+}
+
+
+
+// We never pin the outputs of the children, they are only stored and moved out.
+//
+impl<T, E> Unpin for Nursery<T, E> {}
+
+
+
+impl<T, E> Default for Nursery<T, E>
+{
+	fn default() -> Self
+	{
+		Self::new()
+	}
+}
+
+
+
+impl<T, E> fmt::Debug for Nursery<T, E>
+{
+	fn fmt( &self, f: &mut fmt::Formatter<'_> ) -> fmt::Result
+	{
+		f.debug_struct( "Nursery" )
+
+			.field( "children", &self.children.len() )
+
+		.finish()
+	}
+}
+
+
+
+/// Future returned by [`Nursery::collect_all`]. It resolves to the result of each child, in the order they
+/// were spawned. Dropping it cancels the children that haven't finished yet.
+//
+#[ must_use = "futures do nothing unless you `.await` or poll them" ]
+#[ derive( Debug ) ]
+//
+pub struct CollectAll<T, E>
+{
+	nursery: Nursery<T, E>,
+}
+
+
+
+impl<T, E: From<JoinError>> Future for CollectAll<T, E>
+{
+	type Output = Vec< Result<T, E> >;
+
+	fn poll( mut self: Pin<&mut Self>, cx: &mut Context<'_> ) -> Poll<Self::Output>
+	{
+		// Without fail_fast this never returns an error.
+		//
+		let _ = ready!( self.nursery.poll_children( cx, false ) );
+
+		let results = self.nursery.results.drain(..).map( |result| match result
+		{
+			Some( result ) => result,
+
+			// We only get here when all children are done.
+			//
+			None => unreachable!(),
+
+		}).collect();
+
+		Poll::Ready( results )
+	}
+}
+
+
+
+// A child task, which remembers in which order it was spawned.
+//
+struct Child<T, E>
+{
+	index : usize                       ,
+	handle: JoinHandle< Result<T, E> > ,
+}
+
+
+
+impl<T, E> Future for Child<T, E>
+{
+	type Output = ( usize, Result< Result<T, E>, JoinError > );
+
+	fn poll( mut self: Pin<&mut Self>, cx: &mut Context<'_> ) -> Poll<Self::Output>
+	{
+		let result = ready!( Pin::new( &mut self.handle ).poll( cx ) );
+
+		Poll::Ready( ( self.index, result ) )
+	}
+}
@@ -0,0 +1,193 @@
+use
+{
+	crate :: { self as rt, import::*, Error, ErrorKind, Executor, JoinHandle, LocalPool, task::Task } ,
+	std   :: { sync::atomic::AtomicUsize                                                            } ,
+};
+
+
+static POOL: SyncLazy< PinnedPool > = SyncLazy::new( PinnedPool::new );
+
+
+type Job = Box< dyn FnOnce( &Executor ) + Send + 'static >;
+
+
+
+/// Run a `!Send` future on a dedicated thread and get a [`JoinHandle`] to it's output. You pass a closure that
+/// creates the future. The closure must be `Send`, the future it returns doesn't have to be.
+///
+/// This lets code that runs on a threadpool host libraries that need [`spawn_local`](crate::spawn_local). The
+/// closure is sent to one of a small pool of threads that each run a [`LocalPool`]. The threads are started the
+/// first time they are needed, up to one per cpu, and each new future goes to the thread with the least tasks.
+/// The threads have their localpool as executor, so the future can call `rt::spawn_local` itself.
+///
+/// Like [`spawn_blocking`](crate::spawn_blocking), this works whatever executor the current thread uses, even on
+/// a thread without executor. Only the output has to be `Send`.
+///
+/// If the future panics, awaiting the handle returns a [`JoinError`](crate::JoinError). Dropping the handle
+/// cancels the task. If that happens before the thread got to it, the closure is dropped without being called.
+///
+/// ### Errors
+///
+/// Returns [`ErrorKind::Spawn`] if no thread could be started.
+///
+/// ### Example
+///
+/// ```
+/// use { async_runtime as rt, std::rc::Rc };
+///
+/// let handle = rt::spawn_pinned( ||
+/// {
+///    let shared = Rc::new( 5 );
+///
+///    async move { *shared + 1 }
+///
+/// }).expect( "spawn pinned" );
+///
+/// assert_eq!( 6, rt::block_on( handle ).expect( "join task" ) );
+/// ```
+//
+#[ track_caller ]
+//
+pub fn spawn_pinned<F, Fut>( f: F ) -> Result< JoinHandle<Fut::Output>, Error >
+
+	where F          : FnOnce() -> Fut + Send + 'static ,
+	      Fut        : Future + 'static                 ,
+	      Fut::Output: Send + 'static                   ,
+{
+	let task           = Task::new( None, Location::caller() );
+	let info           = task.clone();
+	let (slot, handle) = JoinHandle::pair();
+
+	POOL.execute( move |exec, load|
+	{
+		let fut = async move
+		{
+			let _load = load;
+			f().await
+		};
+
+		// Only fails if the localpool was shut down, which we never do. The handle will report the task as
+		// cancelled.
+		//
+		let _ = exec.spawn_local( task, slot.wrap( fut ) );
+
+	})?;
+
+	Ok( handle.with_task( info ) )
+}
+
+
+
+struct PinnedPool
+{
+	workers: Mutex< Vec<Worker> > ,
+	max    : usize                ,
+}
+
+
+struct Worker
+{
+	tx  : mpsc::UnboundedSender<Job> ,
+	load: Arc<AtomicUsize>           ,
+}
+
+
+// Counts the tasks of a worker. It's dropped when the task ends, also when it get's cancelled.
+//
+struct Load( Arc<AtomicUsize> );
+
+impl Drop for Load
+{
+	fn drop( &mut self )
+	{
+		self.0.fetch_sub( 1, Ordering::Relaxed );
+	}
+}
+
+
+
+impl PinnedPool
+{
+	fn new() -> Self
+	{
+		let max = thread::available_parallelism().map_or( 1, |n| n.get() );
+
+		Self { workers: Mutex::new( Vec::new() ), max }
+	}
+
+
+	fn execute( &self, job: impl FnOnce( &Executor, Load ) + Send + 'static ) -> Result< (), Error >
+	{
+		let mut workers = self.workers.lock().expect( "lock pinned pool" );
+
+		// A thread only ends if a task panicked outside of a JoinHandle, like a task it spawned with
+		// `rt::spawn_local`. Forget about it, a new one will be started if needed.
+		//
+		workers.retain( |w| !w.tx.is_closed() );
+
+		// Use an idle thread, otherwise start a new one, otherwise use the least busy one.
+		//
+		let idle = workers.iter().position( |w| w.load.load( Ordering::Relaxed ) == 0 );
+
+		let index = match idle
+		{
+			Some(i)                          => Some(i)                      ,
+			None if workers.len() < self.max => Self::start( &mut workers )  ,
+			None                             => None                         ,
+		};
+
+		let worker = match index.or_else( || Self::least_busy( &workers ) )
+		{
+			Some(i) => &workers[i]                          ,
+			None    => return Err( ErrorKind::Spawn.into() ) ,
+		};
+
+		worker.load.fetch_add( 1, Ordering::Relaxed );
+
+		let load     = Load( worker.load.clone() );
+		let job: Job = Box::new( move |exec| job( exec, load ) );
+
+		worker.tx.unbounded_send( job ).map_err( |_| ErrorKind::Spawn.into() )
+	}
+
+
+	fn least_busy( workers: &[Worker] ) -> Option<usize>
+	{
+		workers.iter().enumerate()
+
+			.min_by_key( |(_, w)| w.load.load( Ordering::Relaxed ) )
+			.map( |(i, _)| i )
+	}
+
+
+	// Start a thread and return it's index. Returns `None` if the thread couldn't be started.
+	//
+	fn start( workers: &mut Vec<Worker> ) -> Option<usize>
+	{
+		let (tx, rx) = mpsc::unbounded::<Job>();
+
+		let spawned = thread::Builder::new()
+
+			.name( "async_runtime_pinned".to_string() )
+			.spawn( move ||
+			{
+				let pool = LocalPool::new();
+				let exec = Executor::from( pool.clone() );
+
+				rt::init_with( pool.clone() ).expect( "fresh thread has no executor" );
+
+				pool.run_until( rx.for_each( |job|
+				{
+					job( &exec );
+					future::ready(())
+				}));
+			})
+		;
+
+		spawned.ok()?;
+
+		workers.push( Worker { tx, load: Arc::new( AtomicUsize::new( 0 ) ) } );
+
+		Some( workers.len() - 1 )
+	}
+}
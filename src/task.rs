@@ -0,0 +1,385 @@
+//! Information about the tasks spawned through `async_runtime`.
+//!
+//! Every task spawned through [`spawn`](crate::spawn) and friends or through a [`Builder`] gets a unique id
+//! and remembers where it was spawned. Tasks spawned with a [`Builder`] can also have a name. From within a
+//! task you can get this information with [`current`]. It also shows up in the [`JoinError`](crate::JoinError)
+//! of the task and in the tracing span if you enable the `tracing` feature.
+//!
+//! Task local storage is declared with [`task_local!`](crate::task_local). See [`LocalKey`].
+//!
+//! Without the `std` feature there are no thread locals, so [`current`] and task locals are not available.
+//!
+//! Tasks spawned directly on an executor object, like `ThreadPool::spawn`, don't have this information.
+//!
+//! Since [`current`] also works in a panic hook, you can use it to find out which task panicked, even if the
+//! executor swallows the panic:
+//!
+//! ```
+//! use async_runtime as rt;
+//!
+//! let default = std::panic::take_hook();
+//!
+//! std::panic::set_hook( Box::new( move |info|
+//! {
+//!    if let Some( task ) = rt::task::current()
+//!    {
+//!       eprintln!( "{} panicked", task );
+//!    }
+//!
+//!    default( info );
+//! }));
+//! ```
+//
+use crate :: { import::*, Error, ErrorKind, JoinHandle, with_exec };
+
+
+#[ cfg( feature = "std" ) ] mod local;
+#[ cfg( feature = "std" ) ] pub use local::LocalKey;
+
+
+// Every task that goes through the executor gets a unique id, so you can tell them apart in the logs.
+//
+#[ cfg( target_has_atomic = "64" ) ]
+//
+static NEXT_ID: AtomicU64 = AtomicU64::new( 1 );
+
+#[ cfg(not( target_has_atomic = "64" )) ]
+//
+static NEXT_ID: AtomicUsize = AtomicUsize::new( 1 );
+
+
+#[ cfg( target_has_atomic = "64" ) ]
+//
+fn next_id() -> u64
+{
+	NEXT_ID.fetch_add( 1, Ordering::Relaxed )
+}
+
+
+#[ cfg(not( target_has_atomic = "64" )) ]
+//
+fn next_id() -> u64
+{
+	NEXT_ID.fetch_add( 1, Ordering::Relaxed ) as u64
+}
+
+
+#[ cfg( feature = "std" ) ]
+//
+std::thread_local!
+(
+	// The task being polled on this thread.
+	//
+	static CURRENT: RefCell< Option<Task> > = const { RefCell::new( None ) };
+);
+
+
+
+/// Information about a task. See the [module docs](self).
+//
+#[ derive( Debug, Clone ) ]
+//
+pub struct Task
+{
+	id      : u64                        ,
+	name    : Option< Arc<str> >         ,
+	location: &'static Location<'static> ,
+}
+
+
+impl Task
+{
+	pub(crate) fn new( name: Option< Arc<str> >, location: &'static Location<'static> ) -> Self
+	{
+		Self { id: next_id(), name, location }
+	}
+
+
+	/// A unique id for this task.
+	//
+	pub fn id( &self ) -> u64
+	{
+		self.id
+	}
+
+
+	/// The name given to the task with [`Builder::name`], if any.
+	//
+	pub fn name( &self ) -> Option<&str>
+	{
+		self.name.as_deref()
+	}
+
+
+	/// Where the task was spawned.
+	//
+	pub fn location( &self ) -> &'static Location<'static>
+	{
+		self.location
+	}
+}
+
+
+
+impl fmt::Display for Task
+{
+	fn fmt( &self, f: &mut fmt::Formatter<'_> ) -> fmt::Result
+	{
+		match &self.name
+		{
+			Some(name) => write!( f, "task {} \"{}\" spawned at {}", self.id, name, self.location ),
+			None       => write!( f, "task {} spawned at {}"       , self.id,       self.location ),
+		}
+	}
+}
+
+
+
+/// The task currently being polled on this thread. Returns `None` when called outside of a task spawned through
+/// `async_runtime`.
+//
+#[ cfg( feature = "std" ) ]
+//
+pub fn current() -> Option<Task>
+{
+	CURRENT.with( |current| current.borrow().clone() )
+}
+
+
+
+// Make the task available through `current` while the future is being polled.
+//
+#[ cfg( feature = "std" ) ]
+//
+pub(crate) async fn instrument<F: Future>( task: Task, fut: F ) -> F::Output
+{
+	let mut fut = core::pin::pin!( fut );
+
+	future::poll_fn( |cx|
+	{
+		let _guard = CurrentGuard::enter( &task );
+
+		fut.as_mut().poll( cx )
+
+	}).await
+}
+
+
+// There is no `current` without std.
+//
+#[ cfg(not( feature = "std" )) ]
+//
+pub(crate) fn instrument<F: Future>( _task: Task, fut: F ) -> F
+{
+	fut
+}
+
+
+
+// Restores the previous task when dropped. Executors like localpool poll tasks from within `block_on`, which
+// can itself be called from a task.
+//
+#[ cfg( feature = "std" ) ]
+//
+struct CurrentGuard( Option<Task> );
+
+#[ cfg( feature = "std" ) ]
+//
+impl CurrentGuard
+{
+	fn enter( task: &Task ) -> Self
+	{
+		Self( CURRENT.with( |current| current.replace( Some( task.clone() ) ) ) )
+	}
+}
+
+#[ cfg( feature = "std" ) ]
+//
+impl Drop for CurrentGuard
+{
+	fn drop( &mut self )
+	{
+		let previous = self.0.take();
+
+		CURRENT.with( |current| *current.borrow_mut() = previous );
+	}
+}
+
+
+
+/// Configure a task before spawning it. The spawn methods do the same as [`spawn`](crate::spawn) and friends,
+/// on the executor of the current thread.
+///
+/// You can use the same builder to spawn several tasks. They will each get their own id.
+///
+/// By default a task doesn't see the [task locals](crate::task_local) of the task that spawned it. Use
+/// [`Builder::inherit_locals`] to give it a clone of them.
+///
+/// ### Example
+#[ cfg_attr( feature = "localpool", doc = r##"
+```
+use async_runtime as rt;
+
+rt::init( rt::Config::LocalPool ).expect( "no double executor init" );
+
+let handle = rt::Builder::new().name( "flush-loop" ).spawn_handle( async
+{
+   rt::task::current().expect( "in a task" ).name().map( String::from )
+
+}).expect( "spawn on localpool" );
+
+let name = rt::localpool::run_until( handle ).expect( "run localpool" ).expect( "join task" );
+
+assert_eq!( Some( "flush-loop" ), name.as_deref() );
+```
+"##)]
+//
+#[ derive( Debug, Clone, Default ) ]
+//
+pub struct Builder
+{
+	name   : Option< Arc<str> > ,
+
+	#[ cfg_attr( not( feature = "std" ), allow( dead_code ) ) ]
+	//
+	inherit: bool               ,
+}
+
+
+impl Builder
+{
+	/// Create a builder for an anonymous task.
+	//
+	pub fn new() -> Self
+	{
+		Self::default()
+	}
+
+
+	/// Give the task a name. It will show up in [`task::current`](current), in the [`JoinError`](crate::JoinError)
+	/// and the [`Error`] it converts into, and in the tracing span of the task.
+	//
+	pub fn name( mut self, name: impl Into<String> ) -> Self
+	{
+		self.name = Some( name.into().into() );
+		self
+	}
+
+
+	/// Whether the task gets a clone of the [task locals](crate::task_local) that are set where it is spawned.
+	/// Defaults to `false`. Without the `std` feature there are no task locals and this does nothing.
+	//
+	pub fn inherit_locals( mut self, inherit: bool ) -> Self
+	{
+		self.inherit = inherit;
+		self
+	}
+
+
+	// Give the task the task locals it inherits.
+	//
+	#[ cfg( feature = "std" ) ]
+	//
+	fn with_locals<F: Future>( &self, fut: F ) -> impl Future< Output=F::Output >
+	{
+		let locals = if self.inherit { local::capture() } else { Vec::new() };
+
+		local::with_locals( locals, fut )
+	}
+
+
+	#[ cfg(not( feature = "std" )) ]
+	//
+	fn with_locals<F: Future>( &self, fut: F ) -> F
+	{
+		fut
+	}
+
+
+	/// Spawn a future on the executor of the current thread. See [`spawn`](crate::spawn).
+	//
+	#[ track_caller ]
+	//
+	pub fn spawn( &self, fut: impl Future< Output=() > + 'static + Send ) -> Result< (), Error >
+	{
+		let task = Task::new( self.name.clone(), Location::caller() );
+		let fut  = self.with_locals( fut );
+
+		with_exec( move |exec|
+		{
+			match exec
+			{
+				Some(e) => e.spawn( task, fut )                           ,
+				None    => Err( ErrorKind::NoExecutorInitialized.into() ) ,
+			}
+		})
+	}
+
+
+	/// Spawn a `!Send` future on the executor of the current thread. See [`spawn_local`](crate::spawn_local).
+	//
+	#[ track_caller ]
+	//
+	pub fn spawn_local( &self, fut: impl Future< Output=() > + 'static ) -> Result< (), Error >
+	{
+		let task = Task::new( self.name.clone(), Location::caller() );
+		let fut  = self.with_locals( fut );
+
+		with_exec( move |exec|
+		{
+			match exec
+			{
+				Some(e) => e.spawn_local( task, fut )                     ,
+				None    => Err( ErrorKind::NoExecutorInitialized.into() ) ,
+			}
+		})
+	}
+
+
+	/// Spawn a future and recover the output. See [`spawn_handle`](crate::spawn_handle).
+	//
+	#[ track_caller ]
+	//
+	pub fn spawn_handle<T: Send + 'static>( &self, fut: impl Future< Output=T > + Send + 'static )
+
+		-> Result< JoinHandle<T>, Error >
+
+	{
+		let task = Task::new( self.name.clone(), Location::caller() );
+		let fut  = self.with_locals( fut );
+		let info = task.clone();
+
+		with_exec( move |exec|
+		{
+			match exec
+			{
+				Some(e) => e.spawn_handle( task, fut ).map( |h| h.with_task( info ) ) ,
+				None    => Err( ErrorKind::NoExecutorInitialized.into() )             ,
+			}
+		})
+	}
+
+
+	/// Spawn a `!Send` future and recover the output. See [`spawn_handle_local`](crate::spawn_handle_local).
+	//
+	#[ track_caller ]
+	//
+	pub fn spawn_handle_local<T: 'static>( &self, fut: impl Future< Output=T > + 'static )
+
+		-> Result< JoinHandle<T>, Error >
+
+	{
+		let task = Task::new( self.name.clone(), Location::caller() );
+		let fut  = self.with_locals( fut );
+		let info = task.clone();
+
+		with_exec( move |exec|
+		{
+			match exec
+			{
+				Some(e) => e.spawn_handle_local( task, fut ).map( |h| h.with_task( info ) ) ,
+				None    => Err( ErrorKind::NoExecutorInitialized.into() )                   ,
+			}
+		})
+	}
+}
@@ -0,0 +1,255 @@
+use crate :: { import::*, Error, ErrorKind };
+
+
+std::thread_local!
+(
+	// The task locals that are set on this thread, so tasks that inherit them can capture them.
+	//
+	static ACTIVE: RefCell< Vec<&'static dyn Capture> > = const { RefCell::new( Vec::new() ) };
+);
+
+
+
+/// Declare task local storage. The values are visible inside a future that runs in [`LocalKey::scope`], whatever
+/// thread polls it. Since tasks move between worker threads on threadpools, you can not use thread locals for
+/// this.
+///
+/// The values must be `Clone + Send`, so they can be inherited by tasks spawned with
+/// [`Builder::inherit_locals`](crate::Builder::inherit_locals).
+///
+/// ### Example
+#[ cfg_attr( feature = "localpool", doc = r##"
+```
+use async_runtime as rt;
+
+rt::task_local!
+{
+   static TENANT: u32;
+}
+
+rt::init( rt::Config::LocalPool ).expect( "no double executor init" );
+
+let fut    = TENANT.scope( 42, async { TENANT.get() } );
+let handle = rt::spawn_handle( fut ).expect( "spawn on localpool" );
+
+assert_eq!( 42, rt::localpool::run_until( handle ).expect( "run localpool" ).expect( "join task" ) );
+```
+"##)]
+//
+#[ macro_export ]
+//
+macro_rules! task_local
+{
+	() => {};
+
+	( $(#[$attr:meta])* $vis:vis static $name:ident: $t:ty; $($rest:tt)* ) =>
+	{
+		$(#[$attr])* $vis static $name: $crate::task::LocalKey<$t> =
+		{
+			$crate::__private::thread_local!
+			(
+				static __KEY: $crate::__private::RefCell< Option<$t> > = const { $crate::__private::RefCell::new( None ) };
+			);
+
+			$crate::task::LocalKey { __inner: &__KEY }
+		};
+
+		$crate::task_local!( $($rest)* );
+	};
+}
+
+
+
+/// A key for task local storage. Create it with the [`task_local!`](crate::task_local) macro.
+//
+pub struct LocalKey<T: 'static>
+{
+	#[ doc( hidden ) ]
+	//
+	pub __inner: &'static thread::LocalKey< RefCell< Option<T> > >,
+}
+
+
+
+impl<T: Clone + Send + 'static> LocalKey<T>
+{
+	/// Set the value of the task local for the duration of every poll of the future. Other futures don't see it,
+	/// even when they run on the same thread.
+	//
+	pub fn scope<F: Future>( &'static self, value: T, fut: F ) -> impl Future< Output = F::Output >
+	{
+		with_locals( vec![ Box::new( Value { key: self, value: Some( value ) } ) ], fut )
+	}
+
+
+	/// Get a clone of the value.
+	///
+	/// ### Panics
+	///
+	/// When called outside of [`LocalKey::scope`].
+	//
+	pub fn get( &'static self ) -> T
+	{
+		self.with( T::clone )
+	}
+}
+
+
+
+impl<T: 'static> LocalKey<T>
+{
+	/// Access the value.
+	///
+	/// ### Panics
+	///
+	/// When called outside of [`LocalKey::scope`], or when you poll a future in the scope of the same key from
+	/// within the closure.
+	//
+	pub fn with<R>( &'static self, f: impl FnOnce( &T ) -> R ) -> R
+	{
+		self.try_with( f ).expect( "async_runtime: task local accessed outside of it's scope" )
+	}
+
+
+	/// Access the value if it is set.
+	///
+	/// ### Errors
+	///
+	/// When called outside of [`LocalKey::scope`], [`ErrorKind::TaskLocalNotSet`] is returned.
+	//
+	pub fn try_with<R>( &'static self, f: impl FnOnce( &T ) -> R ) -> Result< R, Error >
+	{
+		self.__inner.with( |cell|
+		{
+			match cell.borrow().as_ref()
+			{
+				Some(value) => Ok( f( value ) )                         ,
+				None        => Err( ErrorKind::TaskLocalNotSet.into() ) ,
+			}
+		})
+	}
+}
+
+
+
+impl<T: 'static> fmt::Debug for LocalKey<T>
+{
+	fn fmt( &self, f: &mut fmt::Formatter<'_> ) -> fmt::Result
+	{
+		f.pad( "LocalKey { .. }" )
+	}
+}
+
+
+
+// The values of task locals for one future. They are moved into the thread locals while the future is polled
+// and moved back out afterwards.
+//
+pub(crate) type Locals = Vec< Box<dyn Slot> >;
+
+
+pub(crate) trait Slot: Send
+{
+	fn enter( &mut self );
+	fn exit ( &mut self );
+}
+
+
+// Clone the value of a task local that is set on this thread.
+//
+trait Capture: Sync
+{
+	fn capture( &'static self ) -> Box<dyn Slot>;
+}
+
+
+struct Value<T: 'static>
+{
+	key  : &'static LocalKey<T> ,
+	value: Option<T>            ,
+}
+
+
+impl<T: 'static> Value<T>
+{
+	fn swap( &mut self )
+	{
+		self.key.__inner.with( |cell| std::mem::swap( &mut *cell.borrow_mut(), &mut self.value ) );
+	}
+}
+
+
+impl<T: Clone + Send + 'static> Slot for Value<T>
+{
+	fn enter( &mut self )
+	{
+		self.swap();
+		ACTIVE.with( |active| active.borrow_mut().push( self.key ) );
+	}
+
+
+	fn exit( &mut self )
+	{
+		ACTIVE.with( |active| active.borrow_mut().pop() );
+		self.swap();
+	}
+}
+
+
+impl<T: Clone + Send + 'static> Capture for LocalKey<T>
+{
+	fn capture( &'static self ) -> Box<dyn Slot>
+	{
+		Box::new( Value { key: self, value: self.__inner.with( |cell| cell.borrow().clone() ) } )
+	}
+}
+
+
+
+// Clone the values of all task locals that are set on this thread.
+//
+pub(crate) fn capture() -> Locals
+{
+	ACTIVE.with( |active| active.borrow().iter().map( |key| key.capture() ).collect() )
+}
+
+
+
+// Set the task locals for every poll of the future.
+//
+pub(crate) async fn with_locals<F: Future>( mut locals: Locals, fut: F ) -> F::Output
+{
+	let mut fut = core::pin::pin!( fut );
+
+	future::poll_fn( |cx|
+	{
+		let _guard = Entered::new( &mut locals );
+
+		fut.as_mut().poll( cx )
+
+	}).await
+}
+
+
+
+// Moves the values back out when dropped, also when the future panics.
+//
+struct Entered<'a>( &'a mut Locals );
+
+impl<'a> Entered<'a>
+{
+	fn new( locals: &'a mut Locals ) -> Self
+	{
+		locals.iter_mut().for_each( |slot| slot.enter() );
+
+		Self( locals )
+	}
+}
+
+impl Drop for Entered<'_>
+{
+	fn drop( &mut self )
+	{
+		self.0.iter_mut().rev().for_each( |slot| slot.exit() );
+	}
+}
@@ -0,0 +1,243 @@
+//! Start OS threads that have an executor.
+//!
+//! Executors are configured per thread, so every thread you start has to call [`init`](crate::init) before code
+//! on it can [`spawn`](crate::spawn). The functions in this module mirror `std::thread`, but initialize the new
+//! thread with an executor first. By default it gets the same [`Config`] as the thread that starts it, which is
+//! like calling `rt::init` with [`current_rt`](crate::current_rt) on the new thread. You can also give it a
+//! different one with [`Builder::config`].
+//!
+//! Remember that a [`Config`] describes an executor. Threads initialized with an equal threadpool configuration
+//! share a threadpool, but a single threaded executor, like _localpool_, is a new one on each thread. Running it
+//! is up to the closure you pass, or use [`spawn_async`], which does it for you.
+//
+use crate :: { self as rt, import::*, Config, Error, ErrorKind, Executor, task::Task };
+use std   :: { io, thread::JoinHandle };
+
+
+
+/// Configure a thread before starting it. This mirrors `std::thread::Builder`, and lets you choose the executor
+/// of the thread with [`Builder::config`].
+//
+#[ derive( Debug, Default ) ]
+//
+pub struct Builder
+{
+	name      : Option<String> ,
+	stack_size: Option<usize>  ,
+	config    : Option<Config> ,
+}
+
+
+impl Builder
+{
+	/// Create a builder for a thread that gets the same executor configuration as the current thread.
+	//
+	pub fn new() -> Self
+	{
+		Self::default()
+	}
+
+
+	/// Name the thread. See `std::thread::Builder::name`.
+	//
+	pub fn name( mut self, name: String ) -> Self
+	{
+		self.name = Some( name );
+		self
+	}
+
+
+	/// Set the size of the stack of the thread. See `std::thread::Builder::stack_size`.
+	//
+	pub fn stack_size( mut self, size: usize ) -> Self
+	{
+		self.stack_size = Some( size );
+		self
+	}
+
+
+	/// The executor to initialize the thread with, instead of the one of the current thread.
+	//
+	pub fn config( mut self, config: Config ) -> Self
+	{
+		self.config = Some( config );
+		self
+	}
+
+
+	/// Start the thread and run the closure on it, after initializing it's executor. If neither this builder
+	/// nor the current thread have an executor configured, the new thread doesn't get one either.
+	///
+	/// ### Errors
+	///
+	/// Returns the error from `std::thread::Builder::spawn` if the thread could not be started.
+	//
+	pub fn spawn<F, T>( self, f: F ) -> io::Result< JoinHandle<T> >
+
+		where F: FnOnce() -> T + Send + 'static ,
+		      T: Send + 'static                 ,
+	{
+		let config = self.config.clone().or_else( rt::current_rt );
+
+		self.std_builder().spawn( move ||
+		{
+			if let Some( config ) = config
+			{
+				rt::init( config ).expect( "fresh thread has no executor" );
+			}
+
+			f()
+		})
+	}
+
+
+	/// Start the thread and run the future to completion on it, on a new executor. Awaiting is done for you:
+	/// single threaded executors, like _localpool_ or _tokio_ct_, are run until the future and all the tasks it
+	/// spawned are finished, since the executor goes away with the thread. On threadpools, the thread waits for
+	/// the future only.
+	///
+	/// Joining the thread returns the output of the future. If the future panics, joining returns the panic, like
+	/// it does for a closure on `std::thread`.
+	///
+	/// Custom backends that can spawn `!Send` futures are not run, since we don't know how. Start those threads
+	/// with [`Builder::spawn`] and run the backend yourself.
+	///
+	/// ### Errors
+	///
+	/// - Returns the error from `std::thread::Builder::spawn` if the thread could not be started.
+	/// - Returns an error with kind `Other`, which wraps [`ErrorKind::NoExecutorInitialized`], if neither this
+	///   builder nor the current thread have an executor configured.
+	//
+	#[ track_caller ]
+	//
+	pub fn spawn_async<Fut>( self, fut: Fut ) -> io::Result< JoinHandle<Fut::Output> >
+
+		where Fut        : Future + Send + 'static ,
+		      Fut::Output: Send + 'static          ,
+	{
+		let config = self.config.clone().or_else( rt::current_rt ).ok_or_else( ||
+		{
+			io::Error::other( Error::from( ErrorKind::NoExecutorInitialized ) )
+		})?;
+
+		let task = Task::new( None, Location::caller() );
+
+		self.std_builder().spawn( move ||
+		{
+			let exec = Executor::new( config );
+
+			rt::init_with( exec.clone() ).expect( "fresh thread has no executor" );
+
+			block_on_exec( &exec, task, fut )
+		})
+	}
+
+
+	fn std_builder( &self ) -> thread::Builder
+	{
+		let mut builder = thread::Builder::new();
+
+		if let Some( name ) = &self.name { builder = builder.name( name.clone() ); }
+		if let Some( size ) = self.stack_size { builder = builder.stack_size( size ); }
+
+		builder
+	}
+}
+
+
+
+// Spawn the future on the executor, run it and wait for the output. Panics of the future are resumed here, so
+// they end up in the join handle of the thread.
+//
+fn block_on_exec<Fut>( exec: &Executor, task: Task, fut: Fut ) -> Fut::Output
+
+	where Fut        : Future + Send + 'static ,
+	      Fut::Output: Send + 'static          ,
+{
+	let handle = exec.spawn_handle( task, fut ).expect( "spawn on a fresh executor" );
+
+	exec.run().expect( "run executor" );
+
+	match rt::block_on( handle )
+	{
+		Ok ( output ) => output,
+
+		Err( error  ) => match error.into_panic()
+		{
+			Some( payload ) => std::panic::resume_unwind( payload ),
+			None            => panic!( "the executor dropped the future passed to rt::thread::spawn_async" ),
+		}
+	}
+}
+
+
+
+/// Start a thread that is initialized with the executor configuration of the current thread. This is
+/// `Builder::new().spawn( f )`. See [`Builder::spawn`].
+///
+/// ### Panics
+///
+/// Like `std::thread::spawn`, if the thread could not be started.
+///
+/// ### Example
+#[ cfg_attr( feature = "localpool", doc = r##"
+```
+use async_runtime as rt;
+
+rt::init( rt::Config::LocalPool ).expect( "no double executor init" );
+
+let thread = rt::thread::spawn( ||
+{
+   rt::spawn( async { println!( "on the localpool of the new thread" ); } ).expect( "spawn" );
+
+   rt::localpool::run().expect( "run localpool" );
+
+   rt::current_rt()
+});
+
+assert_eq!( Some( rt::Config::LocalPool ), thread.join().expect( "join thread" ) );
+```
+"##)]
+//
+pub fn spawn<F, T>( f: F ) -> JoinHandle<T>
+
+	where F: FnOnce() -> T + Send + 'static ,
+	      T: Send + 'static                 ,
+{
+	Builder::new().spawn( f ).expect( "failed to spawn thread" )
+}
+
+
+/// Start a thread with a new executor for `config` and run the future to completion on it. Joining the thread
+/// returns the output of the future. This is `Builder::new().config( config ).spawn_async( fut )`. See
+/// [`Builder::spawn_async`].
+///
+/// ### Panics
+///
+/// Like `std::thread::spawn`, if the thread could not be started.
+///
+/// ### Example
+#[ cfg_attr( feature = "localpool", doc = r##"
+```
+use async_runtime as rt;
+
+let thread = rt::thread::spawn_async( rt::Config::LocalPool, async
+{
+   let handle = rt::spawn_handle_local( async { 5 } ).expect( "spawn" );
+
+   handle.await.expect( "join task" ) + 1
+});
+
+assert_eq!( 6, thread.join().expect( "join thread" ) );
+```
+"##)]
+//
+#[ track_caller ]
+//
+pub fn spawn_async<Fut>( config: Config, fut: Fut ) -> JoinHandle<Fut::Output>
+
+	where Fut        : Future + Send + 'static ,
+	      Fut::Output: Send + 'static          ,
+{
+	Builder::new().config( config ).spawn_async( fut ).expect( "failed to spawn thread" )
+}
@@ -0,0 +1,204 @@
+//! Timers that work on all executors.
+//!
+//! On native targets, timers are driven by a single lightweight background thread, which is started the first
+//! time you use a timer. On WASM, they use the browser's `setTimeout`. The timers don't depend on the executor,
+//! so they work the same whichever [`Config`](crate::Config) is used for the thread.
+//!
+//! For tests, you can [`pause`] the clock of a thread. Timers created on that thread then use a virtual clock
+//! that only moves when you [`advance`] it, or automatically when the localpool or the deterministic executor
+//! runs out of work. A test that sleeps for an hour then finishes immediately.
+//!
+//! ### Example
+#![cfg_attr(feature = "localpool", doc = r##"
+```
+use
+{
+   async_runtime as rt,
+   std::time::Duration,
+   futures::StreamExt,
+};
+
+rt::init( rt::Config::LocalPool ).expect( "no double executor init" );
+
+let program = async
+{
+   rt::time::sleep( Duration::from_millis( 5 ) ).await;
+
+   let result = rt::time::timeout( Duration::from_millis( 5 ), futures::future::pending::<()>() ).await;
+   assert_eq!( &rt::ErrorKind::Timeout, result.unwrap_err().kind() );
+
+   let ticks: Vec<()> = rt::time::interval( Duration::from_millis( 1 ) ).take( 3 ).collect().await;
+   assert_eq!( 3, ticks.len() );
+};
+
+rt::localpool::run_until( program ).expect( "run localpool" );
+```
+"##)]
+
+mod clock;
+
+pub use clock::{ pause, advance, elapsed };
+
+#[ cfg(any( feature = "localpool", feature = "deterministic" )) ]
+//
+pub(crate) use clock::auto_advance;
+
+
+use
+{
+	crate         :: { import::*, Error, ErrorKind } ,
+	futures_timer :: { Delay                       } ,
+};
+
+
+
+/// Wait until `duration` has elapsed.
+//
+pub fn sleep( duration: Duration ) -> Sleep
+{
+	Sleep { timer: Timer::new( duration ) }
+}
+
+
+
+/// Wait for a future, but give up after `duration`. When the timeout expires, the future is dropped and
+/// [`ErrorKind::Timeout`] is returned.
+///
+/// ### Errors
+///
+/// [`ErrorKind::Timeout`] if the future did not finish in time.
+//
+pub async fn timeout<F: Future>( duration: Duration, fut: F ) -> Result< F::Output, Error >
+{
+	let fut = std::pin::pin!( fut );
+
+	match future::select( fut, sleep( duration ) ).await
+	{
+		Either::Left ( (out, _) ) => Ok ( out                        ),
+		Either::Right( _        ) => Err( ErrorKind::Timeout.into() ),
+	}
+}
+
+
+
+/// A stream that yields every `period`. The first tick comes after one period. If the consumer is slow, ticks
+/// are not buffered, the next tick comes one period after the previous one was consumed.
+//
+pub fn interval( period: Duration ) -> Interval
+{
+	Interval { timer: Timer::new( period ), period }
+}
+
+
+
+/// Future returned by [`sleep`].
+//
+#[ must_use = "futures do nothing unless you `.await` or poll them" ]
+#[ derive( Debug ) ]
+//
+pub struct Sleep
+{
+	timer: Timer,
+}
+
+
+impl Sleep
+{
+	/// Restart the timer, so it expires `duration` from now.
+	//
+	pub fn reset( &mut self, duration: Duration )
+	{
+		self.timer.reset( duration );
+	}
+}
+
+
+impl Future for Sleep
+{
+	type Output = ();
+
+	fn poll( mut self: Pin<&mut Self>, cx: &mut Context<'_> ) -> Poll<Self::Output>
+	{
+		Pin::new( &mut self.timer ).poll( cx )
+	}
+}
+
+
+
+/// Stream returned by [`interval`].
+//
+#[ must_use = "streams do nothing unless polled" ]
+#[ derive( Debug ) ]
+//
+pub struct Interval
+{
+	timer : Timer    ,
+	period: Duration ,
+}
+
+
+impl Stream for Interval
+{
+	type Item = ();
+
+	fn poll_next( mut self: Pin<&mut Self>, cx: &mut Context<'_> ) -> Poll< Option<Self::Item> >
+	{
+		ready!( Pin::new( &mut self.timer ).poll( cx ) );
+
+		let period = self.period;
+		self.timer.reset( period );
+
+		Poll::Ready( Some(()) )
+	}
+}
+
+
+
+// A timer on the real clock, or on the virtual one if the clock of the thread was paused when the timer was
+// created.
+//
+#[ derive( Debug ) ]
+//
+enum Timer
+{
+	Real   ( Delay        ),
+	Virtual( clock::Timer ),
+}
+
+
+impl Timer
+{
+	fn new( duration: Duration ) -> Self
+	{
+		match clock::current()
+		{
+			Some(clock) => Timer::Virtual( clock::Timer::new( clock, duration ) ),
+			None        => Timer::Real   ( Delay::new( duration )               ),
+		}
+	}
+
+
+	fn reset( &mut self, duration: Duration )
+	{
+		match self
+		{
+			Timer::Real   ( delay ) => delay.reset( duration ),
+			Timer::Virtual( timer ) => timer.reset( duration ),
+		}
+	}
+}
+
+
+impl Future for Timer
+{
+	type Output = ();
+
+	fn poll( self: Pin<&mut Self>, cx: &mut Context<'_> ) -> Poll<Self::Output>
+	{
+		match self.get_mut()
+		{
+			Timer::Real   ( delay ) => Pin::new( delay ).poll( cx ),
+			Timer::Virtual( timer ) => Pin::new( timer ).poll( cx ),
+		}
+	}
+}
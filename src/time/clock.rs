@@ -0,0 +1,244 @@
+//! The virtual clock used by the timers of a thread after calling [`pause`].
+
+use crate :: { import::*, Error, ErrorKind };
+
+
+thread_local!
+{
+	static CLOCK: RefCell< Option< Arc<Clock> > > = const { RefCell::new( None ) };
+}
+
+
+
+// A clock that only moves when told to. Timers created on the paused thread hold on to it, so they keep
+// working if they are polled on another thread.
+//
+#[ derive( Debug ) ]
+//
+pub(crate) struct Clock
+{
+	state: Mutex<State>,
+}
+
+
+// The time elapsed since the clock was paused and the pending timers, ordered by deadline. The id makes
+// timers with the same deadline distinct.
+//
+#[ derive( Debug ) ]
+//
+struct State
+{
+	now    : Duration                           ,
+	timers : BTreeMap< (Duration, u64), Waker > ,
+	next_id: u64                                ,
+}
+
+
+
+/// Pause the clock of the current thread. From now on, timers created on this thread only expire when the
+/// clock is moved forward, either with [`advance`] or automatically.
+///
+/// The clock advances automatically when the localpool or the deterministic executor of this thread has no
+/// tasks ready to run. It then jumps to the next pending timer, so tests that sleep or time out finish
+/// without waiting. Other executors never advance the clock on their own. Note that while a task waits for
+/// something that happens outside of the executor, like [`spawn_blocking`](crate::spawn_blocking), the
+/// executor is idle, so the clock might jump past timers the task was racing against.
+///
+/// Pause the clock before running the executor. Timers that already exist keep using real time. Calling
+/// this when the clock is already paused does nothing. The clock stays paused until the thread ends.
+//
+pub fn pause()
+{
+	CLOCK.with( |clock|
+	{
+		clock.borrow_mut().get_or_insert_with( || Arc::new( Clock::new() ) );
+	});
+}
+
+
+
+/// Move the paused clock of the current thread forward by `duration`, waking up the timers that expire.
+/// The tasks waiting on them still need to be polled by the executor. The clock stops at `Duration::MAX`.
+///
+/// ### Errors
+///
+/// [`ErrorKind::ClockNotPaused`] if you didn't call [`pause`] on this thread.
+//
+pub fn advance( duration: Duration ) -> Result< (), Error >
+{
+	let clock = current().ok_or( ErrorKind::ClockNotPaused )?;
+	let now   = clock.state().now.saturating_add( duration );
+
+	clock.advance_to( now );
+
+	Ok(())
+}
+
+
+
+/// How far the clock of the current thread has advanced since it was paused.
+///
+/// ### Errors
+///
+/// [`ErrorKind::ClockNotPaused`] if you didn't call [`pause`] on this thread.
+//
+pub fn elapsed() -> Result< Duration, Error >
+{
+	let clock = current().ok_or( ErrorKind::ClockNotPaused )?;
+	let now   = clock.state().now;
+
+	Ok( now )
+}
+
+
+
+// The clock of the current thread if it is paused.
+//
+pub(crate) fn current() -> Option< Arc<Clock> >
+{
+	CLOCK.with( |clock| clock.borrow().clone() )
+}
+
+
+
+// Called by single threaded executors when no task is ready. If the clock of this thread is paused, jump
+// to the next timer and wake it up. Returns false if there is nothing to wake up.
+//
+#[ cfg(any( feature = "localpool", feature = "deterministic" )) ]
+//
+pub(crate) fn auto_advance() -> bool
+{
+	let clock = match current()
+	{
+		Some(clock) => clock,
+		None        => return false,
+	};
+
+	let next = match clock.state().timers.keys().next()
+	{
+		Some( &(deadline, _) ) => deadline,
+		None                   => return false,
+	};
+
+	clock.advance_to( next );
+
+	true
+}
+
+
+
+impl Clock
+{
+	fn new() -> Self
+	{
+		Self { state: Mutex::new( State { now: Duration::ZERO, timers: BTreeMap::new(), next_id: 0 } ) }
+	}
+
+
+	fn state( &self ) -> MutexGuard<'_, State>
+	{
+		self.state.lock().expect( "lock virtual clock" )
+	}
+
+
+	// Move the clock to `now` if that's later and wake up the expired timers. The wakers are called after
+	// releasing the lock.
+	//
+	fn advance_to( &self, now: Duration )
+	{
+		let mut state = self.state();
+
+		let now   = state.now.max( now );
+		state.now = now;
+
+		let pending = state.timers.split_off( &( now, u64::MAX ) );
+		let expired = std::mem::replace( &mut state.timers, pending );
+
+		drop( state );
+
+		expired.into_values().for_each( Waker::wake );
+	}
+}
+
+
+
+// A timer on the virtual clock. The deadline is `None` if it lies beyond what the clock can count, in which
+// case the timer never fires.
+//
+#[ derive( Debug ) ]
+//
+pub(crate) struct Timer
+{
+	clock   : Arc<Clock>       ,
+	deadline: Option<Duration> ,
+	id      : u64              ,
+}
+
+
+impl Timer
+{
+	pub(crate) fn new( clock: Arc<Clock>, duration: Duration ) -> Self
+	{
+		let mut state = clock.state();
+
+		let deadline = state.now.checked_add( duration );
+		let id       = state.next_id;
+
+		state.next_id += 1;
+		drop( state );
+
+		Self { clock, deadline, id }
+	}
+
+
+	pub(crate) fn reset( &mut self, duration: Duration )
+	{
+		let mut state = self.clock.state();
+
+		if let Some( deadline ) = self.deadline
+		{
+			state.timers.remove( &( deadline, self.id ) );
+		}
+
+		self.deadline = state.now.checked_add( duration );
+	}
+}
+
+
+impl Future for Timer
+{
+	type Output = ();
+
+	fn poll( self: Pin<&mut Self>, cx: &mut Context<'_> ) -> Poll<Self::Output>
+	{
+		let deadline = match self.deadline
+		{
+			Some(deadline) => deadline,
+			None           => return Poll::Pending,
+		};
+
+		let mut state = self.clock.state();
+
+		if state.now >= deadline
+		{
+			state.timers.remove( &( deadline, self.id ) );
+			return Poll::Ready(());
+		}
+
+		state.timers.insert( ( deadline, self.id ), cx.waker().clone() );
+
+		Poll::Pending
+	}
+}
+
+
+impl Drop for Timer
+{
+	fn drop( &mut self )
+	{
+		if let Some( deadline ) = self.deadline
+		{
+			self.clock.state().timers.remove( &( deadline, self.id ) );
+		}
+	}
+}
@@ -0,0 +1,64 @@
+use
+{
+	crate         :: { import::*, task::Task            } ,
+	tracing_crate :: { debug_span, error, trace, Span } ,
+};
+
+
+
+/// Create the span for a task. It is a child of the current span at the spawn site. The span has the name
+/// "task" and carries the executor, the id, name and spawn location of the task.
+//
+pub(crate) fn span( executor: &'static str, task: &Task ) -> Span
+{
+	debug_span!( "task", executor, task.id = task.id(), task.name = task.name(), task.location = %task.location() )
+}
+
+
+
+/// Wrap a future in the span of the task. Every poll and the completion are recorded as trace events inside
+/// the span. If the task panics, an error event is recorded.
+//
+pub(crate) async fn instrument<F: Future>( span: Span, fut: F ) -> F::Output
+{
+	let mut fut = std::pin::pin!( fut );
+
+	future::poll_fn( |cx| poll( &span, fut.as_mut(), cx ) ).await
+}
+
+
+
+fn poll<F: Future>( span: &Span, fut: Pin<&mut F>, cx: &mut Context<'_> ) -> Poll<F::Output>
+{
+	let _enter = span.enter();
+	let _guard = PanicGuard;
+
+	trace!( "poll" );
+
+	let out = fut.poll( cx );
+
+	if out.is_ready()
+	{
+		trace!( "completed" );
+	}
+
+	out
+}
+
+
+
+// Records an error in the span if the poll unwinds. Declared after the span guard, so it's dropped while
+// the span is still entered.
+//
+struct PanicGuard;
+
+impl Drop for PanicGuard
+{
+	fn drop( &mut self )
+	{
+		if thread::panicking()
+		{
+			error!( "task panicked" );
+		}
+	}
+}
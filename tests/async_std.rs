@@ -195,7 +195,7 @@ fn spawn_handle()
 
 	let handle = rt::spawn_handle( async { "hello".to_string() } ).expect( "spawn_handle" );
 
-	rt::block_on( async { assert_eq!( "hello", &handle.await ); } );
+	rt::block_on( async { assert_eq!( "hello", &handle.await.expect( "join task" ) ); } );
 }
 
 
@@ -220,7 +220,7 @@ fn spawn_handle_spawn_on_self()
 
 	}).expect( "spawn_handle" );
 
-	rt::block_on( async { assert_eq!( "hello", &handle.await ); } );
+	rt::block_on( async { assert_eq!( "hello", &handle.await.expect( "join task" ) ); } );
 }
 
 
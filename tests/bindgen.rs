@@ -10,6 +10,7 @@
 // ✔ spawn a boxed_local future
 // ✔ spawn several
 // ✔ spawn from within other task
+// ✔ spawn_handle_local can return a !Send output
 
 
 use
@@ -215,7 +216,7 @@ fn spawn_handle()
 
 	let handle = rt::spawn_handle( async { "hello".to_string() } ).expect( "spawn_handle" );
 
-	rt::spawn( async { assert_eq!( "hello", &handle.await ); } ).expect( "spawn" );
+	rt::spawn( async { assert_eq!( "hello", &handle.await.expect( "join task" ) ); } ).expect( "spawn" );
 }
 
 
@@ -240,7 +241,30 @@ fn spawn_handle_local()
 
 	}).expect( "spawn_handle" );
 
-	rt::spawn_local( async { assert_eq!( "hello", &handle.await ); } ).expect( "spawn" );
+	rt::spawn_local( async { assert_eq!( "hello", &handle.await.expect( "join task" ) ); } ).expect( "spawn" );
+}
+
+
+
+// The output of a local task doesn't have to be Send.
+//
+#[wasm_bindgen_test]
+//
+fn spawn_handle_local_not_send()
+{
+	rt::init_allow_same( rt::Config::Bindgen ).expect( "no double executor init" );
+
+	let handle = rt::spawn_handle_local( async { Rc::new( RefCell::new( 5 ) ) } ).expect( "spawn_handle_local" );
+
+	rt::spawn_local( async
+	{
+		let out = handle.await.expect( "join task" );
+
+		*out.borrow_mut() += 1;
+
+		assert_eq!( 6, *out.borrow() );
+
+	}).expect( "spawn" );
 }
 
 
@@ -0,0 +1,229 @@
+#![ cfg(not( target_arch = "wasm32" )) ]
+
+// Tested:
+//
+// ✔ spawn and spawn_handle go to the custom backend, also from within a task
+// ✔ spawn_local and spawn_handle_local return the right error without calling the backend if it's not supported
+// ✔ a single threaded backend can spawn !Send futures
+// ✔ shutdown is forwarded if supported and returns the right error otherwise
+// ✔ current_rt and init_allow_same compare the backend instance
+// ✔ a custom backend can be used as a scoped executor with exec
+//
+use
+{
+	async_runtime as rt,
+
+	std     :: { rc::Rc, cell::RefCell, thread, time::Duration                              } ,
+	std     :: { sync::{ Arc, atomic::{ AtomicUsize, Ordering } }                          } ,
+	futures :: { executor::{ block_on, LocalPool }, task::{ FutureObj, LocalFutureObj, LocalSpawnExt } } ,
+};
+
+
+
+// Runs every task on it's own thread, which is initialized with a backend that shares the counter.
+//
+#[ derive( Debug, Default ) ]
+//
+struct ThreadPerTask
+{
+	spawned: Arc<AtomicUsize>,
+}
+
+
+impl rt::Backend for ThreadPerTask
+{
+	fn name( &self ) -> &'static str { "ThreadPerTask" }
+
+	fn capabilities( &self ) -> rt::Capabilities
+	{
+		rt::Capabilities::default().shutdown( true )
+	}
+
+
+	fn spawn( &self, fut: FutureObj<'static, ()> ) -> Result< (), rt::Error >
+	{
+		self.spawned.fetch_add( 1, Ordering::SeqCst );
+
+		let spawned = self.spawned.clone();
+
+		thread::spawn( move ||
+		{
+			rt::init_custom( ThreadPerTask{ spawned } ).expect( "no double executor init" );
+			block_on( fut );
+		});
+
+		Ok(())
+	}
+
+
+	fn shutdown( &self, _timeout: Duration ) -> Result< bool, rt::Error >
+	{
+		Ok( true )
+	}
+}
+
+
+
+// Spawns on a LocalPool in a thread local. Counts calls to spawn_local, even though it claims not to support them.
+//
+#[ derive( Debug, Default ) ]
+//
+struct Local
+{
+	supported: bool,
+	calls    : Arc<AtomicUsize>,
+}
+
+
+thread_local!
+(
+	static POOL: RefCell<LocalPool> = RefCell::new( LocalPool::new() );
+);
+
+
+impl rt::Backend for Local
+{
+	fn capabilities( &self ) -> rt::Capabilities
+	{
+		rt::Capabilities::default().spawn_local( self.supported )
+	}
+
+
+	fn spawn( &self, fut: FutureObj<'static, ()> ) -> Result< (), rt::Error >
+	{
+		self.spawn_local( fut.into() )
+	}
+
+
+	fn spawn_local( &self, fut: LocalFutureObj<'static, ()> ) -> Result< (), rt::Error >
+	{
+		self.calls.fetch_add( 1, Ordering::SeqCst );
+
+		POOL.with( |pool| pool.borrow().spawner().spawn_local( fut ) )
+
+			.map_err( |_| rt::ErrorKind::Spawn.into() )
+	}
+}
+
+
+
+#[test]
+//
+fn spawn()
+{
+	let spawned = Arc::new( AtomicUsize::new( 0 ) );
+
+	rt::init_custom( ThreadPerTask{ spawned: spawned.clone() } ).expect( "no double executor init" );
+
+	let handle = rt::spawn_handle( async
+	{
+		let thread = thread::current().id();
+
+		rt::spawn_handle( async { thread::current().id() } ).expect( "spawn from within" ).await
+
+			.map( |inner| ( thread, inner ) )
+
+	}).expect( "spawn_handle" );
+
+	let (outer, inner) = block_on( handle ).expect( "join task" ).expect( "join inner task" );
+
+	assert_ne!( thread::current().id(), outer );
+	assert_ne!( outer, inner );
+	assert_eq!( 2, spawned.load( Ordering::SeqCst ) );
+}
+
+
+
+#[test]
+//
+fn spawn_local_unsupported()
+{
+	let calls = Arc::new( AtomicUsize::new( 0 ) );
+
+	rt::init_custom( Local{ supported: false, calls: calls.clone() } ).expect( "no double executor init" );
+
+	let res    = rt::spawn_local       ( async {}   );
+	let handle = rt::spawn_handle_local( async { 1 } );
+
+	assert_eq!( &rt::ErrorKind::SpawnLocalOnThreadPool, res   .unwrap_err().kind() );
+	assert_eq!( &rt::ErrorKind::SpawnLocalOnThreadPool, handle.unwrap_err().kind() );
+	assert_eq!( 0, calls.load( Ordering::SeqCst ) );
+}
+
+
+
+#[test]
+//
+fn spawn_local()
+{
+	rt::init_custom( Local{ supported: true, calls: Default::default() } ).expect( "no double executor init" );
+
+	let number = Rc::new( RefCell::new( 0 ) );
+	let num2   = number.clone();
+
+	rt::spawn_local( async move { *num2.borrow_mut() += 2; } ).expect( "spawn_local" );
+
+	let handle = rt::spawn_handle_local( async { 3 } ).expect( "spawn_handle_local" );
+
+	let out = POOL.with( |pool| pool.borrow_mut().run_until( handle ) ).expect( "join task" );
+
+	assert_eq!( 2, *number.borrow() );
+	assert_eq!( 3, out              );
+}
+
+
+
+#[test]
+//
+fn shutdown()
+{
+	rt::init_custom( ThreadPerTask::default() ).expect( "no double executor init" );
+
+	assert!( rt::shutdown( Duration::from_secs( 1 ) ).expect( "shutdown" ) );
+
+	rt::exec( rt::CustomBackend::new( Local::default() ), |_|
+	{
+		assert_eq!( &rt::ErrorKind::WrongExecutor, rt::shutdown( Duration::from_secs( 1 ) ).unwrap_err().kind() );
+	});
+}
+
+
+
+#[test]
+//
+fn config_identity()
+{
+	let backend = rt::CustomBackend::new( ThreadPerTask::default() );
+
+	rt::init_with( backend.clone() ).expect( "no double executor init" );
+
+	assert_eq!( Some( rt::Config::Custom( backend.clone() ) ), rt::current_rt() );
+	assert_eq!( "ThreadPerTask", backend.name() );
+
+	rt::init_allow_same( rt::Config::Custom( backend ) ).expect( "same backend" );
+
+	let other = rt::Config::Custom( rt::CustomBackend::new( ThreadPerTask::default() ) );
+
+	assert_eq!( &rt::ErrorKind::DoubleExecutorInit, rt::init_allow_same( other ).unwrap_err().kind() );
+}
+
+
+
+#[test]
+//
+fn exec()
+{
+	let calls = Arc::new( AtomicUsize::new( 0 ) );
+
+	let backend = rt::CustomBackend::new( Local{ supported: true, calls: calls.clone() } );
+
+	rt::exec( backend, |_|
+	{
+		rt::spawn( async {} ).expect( "spawn" );
+	});
+
+	assert_eq!( None, rt::current_rt() );
+	assert_eq!( 1, calls.load( Ordering::SeqCst ) );
+
+	POOL.with( |pool| pool.borrow_mut().run() );
+}
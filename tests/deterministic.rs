@@ -0,0 +1,204 @@
+#![ cfg(not( target_arch = "wasm32" )) ]
+#![ cfg( feature = "deterministic" ) ]
+
+// Tested:
+//
+// ✔ spawn, spawn_local, spawn_handle and spawn_handle_local
+// ✔ tasks can spawn while the executor runs
+// ✔ the same seed gives the same order
+// ✔ different seeds give different orders
+// ✔ run_until_stalled returns with pending tasks
+// ✔ run waits for wakeups from other threads
+// ✔ current_rt and seed
+// ✔ fuzz runs the test for every seed and propagates the panic
+//
+use
+{
+	async_runtime as rt,
+
+	std     :: { rc::Rc, cell::RefCell, time::Duration, task::{ Context, Poll } } ,
+	futures :: { future::{ pending, poll_fn }                                   } ,
+};
+
+
+
+// Let other tasks run.
+//
+async fn yield_now()
+{
+	let mut yielded = false;
+
+	poll_fn( |cx: &mut Context<'_>|
+	{
+		if yielded { return Poll::Ready(()) }
+
+		yielded = true;
+		cx.waker().wake_by_ref();
+		Poll::Pending
+
+	}).await
+}
+
+
+
+// Spawn some tasks that yield a couple of times and return the order in which they ran.
+//
+fn order( seed: u64 ) -> Vec<usize>
+{
+	let exec  = rt::Deterministic::new( seed );
+	let order = Rc::new( RefCell::new( Vec::new() ) );
+
+	for id in 0..5
+	{
+		let order = order.clone();
+
+		exec.spawn_local( async move
+		{
+			for _ in 0..3
+			{
+				order.borrow_mut().push( id );
+				yield_now().await;
+			}
+
+		}).expect( "spawn_local" );
+	}
+
+	exec.run();
+
+	let order = order.borrow().clone();
+	order
+}
+
+
+
+#[test]
+//
+fn spawn()
+{
+	rt::init( rt::Config::Deterministic{ seed: 1 } ).expect( "no double executor init" );
+
+	let local = Rc::new( RefCell::new( 0 ) );
+	let task  = local.clone();
+
+	rt::spawn      ( async {}                               ).expect( "spawn"       );
+	rt::spawn_local( async move { *task.borrow_mut() = 1; } ).expect( "spawn_local" );
+
+	let a = rt::spawn_handle      ( async { 2 } ).expect( "spawn_handle"       );
+	let b = rt::spawn_handle_local( async
+	{
+		// Spawn from within a task.
+		//
+		rt::spawn_handle( async { 3 } ).expect( "spawn_handle" ).await.expect( "join task" )
+
+	}).expect( "spawn_handle_local" );
+
+	let (a, b) = rt::deterministic::run_until( futures::future::join( a, b ) ).expect( "run" );
+
+	rt::deterministic::run().expect( "run" );
+
+	assert_eq!( 2, a.expect( "join task" ) );
+	assert_eq!( 3, b.expect( "join task" ) );
+	assert_eq!( 1, *local.borrow()         );
+}
+
+
+
+#[test]
+//
+fn same_seed()
+{
+	for seed in 0..10
+	{
+		assert_eq!( order( seed ), order( seed ) );
+	}
+}
+
+
+
+#[test]
+//
+fn different_seeds()
+{
+	let mut orders: Vec<_> = ( 0..10 ).map( order ).collect();
+
+	orders.sort();
+	orders.dedup();
+
+	assert!( orders.len() > 1 );
+}
+
+
+
+#[test]
+//
+fn run_until_stalled()
+{
+	rt::init( rt::Config::Deterministic{ seed: 1 } ).expect( "no double executor init" );
+
+	let handle = rt::spawn_handle( pending::<()>() ).expect( "spawn_handle" );
+
+	rt::spawn( async {} ).expect( "spawn" );
+	rt::deterministic::run_until_stalled().expect( "run" );
+
+	assert!( !handle.is_finished() );
+}
+
+
+
+#[test]
+//
+fn timer()
+{
+	rt::init( rt::Config::Deterministic{ seed: 1 } ).expect( "no double executor init" );
+
+	let mut handle = rt::spawn_handle( async
+	{
+		rt::time::sleep( Duration::from_millis( 10 ) ).await;
+		5
+
+	}).expect( "spawn_handle" );
+
+	rt::deterministic::run().expect( "run" );
+
+	assert_eq!( 5, handle.try_join().expect( "finished" ).expect( "join task" ) );
+}
+
+
+
+#[test]
+//
+fn config()
+{
+	assert_eq!( &rt::ErrorKind::NoExecutorInitialized, rt::deterministic::seed().unwrap_err().kind() );
+
+	rt::init( rt::Config::Deterministic{ seed: 42 } ).expect( "no double executor init" );
+
+	assert_eq!( Some( rt::Config::Deterministic{ seed: 42 } ), rt::current_rt()      );
+	assert_eq!( 42, rt::deterministic::seed().expect( "seed" ) );
+}
+
+
+
+#[test]
+//
+fn fuzz()
+{
+	let seeds = RefCell::new( Vec::new() );
+
+	rt::deterministic::fuzz( 0..5, ||
+	{
+		seeds.borrow_mut().push( rt::deterministic::seed().expect( "seed" ) );
+	});
+
+	assert_eq!( vec![ 0, 1, 2, 3, 4 ], *seeds.borrow() );
+
+	let result = std::panic::catch_unwind( ||
+	{
+		rt::deterministic::fuzz( 0..5, ||
+		{
+			assert_ne!( 3, rt::deterministic::seed().expect( "seed" ) );
+		});
+	});
+
+	assert!( result.is_err() );
+}
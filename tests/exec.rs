@@ -0,0 +1,149 @@
+#![ cfg(not( target_arch = "wasm32"    )) ]
+#![ cfg(     feature     = "localpool"  ) ]
+
+// Tested:
+//
+// ✔ spawn inside exec goes to the scoped executor, not the one set by init
+// ✔ the previous executor comes back after the scope
+// ✔ exec works on a thread without an executor
+// ✔ nested scopes
+// ✔ the scope is popped when the closure panics
+// ✔ localpool::run inside the scope runs the scoped pool
+// ✔ the executor passed to the closure implements Spawn
+// ✔ exec with a threadpool config
+//
+use
+{
+	async_runtime as rt,
+
+	std     :: { panic::{ catch_unwind, AssertUnwindSafe } } ,
+	futures :: { channel::oneshot, task::{ Spawn, SpawnExt } } ,
+};
+
+
+
+#[test]
+//
+fn exec_overrides_init()
+{
+	rt::init( rt::Config::LocalPool ).expect( "no double executor init" );
+
+	let isolated     = rt::LocalPool::new();
+	let (tx, mut rx) = oneshot::channel();
+
+	rt::exec( isolated.clone(), |_|
+	{
+		rt::spawn( async move { tx.send( 3u8 ).expect( "send on channel" ); } ).expect( "spawn" );
+	});
+
+	// The pool set by init does not have the future.
+	//
+	rt::localpool::run().expect( "run localpool" );
+	assert_eq!( Ok( None ), rx.try_recv() );
+
+	isolated.run();
+	assert_eq!( Ok( Some(3) ), rx.try_recv() );
+}
+
+
+
+#[test]
+//
+fn exec_without_init()
+{
+	assert_eq!( None, rt::current_rt() );
+
+	let number = rt::exec( rt::Config::LocalPool, |_|
+	{
+		assert_eq!( Some( rt::Config::LocalPool ), rt::current_rt() );
+
+		let handle = rt::spawn_handle_local( async { 5 } ).expect( "spawn_handle_local" );
+
+		rt::localpool::run_until( handle ).expect( "run localpool" ).expect( "join task" )
+	});
+
+	assert_eq!( 5, number );
+	assert_eq!( None, rt::current_rt() );
+
+	let result = rt::spawn( async {} );
+
+	assert_eq!( &rt::ErrorKind::NoExecutorInitialized, result.unwrap_err().kind() );
+}
+
+
+
+#[ cfg( feature = "threadpool" ) ]
+//
+#[test]
+//
+fn nested()
+{
+	let config = rt::Config::ThreadPool( rt::ThreadPoolConfig::default().workers( std::num::NonZeroUsize::MIN ) );
+
+	rt::init( rt::Config::LocalPool ).expect( "no double executor init" );
+
+	rt::exec( rt::LocalPool::new(), |_|
+	{
+		rt::exec( config.clone(), |_|
+		{
+			assert_eq!( Some( config.clone() ), rt::current_rt() );
+
+			let handle = rt::spawn_handle( async { rt::current_rt() } ).expect( "spawn_handle" );
+
+			// Tasks run on the worker threads, which use the threadpool as their executor.
+			//
+			assert_eq!( Some( config.clone() ), rt::block_on( handle ).expect( "join task" ) );
+
+			let result = rt::spawn_local( async {} );
+
+			assert_eq!( &rt::ErrorKind::SpawnLocalOnThreadPool, result.unwrap_err().kind() );
+		});
+
+		assert_eq!( Some( rt::Config::LocalPool ), rt::current_rt() );
+		rt::spawn_local( async {} ).expect( "spawn_local" );
+	});
+}
+
+
+
+#[test]
+//
+fn panic_pops_scope()
+{
+	let result = catch_unwind( AssertUnwindSafe( ||
+	{
+		rt::exec( rt::Config::LocalPool, |_| panic!( "boom" ) )
+	}));
+
+	assert!( result.is_err() );
+	assert_eq!( None, rt::current_rt() );
+}
+
+
+
+// Simulates a library that is generic over the executor.
+//
+fn lib_spawn( exec: &impl Spawn ) -> oneshot::Receiver<u8>
+{
+	let (tx, rx) = oneshot::channel();
+
+	exec.spawn( async move { tx.send( 5 ).expect( "send on channel" ); } ).expect( "spawn" );
+
+	rx
+}
+
+
+
+#[test]
+//
+fn exec_as_spawn()
+{
+	let out = rt::exec( rt::Config::LocalPool, |exec|
+	{
+		let rx = lib_spawn( exec );
+
+		rt::localpool::run_until( rx ).expect( "run localpool" )
+	});
+
+	assert_eq!( Ok(5), out );
+}
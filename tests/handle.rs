@@ -0,0 +1,229 @@
+#![ cfg(not( target_arch = "wasm32"   )) ]
+#![ cfg(     feature     = "localpool" ) ]
+
+// Tested:
+//
+// ✔ the handle is Send and Clone
+// ✔ a worker thread spawns on the localpool of the main thread, and the task runs on the main thread
+// ✔ a task spawned through the handle can spawn_local
+// ✔ spawning wakes up a localpool that waits for work
+// ✔ the handle spawns directly on a threadpool
+// ✔ inside `rt::exec`, the handle refers to the scoped executor
+// ✔ spawning errors when the executor dropped the task that receives the futures
+// ✔ rt::handle errors if no executor initialized
+// ✔ the handle can be passed as `T: Spawn`
+// ✔ the handle knows where the task was spawned
+//
+use
+{
+	async_runtime as rt,
+
+	std     :: { rc::Rc, cell::RefCell, thread, time::Duration                } ,
+	futures :: { channel::oneshot, task::SpawnExt, future::join_all            } ,
+	futures :: { task::{ FutureObj, LocalFutureObj }                           } ,
+};
+
+
+
+#[test]
+//
+fn send_clone()
+{
+	fn is_send_clone<T: Send + Sync + Clone>() {}
+
+	is_send_clone::< rt::ExecutorHandle >();
+}
+
+
+
+#[test]
+//
+fn from_worker()
+{
+	rt::init( rt::Config::LocalPool ).expect( "no double executor init" );
+
+	let handle = rt::handle().expect( "get executor handle" );
+
+	let workers: Vec<_> = (0..4).map( |i|
+	{
+		let handle = handle.clone();
+
+		thread::spawn( move || handle.spawn_handle( async move { ( i, thread::current().id() ) } ).expect( "spawn" ) )
+
+	}).collect();
+
+	let tasks: Vec<_> = workers.into_iter().map( |w| w.join().expect( "join worker" ) ).collect();
+	let outputs       = rt::localpool::run_until( join_all( tasks ) ).expect( "run localpool" );
+
+	for (i, out) in outputs.into_iter().enumerate()
+	{
+		assert_eq!( ( i, thread::current().id() ), out.expect( "join task" ) );
+	}
+}
+
+
+
+#[test]
+//
+fn spawn_local()
+{
+	rt::init( rt::Config::LocalPool ).expect( "no double executor init" );
+
+	let handle = rt::handle().expect( "get executor handle" );
+
+	// The outer future has to be Send, the one it spawns locally doesn't.
+	//
+	let task = thread::spawn( move || handle.spawn_handle( async
+	{
+		let (tx, rx) = oneshot::channel();
+
+		rt::spawn_local( async move
+		{
+			let number = Rc::new( RefCell::new( 1 ) );
+
+			*number.borrow_mut() += 1;
+
+			tx.send( *number.borrow() ).expect( "send" );
+
+		}).expect( "spawn_local" );
+
+		rx.await.expect( "receive" )
+
+	}).expect( "spawn" ) ).join().expect( "join worker" );
+
+	assert_eq!( 2, rt::localpool::run_until( task ).expect( "run localpool" ).expect( "join task" ) );
+}
+
+
+
+// The localpool parks the thread while there is nothing to do. The worker only spawns after that.
+//
+#[test]
+//
+fn wake_up()
+{
+	rt::init( rt::Config::LocalPool ).expect( "no double executor init" );
+
+	let handle   = rt::handle().expect( "get executor handle" );
+	let (tx, rx) = oneshot::channel();
+
+	let worker = thread::spawn( move ||
+	{
+		thread::sleep( Duration::from_millis( 50 ) );
+
+		handle.spawn( async move { tx.send( thread::current().id() ).expect( "send" ); } ).expect( "spawn" );
+	});
+
+	let id = rt::localpool::run_until( rx ).expect( "run localpool" ).expect( "receive" );
+
+	worker.join().expect( "join worker" );
+
+	assert_eq!( thread::current().id(), id );
+}
+
+
+
+#[ cfg( feature = "threadpool" ) ]
+//
+#[test]
+//
+fn threadpool()
+{
+	rt::init( rt::Config::ThreadPool( Default::default() ) ).expect( "no double executor init" );
+
+	let handle = rt::handle().expect( "get executor handle" );
+
+	let task = thread::spawn( move ||
+	{
+		handle.spawn_handle( async { rt::current_rt() } ).expect( "spawn" )
+
+	}).join().expect( "join worker" );
+
+	assert_eq!( Some( rt::Config::ThreadPool( Default::default() ) ), rt::block_on( task ).expect( "join task" ) );
+}
+
+
+
+#[test]
+//
+fn scoped()
+{
+	let pool = rt::LocalPool::new();
+
+	let handle = rt::exec( pool.clone(), |_| rt::handle() ).expect( "get executor handle" );
+
+	let task = thread::spawn( move || handle.spawn_handle( async { 3 } ).expect( "spawn" ) ).join().expect( "join worker" );
+
+	assert_eq!( 3, pool.run_until( task ).expect( "join task" ) );
+}
+
+
+
+// A backend that drops the futures it should run locally, so the task that receives the futures of the handle
+// never runs.
+//
+#[ derive( Debug ) ]
+//
+struct Dropper;
+
+impl rt::Backend for Dropper
+{
+	fn capabilities( &self ) -> rt::Capabilities { rt::Capabilities::default().spawn_local( true ) }
+
+	fn spawn      ( &self, _: FutureObj<'static, ()>      ) -> Result< (), rt::Error > { Ok(()) }
+	fn spawn_local( &self, _: LocalFutureObj<'static, ()> ) -> Result< (), rt::Error > { Ok(()) }
+}
+
+
+#[test]
+//
+fn dropped()
+{
+	rt::init_custom( Dropper ).expect( "no double executor init" );
+
+	let handle = rt::handle().expect( "get executor handle" );
+
+	let err = thread::spawn( move || handle.spawn( async {} ).unwrap_err() ).join().expect( "join worker" );
+
+	assert_eq!( &rt::ErrorKind::Spawn, err.kind() );
+}
+
+
+
+#[test]
+//
+fn no_executor()
+{
+	assert_eq!( &rt::ErrorKind::NoExecutorInitialized, rt::handle().unwrap_err().kind() );
+}
+
+
+
+#[test]
+//
+fn as_spawn()
+{
+	rt::init( rt::Config::LocalPool ).expect( "no double executor init" );
+
+	let handle = rt::handle().expect( "get executor handle" );
+
+	let task = thread::spawn( move || handle.spawn_with_handle( async { 4 } ).expect( "spawn" ) ).join().expect( "join worker" );
+
+	assert_eq!( 4, rt::localpool::run_until( task ).expect( "run localpool" ) );
+}
+
+
+
+#[test]
+//
+fn location()
+{
+	rt::init( rt::Config::LocalPool ).expect( "no double executor init" );
+
+	let handle = rt::handle().expect( "get executor handle" );
+	let task   = handle.spawn_handle( async {} ).expect( "spawn" );
+
+	assert_eq!( file!(), task.task().expect( "task info" ).location().file() );
+
+	rt::localpool::run_until( task ).expect( "run localpool" ).expect( "join task" );
+}
@@ -42,9 +42,9 @@ fn init_juliex()
 {
 	assert_eq!( None, rt::current_rt() );
 
-	rt::init( rt::Config::Juliex ).expect( "no double executor init" );
+	rt::init( rt::Config::Juliex( Default::default() ) ).expect( "no double executor init" );
 
-	assert_eq!( Some( rt::Config::Juliex ), rt::current_rt() );
+	assert_eq!( Some( rt::Config::Juliex( Default::default() ) ), rt::current_rt() );
 }
 
 
@@ -84,8 +84,8 @@ fn double_init_local()
 //
 fn double_init_juliex()
 {
-	             rt::init( rt::Config::Juliex ).expect( "no double executor init" );
-	let result = rt::init( rt::Config::Juliex );
+	             rt::init( rt::Config::Juliex( Default::default() ) ).expect( "no double executor init" );
+	let result = rt::init( rt::Config::Juliex( Default::default() ) );
 
 	assert_eq!( &rt::ErrorKind::DoubleExecutorInit, result.unwrap_err().kind() );
 }
@@ -99,7 +99,7 @@ fn double_init_juliex()
 fn double_init_different()
 {
 	             rt::init( rt::Config::LocalPool ).expect( "no double executor init" );
-	let result = rt::init( rt::Config::Juliex  );
+	let result = rt::init( rt::Config::Juliex( Default::default() ) );
 
 	assert_eq!( &rt::ErrorKind::DoubleExecutorInit, result.unwrap_err().kind() );
 }
@@ -112,7 +112,7 @@ fn double_init_different()
 //
 fn double_init_inverse()
 {
-	             rt::init( rt::Config::Juliex    ).expect( "no double executor init" );
+	             rt::init( rt::Config::Juliex( Default::default() ) ).expect( "no double executor init" );
 	let result = rt::init( rt::Config::LocalPool );
 
 	assert_eq!( &rt::ErrorKind::DoubleExecutorInit, result.unwrap_err().kind() );
@@ -141,8 +141,8 @@ fn double_init_allow_same_local()
 //
 fn double_init_allow_same_juliex()
 {
-	             rt::init_allow_same( rt::Config::Juliex ).expect( "no double executor init" );
-	let result = rt::init_allow_same( rt::Config::Juliex );
+	             rt::init_allow_same( rt::Config::Juliex( Default::default() ) ).expect( "no double executor init" );
+	let result = rt::init_allow_same( rt::Config::Juliex( Default::default() ) );
 
 	assert!( result.is_ok() );
 }
@@ -156,7 +156,7 @@ fn double_init_allow_same_juliex()
 fn double_init_allow_same_different()
 {
 	             rt::init_allow_same( rt::Config::LocalPool ).expect( "no double executor init" );
-	let result = rt::init_allow_same( rt::Config::Juliex  );
+	let result = rt::init_allow_same( rt::Config::Juliex( Default::default() ) );
 
 	assert_eq!( &rt::ErrorKind::DoubleExecutorInit, result.unwrap_err().kind() );
 }
@@ -169,7 +169,7 @@ fn double_init_allow_same_different()
 //
 fn double_init_allow_same_inverse()
 {
-	             rt::init_allow_same( rt::Config::Juliex    ).expect( "no double executor init" );
+	             rt::init_allow_same( rt::Config::Juliex( Default::default() ) ).expect( "no double executor init" );
 	let result = rt::init_allow_same( rt::Config::LocalPool );
 
 	assert_eq!( &rt::ErrorKind::DoubleExecutorInit, result.unwrap_err().kind() );
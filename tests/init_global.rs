@@ -0,0 +1,161 @@
+#![ cfg(not( target_arch = "wasm32"    )) ]
+#![ cfg(     feature     = "threadpool" ) ]
+
+// Tested:
+//
+// ✔ a thread that never called init spawns on the global executor
+// ✔ a thread that called init uses it's own executor
+// ✔ inside `rt::exec`, the scoped executor takes precedence
+// ✔ spawn_local on the global executor errors
+// ✔ the global executor can only be set once
+// ✔ single threaded executors are refused
+// ✔ custom backends that spawn `!Send` futures are refused
+//
+// The global executor is shared by all tests in this file, so they all set the same one.
+//
+use
+{
+	async_runtime as rt,
+
+	std     :: { thread                                  } ,
+	futures :: { task::{ FutureObj, LocalFutureObj }     } ,
+};
+
+
+fn global() -> rt::Config
+{
+	let config = rt::Config::ThreadPool( rt::ThreadPoolConfig::default().name_prefix( "global" ) );
+
+	match rt::init_global( config.clone() )
+	{
+		Ok(()) => {}
+		Err(e) => assert_eq!( &rt::ErrorKind::DoubleExecutorInit, e.kind() ),
+	}
+
+	config
+}
+
+
+
+#[test]
+//
+fn fallback()
+{
+	let config = global();
+
+	let (current, handle) = thread::spawn( ||
+	{
+		let handle = rt::spawn_handle( async { thread::current().name().map( String::from ) } ).expect( "spawn" );
+
+		( rt::current_rt(), handle )
+
+	}).join().expect( "join thread" );
+
+	assert_eq!( Some( config ), current );
+
+	let name = rt::block_on( handle ).expect( "join task" ).expect( "worker has a name" );
+
+	assert!( name.starts_with( "global" ) );
+}
+
+
+
+#[ cfg( feature = "localpool" ) ]
+//
+#[test]
+//
+fn own_executor()
+{
+	global();
+
+	let current = thread::spawn( ||
+	{
+		rt::init( rt::Config::LocalPool ).expect( "no double executor init" );
+		rt::current_rt()
+
+	}).join().expect( "join thread" );
+
+	assert_eq!( Some( rt::Config::LocalPool ), current );
+}
+
+
+
+#[ cfg( feature = "localpool" ) ]
+//
+#[test]
+//
+fn scoped()
+{
+	global();
+
+	let current = thread::spawn( ||
+	{
+		rt::exec( rt::Config::LocalPool, |_| rt::current_rt() )
+
+	}).join().expect( "join thread" );
+
+	assert_eq!( Some( rt::Config::LocalPool ), current );
+}
+
+
+
+#[test]
+//
+fn spawn_local()
+{
+	global();
+
+	let err = thread::spawn( || rt::spawn_local( async {} ).unwrap_err() ).join().expect( "join thread" );
+
+	assert_eq!( &rt::ErrorKind::SpawnLocalOnThreadPool, err.kind() );
+}
+
+
+
+#[test]
+//
+fn double()
+{
+	global();
+
+	let err = rt::init_global( rt::Config::ThreadPool( Default::default() ) ).unwrap_err();
+
+	assert_eq!( &rt::ErrorKind::DoubleExecutorInit, err.kind() );
+}
+
+
+
+#[ cfg( feature = "localpool" ) ]
+//
+#[test]
+//
+fn not_send()
+{
+	let err = rt::init_global( rt::Config::LocalPool ).unwrap_err();
+
+	assert_eq!( &rt::ErrorKind::GlobalExecutorNotSend, err.kind() );
+}
+
+
+
+#[ derive( Debug ) ]
+//
+struct Local;
+
+impl rt::Backend for Local
+{
+	fn capabilities( &self ) -> rt::Capabilities { rt::Capabilities::default().spawn_local( true ) }
+
+	fn spawn      ( &self, _: FutureObj<'static, ()>      ) -> Result< (), rt::Error > { Ok(()) }
+	fn spawn_local( &self, _: LocalFutureObj<'static, ()> ) -> Result< (), rt::Error > { Ok(()) }
+}
+
+
+#[test]
+//
+fn custom_local()
+{
+	let err = rt::init_global( rt::Config::Custom( rt::CustomBackend::new( Local ) ) ).unwrap_err();
+
+	assert_eq!( &rt::ErrorKind::GlobalExecutorNotSend, err.kind() );
+}
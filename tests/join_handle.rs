@@ -0,0 +1,307 @@
+#![ cfg(not( target_arch = "wasm32" )) ]
+#![ cfg(any( feature = "localpool", feature = "async_std", feature = "threadpool", feature = "juliex", feature = "tokio_ct" )) ]
+
+// Tested:
+//
+// ✔ dropping the handle cancels the task on localpool
+// ✔ dropping the handle cancels the task on async-std
+// ✔ detach lets the task run to completion on localpool
+// ✔ detach lets the task run to completion on threadpool
+// ✔ abort cancels the task
+// ✔ is_finished and try_join before and after running the task
+// ✔ is_finished is true and try_join returns a cancelled JoinError for a cancelled task
+// ✔ awaiting an aborted task returns a cancelled JoinError
+// ✔ a panic is returned as JoinError on localpool, threadpool, juliex, async-std and tokio_ct
+// ✔ JoinError converts into rt::Error
+//
+use async_runtime as rt;
+
+#[ cfg(any( feature = "localpool", feature = "async_std", feature = "threadpool" )) ]
+//
+use futures::channel::oneshot;
+
+
+
+// Dropping the handle cancels the task.
+//
+#[ cfg( feature = "localpool" ) ]
+//
+#[test]
+//
+fn drop_cancels_localpool()
+{
+	rt::init( rt::Config::LocalPool ).expect( "no double executor init" );
+
+	let (tx, rx) = oneshot::channel::<()>();
+
+	let handle = rt::spawn_handle( async move { tx.send(()).expect( "send on channel" ); } ).expect( "spawn_handle" );
+
+	drop( handle );
+	rt::localpool::run().expect( "run localpool" );
+
+	// The sender was dropped without sending.
+	//
+	assert!( rt::block_on( rx ).is_err() );
+}
+
+
+
+// Dropping the handle cancels the task, also on async-std which detaches by default.
+//
+#[ cfg( feature = "async_std" ) ]
+//
+#[test]
+//
+fn drop_cancels_async_std()
+{
+	rt::init( rt::Config::AsyncStd ).expect( "no double executor init" );
+
+	let (start_tx, start_rx) = oneshot::channel::<()>();
+	let (tx      , rx      ) = oneshot::channel::<()>();
+
+	let handle = rt::spawn_handle( async move
+	{
+		// wait so we can drop the handle before sending.
+		//
+		let _ = start_rx.await;
+		let _ = tx.send(());
+
+	}).expect( "spawn_handle" );
+
+	drop( handle   );
+	drop( start_tx );
+
+	assert!( rt::block_on( rx ).is_err() );
+}
+
+
+
+// Detach lets the task run to completion.
+//
+#[ cfg( feature = "localpool" ) ]
+//
+#[test]
+//
+fn detach_localpool()
+{
+	rt::init( rt::Config::LocalPool ).expect( "no double executor init" );
+
+	let (tx, rx) = oneshot::channel();
+
+	rt::spawn_handle( async move { tx.send( 3 ).expect( "send on channel" ); } ).expect( "spawn_handle" ).detach();
+
+	rt::localpool::run().expect( "run localpool" );
+
+	assert_eq!( 3, rt::block_on( rx ).expect( "wait on channel" ) );
+}
+
+
+
+// Detach lets the task run to completion.
+//
+#[ cfg( feature = "threadpool" ) ]
+//
+#[test]
+//
+fn detach_threadpool()
+{
+	rt::init( rt::Config::ThreadPool( Default::default() ) ).expect( "no double executor init" );
+
+	let (start_tx, start_rx) = oneshot::channel::<()>();
+	let (tx      , rx      ) = oneshot::channel();
+
+	rt::spawn_handle( async move
+	{
+		start_rx.await.expect( "wait on channel" );
+		tx.send( 3 ).expect( "send on channel" );
+
+	}).expect( "spawn_handle" ).detach();
+
+	start_tx.send(()).expect( "send on channel" );
+
+	assert_eq!( 3, rt::block_on( rx ).expect( "wait on channel" ) );
+}
+
+
+
+// Abort cancels the task.
+//
+#[ cfg( feature = "localpool" ) ]
+//
+#[test]
+//
+fn abort()
+{
+	rt::init( rt::Config::LocalPool ).expect( "no double executor init" );
+
+	let (tx, rx) = oneshot::channel::<()>();
+
+	let mut handle = rt::spawn_handle( async move { tx.send(()).expect( "send on channel" ); } ).expect( "spawn_handle" );
+
+	handle.abort();
+	rt::localpool::run().expect( "run localpool" );
+
+	assert!( handle.is_finished() );
+	assert!( handle.try_join().expect( "finished" ).unwrap_err().is_cancelled() );
+	assert!( rt::block_on( rx ).is_err() );
+}
+
+
+
+// Awaiting an aborted task gives a cancelled JoinError.
+//
+#[ cfg( feature = "localpool" ) ]
+//
+#[test]
+//
+fn await_aborted()
+{
+	rt::init( rt::Config::LocalPool ).expect( "no double executor init" );
+
+	let handle = rt::spawn_handle( async { 5 } ).expect( "spawn_handle" );
+
+	handle.abort();
+
+	let err = rt::localpool::run_until( handle ).expect( "run localpool" ).unwrap_err();
+
+	assert_eq!( &rt::JoinErrorKind::Cancelled, err.kind() );
+	assert!( err.into_panic().is_none() );
+}
+
+
+
+// is_finished and try_join.
+//
+#[ cfg( feature = "localpool" ) ]
+//
+#[test]
+//
+fn try_join()
+{
+	rt::init( rt::Config::LocalPool ).expect( "no double executor init" );
+
+	let mut handle = rt::spawn_handle( async { 5 } ).expect( "spawn_handle" );
+
+	assert!( !handle.is_finished()       );
+	assert!(  handle.try_join().is_none() );
+
+	rt::localpool::run().expect( "run localpool" );
+
+	assert!( handle.is_finished() );
+	assert_eq!( 5, handle.try_join().expect( "finished" ).expect( "no panic" ) );
+
+	// The output can only be recovered once.
+	//
+	assert!( handle.try_join().is_none() );
+}
+
+
+
+// Verify the panic gets reported through the handle with it's payload.
+//
+#[ cfg(any( feature = "localpool", feature = "juliex", feature = "threadpool", feature = "async_std", feature = "tokio_ct" )) ]
+//
+fn check_panic( err: rt::JoinError )
+{
+	assert!( err.is_panic() );
+	assert!( err.to_string().contains( "boom" ) );
+
+	let payload = err.into_panic().expect( "panic payload" );
+
+	assert_eq!( Some( &"boom" ), payload.downcast_ref::<&str>() );
+}
+
+
+
+#[ cfg( feature = "localpool" ) ]
+//
+#[test]
+//
+fn panic_localpool()
+{
+	rt::init( rt::Config::LocalPool ).expect( "no double executor init" );
+
+	let handle = rt::spawn_handle( async { panic!( "boom" ) } ).expect( "spawn_handle" );
+
+	check_panic( rt::localpool::run_until( handle ).expect( "run localpool" ).unwrap_err() );
+}
+
+
+
+#[ cfg( feature = "threadpool" ) ]
+//
+#[test]
+//
+fn panic_threadpool()
+{
+	rt::init( rt::Config::ThreadPool( Default::default() ) ).expect( "no double executor init" );
+
+	let handle = rt::spawn_handle( async { panic!( "boom" ) } ).expect( "spawn_handle" );
+
+	check_panic( rt::block_on( handle ).unwrap_err() );
+}
+
+
+
+#[ cfg( feature = "juliex" ) ]
+//
+#[test]
+//
+fn panic_juliex()
+{
+	rt::init( rt::Config::Juliex( Default::default() ) ).expect( "no double executor init" );
+
+	let handle = rt::spawn_handle( async { panic!( "boom" ) } ).expect( "spawn_handle" );
+
+	check_panic( rt::block_on( handle ).unwrap_err() );
+}
+
+
+
+#[ cfg( feature = "async_std" ) ]
+//
+#[test]
+//
+fn panic_async_std()
+{
+	rt::init( rt::Config::AsyncStd ).expect( "no double executor init" );
+
+	let handle = rt::spawn_handle( async { panic!( "boom" ) } ).expect( "spawn_handle" );
+
+	check_panic( rt::block_on( handle ).unwrap_err() );
+}
+
+
+
+#[ cfg( feature = "tokio_ct" ) ]
+//
+#[test]
+//
+fn panic_tokio_ct()
+{
+	rt::init( rt::Config::TokioCt( Default::default() ) ).expect( "no double executor init" );
+
+	let handle = rt::spawn_handle( async { panic!( "boom" ) } ).expect( "spawn_handle" );
+
+	rt::tokio_ct::run().expect( "run tokio_ct" );
+
+	check_panic( rt::block_on( handle ).unwrap_err() );
+}
+
+
+
+// The JoinError converts into our general error type.
+//
+#[ cfg( feature = "localpool" ) ]
+//
+#[test]
+//
+fn into_error()
+{
+	rt::init( rt::Config::LocalPool ).expect( "no double executor init" );
+
+	let handle = rt::spawn_handle( async { panic!( "boom" ) } ).expect( "spawn_handle" );
+	let err: rt::Error = rt::localpool::run_until( handle ).expect( "run localpool" ).unwrap_err().into();
+
+	assert_eq!( &rt::ErrorKind::Join, err.kind() );
+}
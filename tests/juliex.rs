@@ -28,7 +28,7 @@ use
 //
 fn basic_spawn()
 {
-	rt::init( rt::Config::Juliex ).expect( "no double executor init" );
+	rt::init( rt::Config::Juliex( Default::default() ) ).expect( "no double executor init" );
 
 	let (tx, rx) = oneshot::channel();
 
@@ -54,7 +54,7 @@ fn basic_spawn()
 //
 fn spawn_boxed()
 {
-	rt::init( rt::Config::Juliex ).expect( "no double executor init" );
+	rt::init( rt::Config::Juliex( Default::default() ) ).expect( "no double executor init" );
 
 	let (tx, rx) = oneshot::channel();
 
@@ -79,7 +79,7 @@ fn spawn_boxed()
 //
 fn several()
 {
-	rt::init( rt::Config::Juliex ).expect( "no double executor init" );
+	rt::init( rt::Config::Juliex( Default::default() ) ).expect( "no double executor init" );
 
 	let (tx , rx ) = oneshot::channel();
 	let (tx2, rx2) = oneshot::channel();
@@ -109,7 +109,7 @@ fn several()
 //
 fn within()
 {
-	rt::init( rt::Config::Juliex ).expect( "no double executor init" );
+	rt::init( rt::Config::Juliex( Default::default() ) ).expect( "no double executor init" );
 
 	let (tx , rx ) = oneshot::channel();
 	let (tx2, rx2) = oneshot::channel();
@@ -122,7 +122,7 @@ fn within()
 
 		let task = async move
 		{
-			assert_eq!( rt::Config::Juliex, rt::current_rt().expect( "some executor" ) );
+			assert_eq!( rt::Config::Juliex( Default::default() ), rt::current_rt().expect( "some executor" ) );
 
 			tx2.send( 5 + rx.await.expect( "channel" ) ).expect( "send on channel" );
 		};
@@ -144,7 +144,7 @@ fn within()
 //
 fn not_running_local()
 {
-	rt::init( rt::Config::Juliex ).expect( "no double executor init" );
+	rt::init( rt::Config::Juliex( Default::default() ) ).expect( "no double executor init" );
 
 	let (tx, rx) = oneshot::channel();
 
@@ -170,7 +170,7 @@ fn not_running_local()
 //
 fn spawn_local_on_thread_pool()
 {
-	rt::init( rt::Config::Juliex ).expect( "no double executor init" );
+	rt::init( rt::Config::Juliex( Default::default() ) ).expect( "no double executor init" );
 
 	let res = rt::spawn_local( async {} );
 
@@ -188,11 +188,11 @@ fn spawn_local_on_thread_pool()
 //
 fn spawn_handle()
 {
-	rt::init( rt::Config::Juliex ).expect( "no double executor init" );
+	rt::init( rt::Config::Juliex( Default::default() ) ).expect( "no double executor init" );
 
 	let handle = rt::spawn_handle( async { "hello".to_string() } ).expect( "spawn_handle" );
 
-	rt::block_on( async { assert_eq!( "hello", &handle.await ); } );
+	rt::block_on( async { assert_eq!( "hello", &handle.await.expect( "join task" ) ); } );
 }
 
 
@@ -203,13 +203,13 @@ fn spawn_handle()
 //
 fn spawn_handle_spawn_on_self()
 {
-	rt::init( rt::Config::Juliex ).expect( "no double executor init" );
+	rt::init( rt::Config::Juliex( Default::default() ) ).expect( "no double executor init" );
 
 	let handle = rt::spawn_handle( async
 	{
 		rt::spawn( async
 		{
-			assert_eq!( rt::Config::Juliex, rt::current_rt().expect( "some executor" ) );
+			assert_eq!( rt::Config::Juliex( Default::default() ), rt::current_rt().expect( "some executor" ) );
 
 		}).expect( "spawn" );
 
@@ -217,7 +217,7 @@ fn spawn_handle_spawn_on_self()
 
 	}).expect( "spawn_handle" );
 
-	rt::block_on( async { assert_eq!( "hello", &handle.await ); } );
+	rt::block_on( async { assert_eq!( "hello", &handle.await.expect( "join task" ) ); } );
 }
 
 
@@ -228,7 +228,7 @@ fn spawn_handle_spawn_on_self()
 //
 fn spawn_handle_local()
 {
-	rt::init( rt::Config::Juliex ).expect( "no double executor init" );
+	rt::init( rt::Config::Juliex( Default::default() ) ).expect( "no double executor init" );
 
 	let handle = rt::spawn_handle_local( async {});
 
@@ -243,5 +243,3 @@ fn spawn_handle_local()
 	}
 }
 
-
-
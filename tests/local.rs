@@ -13,6 +13,7 @@
 // ✔ localpools on several threads
 // ✔ spawn_handle returns the right value
 // ✔ spawn_handle_local returns the right value and can spawn !Send futures
+// ✔ spawn_handle_local can return a !Send output
 // ✔ rt::localpool::run should error if no executor initialized
 // ✔ rt::localpool::run should error if the wrong executor is initialized
 
@@ -228,7 +229,7 @@ fn spawn_handle()
 
 	rt::localpool::run().expect( "run localpool" );
 
-	rt::block_on( async { assert_eq!( "hello", &handle.await ); } );
+	rt::block_on( async { assert_eq!( "hello", &handle.await.expect( "join task" ) ); } );
 }
 
 
@@ -255,7 +256,28 @@ fn spawn_handle_local()
 
 	rt::localpool::run().expect( "run localpool" );
 
-	rt::block_on( async { assert_eq!( "hello", &handle.await ); } );
+	rt::block_on( async { assert_eq!( "hello", &handle.await.expect( "join task" ) ); } );
+}
+
+
+
+// The output of a local task doesn't have to be Send.
+//
+#[test]
+//
+fn spawn_handle_local_not_send()
+{
+	rt::init( rt::Config::LocalPool ).expect( "no double executor init" );
+
+	let handle = rt::spawn_handle_local( async { Rc::new( RefCell::new( 5 ) ) } ).expect( "spawn_handle_local" );
+
+	rt::localpool::run().expect( "run localpool" );
+
+	let out = rt::block_on( handle ).expect( "join task" );
+
+	*out.borrow_mut() += 1;
+
+	assert_eq!( 6, *out.borrow() );
 }
 
 
@@ -0,0 +1,151 @@
+#![ cfg(not( target_arch = "wasm32" )) ]
+#![ cfg(all( feature = "metrics", feature = "localpool" )) ]
+
+// Tested:
+//
+// ✔ spawned, completed and polls are counted
+// ✔ a pending task is alive
+// ✔ a cancelled task is no longer alive, but not completed
+// ✔ a panicked task is counted
+// ✔ counters are kept per executor on the same thread
+// ✔ the counters of a thread that exited are dropped once it's tasks are gone
+//
+use
+{
+	async_runtime as rt,
+
+	std     :: { thread                            } ,
+	futures :: { channel::oneshot, future::pending } ,
+};
+
+
+
+// The metrics for the given executor on the current thread. Tests run on their own thread.
+//
+fn current( executor: &str ) -> rt::metrics::TaskMetrics
+{
+	rt::metrics::snapshot().into_iter()
+
+		.find( |m| m.thread == thread::current().id() && m.executor == executor )
+		.expect( "metrics for this thread" )
+}
+
+
+
+#[test]
+//
+fn completed()
+{
+	rt::init( rt::Config::LocalPool ).expect( "no double executor init" );
+
+	let (tx, rx) = oneshot::channel();
+
+	rt::spawn( async move { rx.await.expect( "channel" ); } ).expect( "spawn" );
+	rt::spawn( async move { tx.send(()).expect( "send" ); } ).expect( "spawn" );
+
+	rt::localpool::run().expect( "run localpool" );
+
+	let metrics = current( "LocalPool" );
+
+	assert_eq!( metrics.thread_name.as_deref(), thread::current().name() );
+	assert_eq!( 2, metrics.spawned   );
+	assert_eq!( 2, metrics.completed );
+	assert_eq!( 0, metrics.alive     );
+	assert_eq!( 0, metrics.panicked  );
+	assert!   (    metrics.polls >= 3 );
+}
+
+
+
+#[test]
+//
+fn alive_and_cancelled()
+{
+	rt::init( rt::Config::LocalPool ).expect( "no double executor init" );
+
+	let handle = rt::spawn_handle( pending::<()>() ).expect( "spawn_handle" );
+
+	rt::localpool::run_until_stalled().expect( "run localpool" );
+
+	assert_eq!( 1, current( "LocalPool" ).alive );
+
+	drop( handle );
+	rt::localpool::run().expect( "run localpool" );
+
+	let metrics = current( "LocalPool" );
+
+	assert_eq!( 1, metrics.spawned   );
+	assert_eq!( 0, metrics.alive     );
+	assert_eq!( 0, metrics.completed );
+	assert_eq!( 0, metrics.panicked  );
+}
+
+
+
+#[test]
+//
+fn panicked()
+{
+	rt::init( rt::Config::LocalPool ).expect( "no double executor init" );
+
+	let handle = rt::spawn_handle( async { panic!( "boom" ) } ).expect( "spawn_handle" );
+
+	let result: Result<(), _> = rt::localpool::run_until( handle ).expect( "run localpool" );
+
+	assert!( result.unwrap_err().is_panic() );
+
+	let metrics = current( "LocalPool" );
+
+	assert_eq!( 1, metrics.panicked  );
+	assert_eq!( 0, metrics.completed );
+	assert_eq!( 0, metrics.alive     );
+}
+
+
+
+#[ cfg( feature = "threadpool" ) ]
+//
+#[test]
+//
+fn per_executor()
+{
+	rt::init( rt::Config::LocalPool ).expect( "no double executor init" );
+
+	rt::spawn( async {} ).expect( "spawn" );
+
+	let handle = rt::exec( rt::Config::ThreadPool( Default::default() ), |_|
+	{
+		rt::spawn_handle( async {} ).expect( "spawn_handle" )
+	});
+
+	rt::block_on( handle ).expect( "join task" );
+
+	assert_eq!( 1, current( "LocalPool"  ).spawned   );
+	assert_eq!( 1, current( "ThreadPool" ).spawned   );
+	assert_eq!( 1, current( "ThreadPool" ).completed );
+}
+
+
+
+// Short lived threads must not make the registry grow forever.
+//
+#[test]
+//
+fn exited_thread()
+{
+	let thread = thread::spawn( ||
+	{
+		rt::init( rt::Config::LocalPool ).expect( "no double executor init" );
+
+		rt::spawn( async {} ).expect( "spawn" );
+		rt::localpool::run().expect( "run localpool" );
+
+		assert_eq!( 1, current( "LocalPool" ).completed );
+
+		thread::current().id()
+	});
+
+	let id = thread.join().expect( "join thread" );
+
+	assert!( rt::metrics::snapshot().iter().all( |m| m.thread != id ) );
+}
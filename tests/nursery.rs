@@ -0,0 +1,218 @@
+#![ cfg(not( target_arch = "wasm32" )) ]
+#![ cfg(any( feature = "localpool", feature = "threadpool" )) ]
+
+// Tested:
+//
+// ✔ awaiting the nursery returns the output of all children in order
+// ✔ an empty nursery resolves immediately
+// ✔ fail-fast returns the first error and cancels the other children
+// ✔ a panicking child is returned as ErrorKind::Join
+// ✔ collect_all waits for all children and returns each result
+// ✔ dropping the nursery cancels the children
+// ✔ nurse_local can spawn !Send futures
+// ✔ nurse_local can collect !Send outputs
+// ✔ nursery on a threadpool
+//
+use async_runtime as rt;
+
+#[ cfg( feature = "localpool" ) ]
+//
+use
+{
+	std     :: { rc::Rc                            } ,
+	futures :: { channel::oneshot, future::pending } ,
+};
+
+
+
+#[ cfg( feature = "localpool" ) ]
+//
+#[test]
+//
+fn all_ok()
+{
+	rt::init( rt::Config::LocalPool ).expect( "no double executor init" );
+
+	let mut nursery = rt::Nursery::new();
+	let (tx, rx)    = oneshot::channel();
+
+	// The first child finishes last.
+	//
+	nursery.nurse( async move { Ok::<_, rt::Error>( rx.await.expect( "channel" ) ) } ).expect( "nurse" );
+	nursery.nurse( async move { tx.send( 2 ).expect( "send on channel" ); Ok( 1 ) } ).expect( "nurse" );
+
+	assert_eq!( 2, nursery.len() );
+
+	let results = rt::localpool::run_until( nursery ).expect( "run localpool" );
+
+	assert_eq!( vec![ 2, 1 ], results.expect( "no child failed" ) );
+}
+
+
+
+#[ cfg( feature = "localpool" ) ]
+//
+#[test]
+//
+fn empty()
+{
+	rt::init( rt::Config::LocalPool ).expect( "no double executor init" );
+
+	let nursery = rt::Nursery::<u8, rt::Error>::new();
+
+	assert!( nursery.is_empty() );
+	assert_eq!( Vec::<u8>::new(), rt::block_on( nursery ).expect( "no child failed" ) );
+}
+
+
+
+#[ cfg( feature = "localpool" ) ]
+//
+#[test]
+//
+fn fail_fast()
+{
+	rt::init( rt::Config::LocalPool ).expect( "no double executor init" );
+
+	let mut nursery  = rt::Nursery::new();
+	let (tx, mut rx) = oneshot::channel::<()>();
+
+	nursery.nurse( async move { let _tx = tx; pending::<()>().await; Ok(()) } ).expect( "nurse" );
+	nursery.nurse( async { Err( rt::Error::from( rt::ErrorKind::Spawn ) ) }  ).expect( "nurse" );
+
+	let result = rt::localpool::run_until( nursery ).expect( "run localpool" );
+
+	assert_eq!( &rt::ErrorKind::Spawn, result.unwrap_err().kind() );
+
+	// The pending child was cancelled, so it dropped the sender.
+	//
+	rt::localpool::run().expect( "run localpool" );
+	assert!( rx.try_recv().is_err() );
+}
+
+
+
+#[ cfg( feature = "localpool" ) ]
+//
+#[test]
+//
+fn panic()
+{
+	rt::init( rt::Config::LocalPool ).expect( "no double executor init" );
+
+	let mut nursery = rt::Nursery::new();
+
+	nursery.nurse( async { Ok::<u8, rt::Error>( 1 ) } ).expect( "nurse" );
+	nursery.nurse( async { panic!( "boom" ) }           ).expect( "nurse" );
+
+	let result = rt::localpool::run_until( nursery ).expect( "run localpool" );
+
+	assert_eq!( &rt::ErrorKind::Join, result.unwrap_err().kind() );
+}
+
+
+
+#[ cfg( feature = "localpool" ) ]
+//
+#[test]
+//
+fn collect_all()
+{
+	rt::init( rt::Config::LocalPool ).expect( "no double executor init" );
+
+	let mut nursery = rt::Nursery::new();
+
+	nursery.nurse( async { Err ( rt::Error::from( rt::ErrorKind::Spawn ) ) } ).expect( "nurse" );
+	nursery.nurse( async { Ok  ( 2u8 )                                     } ).expect( "nurse" );
+	nursery.nurse( async { panic!( "boom" )                                } ).expect( "nurse" );
+
+	let results = rt::localpool::run_until( nursery.collect_all() ).expect( "run localpool" );
+
+	assert_eq!( 3, results.len() );
+	assert_eq!( &rt::ErrorKind::Spawn, results[0].as_ref().unwrap_err().kind() );
+	assert_eq!( 2                    , *results[1].as_ref().expect( "child succeeded" ) );
+	assert_eq!( &rt::ErrorKind::Join , results[2].as_ref().unwrap_err().kind() );
+}
+
+
+
+#[ cfg( feature = "localpool" ) ]
+//
+#[test]
+//
+fn drop_cancels()
+{
+	rt::init( rt::Config::LocalPool ).expect( "no double executor init" );
+
+	let mut nursery  = rt::Nursery::<(), rt::Error>::new();
+	let (tx, mut rx) = oneshot::channel();
+
+	nursery.nurse( async move { tx.send(()).expect( "send on channel" ); Ok(()) } ).expect( "nurse" );
+
+	drop( nursery );
+	rt::localpool::run().expect( "run localpool" );
+
+	assert!( rx.try_recv().is_err() );
+}
+
+
+
+#[ cfg( feature = "localpool" ) ]
+//
+#[test]
+//
+fn nurse_local()
+{
+	rt::init( rt::Config::LocalPool ).expect( "no double executor init" );
+
+	let mut nursery = rt::Nursery::new();
+	let rc          = Rc::new( 5u8 );
+
+	nursery.nurse_local( async move { Ok::<_, rt::Error>( *rc ) } ).expect( "nurse_local" );
+
+	let results = rt::localpool::run_until( nursery ).expect( "run localpool" );
+
+	assert_eq!( vec![ 5 ], results.expect( "no child failed" ) );
+}
+
+
+
+#[ cfg( feature = "localpool" ) ]
+//
+#[test]
+//
+fn nurse_local_rc_output()
+{
+	rt::init( rt::Config::LocalPool ).expect( "no double executor init" );
+
+	let mut nursery = rt::Nursery::new();
+
+	nursery.nurse_local( async { Ok::<_, rt::Error>( Rc::new( 5u8 ) ) } ).expect( "nurse_local" );
+	nursery.nurse_local( async { Ok::<_, rt::Error>( Rc::new( 6u8 ) ) } ).expect( "nurse_local" );
+
+	let results = rt::localpool::run_until( nursery ).expect( "run localpool" ).expect( "no child failed" );
+
+	assert_eq!( vec![ Rc::new( 5 ), Rc::new( 6 ) ], results );
+}
+
+
+
+#[ cfg( feature = "threadpool" ) ]
+//
+#[test]
+//
+fn threadpool()
+{
+	rt::init( rt::Config::ThreadPool( Default::default() ) ).expect( "no double executor init" );
+
+	let mut nursery = rt::Nursery::new();
+
+	for i in 0..10u8
+	{
+		nursery.nurse( async move { Ok::<_, rt::Error>( i ) } ).expect( "nurse" );
+	}
+
+	let results = rt::block_on( nursery ).expect( "no child failed" );
+
+	assert_eq!( (0..10).collect::<Vec<u8>>(), results );
+}
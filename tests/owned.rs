@@ -0,0 +1,288 @@
+#![ cfg(not( target_arch = "wasm32" )) ]
+#![ cfg(any( feature = "localpool", feature = "async_std", feature = "threadpool", feature = "juliex", feature = "tokio_ct" )) ]
+
+// Tested:
+//
+// ✔ owned LocalPool can be passed as T: Spawn and T: LocalSpawn
+// ✔ owned LocalPool installed with init_with is used by rt::spawn
+// ✔ init_with fails if the thread already has an executor
+// ✔ owned ThreadPool can be passed as T: Spawn
+// ✔ worker threads of an owned ThreadPool spawn on the same pool, not on the shared one
+// ✔ dropping the last clone of an owned ThreadPool stops it's worker threads
+// ✔ owned Juliex can be passed as T: Spawn and it's workers spawn on the same pool
+// ✔ dropping the last clone of an owned Juliex stops it's worker threads
+// ✔ owned AsyncStd can be passed as T: Spawn
+// ✔ owned TokioCt can be passed as T: LocalSpawn and spawning from within a task works
+//
+use
+{
+	async_runtime as rt,
+
+	futures :: { channel::oneshot, task::{ Spawn, SpawnExt } } ,
+};
+
+#[ cfg(any( feature = "threadpool", feature = "juliex" )) ]
+//
+use
+{
+	std :: { cell::RefCell as StdRefCell, sync::mpsc, time::Duration } ,
+};
+
+#[ cfg(any( feature = "localpool", feature = "tokio_ct" )) ]
+//
+use
+{
+	std     :: { rc::Rc, cell::RefCell                } ,
+	futures :: { task::{ LocalSpawn, LocalSpawnExt } } ,
+};
+
+
+
+// Simulates a library that is generic over the executor.
+//
+fn lib_spawn( exec: &impl Spawn ) -> oneshot::Receiver<u8>
+{
+	let (tx, rx) = oneshot::channel();
+
+	exec.spawn( async move { tx.send( 5 ).expect( "send on channel" ); } ).expect( "spawn" );
+
+	rx
+}
+
+
+// A sender that is dropped when the thread it's stored on exits, so the receiver disconnects.
+//
+#[ cfg(any( feature = "threadpool", feature = "juliex" )) ]
+//
+thread_local!
+{
+	static EXIT: StdRefCell< Option< mpsc::Sender<()> > > = const { StdRefCell::new( None ) };
+}
+
+
+// Store a sender on a worker thread of the pool, drop the pool and wait for the worker to exit.
+//
+#[ cfg(any( feature = "threadpool", feature = "juliex" )) ]
+//
+fn worker_exits<F>( spawn_handle: F ) -> bool
+
+	where F: FnOnce( Box< dyn FnOnce() + Send > ) -> rt::JoinHandle<()>
+
+{
+	let (tx, rx) = mpsc::channel();
+
+	let handle = spawn_handle( Box::new( move || EXIT.with( |exit| *exit.borrow_mut() = Some( tx ) ) ) );
+
+	rt::block_on( handle ).expect( "join task" );
+
+	rx.recv_timeout( Duration::from_secs( 10 ) ) == Err( mpsc::RecvTimeoutError::Disconnected )
+}
+
+
+#[ cfg(any( feature = "localpool", feature = "tokio_ct" )) ]
+//
+fn lib_spawn_local( exec: &impl LocalSpawn, number: Rc<RefCell<u8>> )
+{
+	exec.spawn_local( async move { *number.borrow_mut() = 3; } ).expect( "spawn_local" );
+}
+
+
+
+#[ cfg( feature = "localpool" ) ]
+//
+#[test]
+//
+fn localpool_as_spawn()
+{
+	let pool   = rt::LocalPool::new();
+	let number = Rc::new( RefCell::new( 0 ) );
+	let rx     = lib_spawn( &pool );
+
+	lib_spawn_local( &pool, number.clone() );
+
+	assert_eq!( 5, pool.run_until( rx ).expect( "wait on channel" ) );
+	assert_eq!( 3, *number.borrow() );
+}
+
+
+
+#[ cfg( feature = "localpool" ) ]
+//
+#[test]
+//
+fn localpool_init_with()
+{
+	let pool = rt::LocalPool::new();
+
+	rt::init_with( pool.clone() ).expect( "no double executor init" );
+
+	assert_eq!( Some( rt::Config::LocalPool ), rt::current_rt() );
+
+	let mut rx        = lib_spawn( &pool );
+	let (tx, mut rx2) = oneshot::channel();
+
+	rt::spawn( async move { tx.send( 2u8 ).expect( "send on channel" ); } ).expect( "spawn" );
+
+	// Both futures end up on the same pool.
+	//
+	pool.run();
+
+	assert_eq!( Ok( Some(5) ), rx .try_recv() );
+	assert_eq!( Ok( Some(2) ), rx2.try_recv() );
+}
+
+
+
+#[ cfg( feature = "localpool" ) ]
+//
+#[test]
+//
+fn init_with_double()
+{
+	rt::init( rt::Config::LocalPool ).expect( "no double executor init" );
+
+	let result = rt::init_with( rt::LocalPool::new() );
+
+	assert_eq!( &rt::ErrorKind::DoubleExecutorInit, result.unwrap_err().kind() );
+}
+
+
+
+#[ cfg( feature = "threadpool" ) ]
+//
+#[test]
+//
+fn threadpool_as_spawn()
+{
+	let pool = rt::ThreadPool::new( rt::ThreadPoolConfig::default().workers( std::num::NonZeroUsize::MIN ) );
+	let rx   = lib_spawn( &pool );
+
+	assert_eq!( 5, rt::block_on( rx ).expect( "wait on channel" ) );
+}
+
+
+
+// The worker threads of an owned pool have it installed as their executor, and it's not the pool
+// used by rt::init, even if the configuration is the same.
+//
+#[ cfg( feature = "threadpool" ) ]
+//
+#[test]
+//
+fn threadpool_workers_use_owned()
+{
+	let config = rt::ThreadPoolConfig::default().workers( std::num::NonZeroUsize::MIN ).name_prefix( "owned_pool" );
+	let pool   = rt::ThreadPool::new( config.clone() );
+
+	rt::init( rt::Config::ThreadPool( config ) ).expect( "no double executor init" );
+
+	let handle = pool.spawn_handle( async move
+	{
+		let inner = rt::spawn_handle( async { std::thread::current().id() } ).expect( "spawn_handle" );
+
+		( std::thread::current().id(), inner.await.expect( "join task" ) )
+
+	}).expect( "spawn_handle" );
+
+	let (outer, inner) = rt::block_on( handle ).expect( "join task" );
+
+	// A pool with a single worker, so if the nested task ran on the same pool, it ran on the same thread.
+	//
+	assert_eq!( outer, inner );
+
+	// The shared pool is a different pool with the same configuration.
+	//
+	let shared = rt::spawn_handle( async { std::thread::current().id() } ).expect( "spawn_handle" );
+
+	assert_ne!( outer, rt::block_on( shared ).expect( "join task" ) );
+}
+
+
+
+// The worker threads have a clone of the pool installed as their executor, but that must not keep it alive.
+//
+#[ cfg( feature = "threadpool" ) ]
+//
+#[test]
+//
+fn threadpool_drop_stops_workers()
+{
+	let pool = rt::ThreadPool::new( rt::ThreadPoolConfig::default().workers( std::num::NonZeroUsize::MIN ) );
+
+	assert!( worker_exits( move |f|
+	{
+		pool.spawn_handle( async move { f() } ).expect( "spawn_handle" )
+	}));
+}
+
+
+
+#[ cfg( feature = "juliex" ) ]
+//
+#[test]
+//
+fn juliex_as_spawn()
+{
+	let pool = rt::Juliex::new( Default::default() );
+	let rx   = lib_spawn( &pool );
+
+	assert_eq!( 5, rt::block_on( rx ).expect( "wait on channel" ) );
+
+	let handle = pool.spawn_handle( async { rt::current_rt() } ).expect( "spawn_handle" );
+
+	assert_eq!( Some( rt::Config::Juliex( Default::default() ) ), rt::block_on( handle ).expect( "join task" ) );
+}
+
+
+
+#[ cfg( feature = "juliex" ) ]
+//
+#[test]
+//
+fn juliex_drop_stops_workers()
+{
+	let pool = rt::Juliex::new( Default::default() );
+
+	assert!( worker_exits( move |f|
+	{
+		pool.spawn_handle( async move { f() } ).expect( "spawn_handle" )
+	}));
+}
+
+
+
+#[ cfg( feature = "async_std" ) ]
+//
+#[test]
+//
+fn async_std_as_spawn()
+{
+	let exec = rt::AsyncStd::new();
+	let rx   = lib_spawn( &exec );
+
+	assert_eq!( 5, rt::block_on( rx ).expect( "wait on channel" ) );
+}
+
+
+
+#[ cfg( feature = "tokio_ct" ) ]
+//
+#[test]
+//
+fn tokio_ct_as_spawn()
+{
+	let exec   = rt::TokioCt::new( Default::default() );
+	let number = Rc::new( RefCell::new( 0 ) );
+	let num2   = number.clone();
+	let inner  = exec.clone();
+	let rx     = lib_spawn( &exec );
+
+	// Spawn from within a running task.
+	//
+	exec.spawn_local( async move { lib_spawn_local( &inner, num2 ); } ).expect( "spawn_local" );
+
+	exec.run().expect( "run tokio_ct" );
+
+	assert_eq!( 3, *number.borrow() );
+	assert_eq!( 5, rt::block_on( rx ).expect( "wait on channel" ) );
+}
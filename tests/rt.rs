@@ -43,9 +43,9 @@ fn init_juliex()
 {
 	assert_eq!( None, rt::current_rt() );
 
-	rt::init( rt::Config::Juliex ).expect( "no double executor init" );
+	rt::init( rt::Config::Juliex( Default::default() ) ).expect( "no double executor init" );
 
-	assert_eq!( Some( rt::Config::Juliex ), rt::current_rt() );
+	assert_eq!( Some( rt::Config::Juliex( Default::default() ) ), rt::current_rt() );
 }
 
 
@@ -70,8 +70,8 @@ fn init_async_std()
 //
 fn double_init_pool()
 {
-	             rt::init( rt::Config::Juliex ).expect( "no double executor init" );
-	let result = rt::init( rt::Config::Juliex );
+	             rt::init( rt::Config::Juliex( Default::default() ) ).expect( "no double executor init" );
+	let result = rt::init( rt::Config::Juliex( Default::default() ) );
 
 	assert_eq!( &rt::ErrorKind::DoubleExecutorInit, result.unwrap_err().kind() );
 }
@@ -85,7 +85,7 @@ fn double_init_pool()
 fn double_init_different()
 {
 	             rt::init( rt::Config::LocalPool ).expect( "no double executor init" );
-	let result = rt::init( rt::Config::Juliex  );
+	let result = rt::init( rt::Config::Juliex( Default::default() ) );
 
 	assert_eq!( &rt::ErrorKind::DoubleExecutorInit, result.unwrap_err().kind() );
 }
@@ -98,7 +98,7 @@ fn double_init_different()
 //
 fn double_init_inverse()
 {
-	             rt::init( rt::Config::Juliex    ).expect( "no double executor init" );
+	             rt::init( rt::Config::Juliex( Default::default() ) ).expect( "no double executor init" );
 	let result = rt::init( rt::Config::LocalPool );
 
 	assert_eq!( &rt::ErrorKind::DoubleExecutorInit, result.unwrap_err().kind() );
@@ -0,0 +1,145 @@
+#![ cfg(not( target_arch = "wasm32" )) ]
+#![ cfg(any( feature = "localpool", feature = "threadpool", feature = "juliex" )) ]
+
+// Tested:
+//
+// ✔ shutdown waits for tasks in flight on threadpool
+// ✔ spawning after shutdown returns ExecutorShutdown, also through the Spawn trait
+// ✔ shutdown returns false when the timeout expires
+// ✔ shutdown with Duration::MAX waits for the tasks in flight instead of overflowing
+// ✔ shutdown waits for tasks in flight on juliex
+// ✔ shutdown on localpool returns WrongExecutor
+// ✔ shutdown without executor returns NoExecutorInitialized
+//
+use
+{
+	async_runtime as rt,
+
+	std :: { time::Duration } ,
+};
+
+#[ cfg(any( feature = "threadpool", feature = "juliex" )) ]
+//
+use std::{ thread, sync::{ Arc, atomic::{ AtomicBool, Ordering } } };
+
+
+
+// A task that takes a while to finish and sets a flag when it's done.
+//
+#[ cfg(any( feature = "threadpool", feature = "juliex" )) ]
+//
+async fn slow_task( done: Arc<AtomicBool> )
+{
+	thread::sleep( Duration::from_millis( 100 ) );
+	done.store( true, Ordering::SeqCst );
+}
+
+
+
+#[ cfg( feature = "threadpool" ) ]
+//
+#[test]
+//
+fn threadpool()
+{
+	// Use a config no other test uses, so we don't shut down their pool.
+	//
+	let config = rt::ThreadPoolConfig::default().name_prefix( "shutdown_test" );
+
+	rt::init( rt::Config::ThreadPool( config ) ).expect( "no double executor init" );
+
+	let done = Arc::new( AtomicBool::new( false ) );
+
+	rt::spawn( slow_task( done.clone() ) ).expect( "spawn" );
+
+	assert!( rt::shutdown( Duration::from_secs( 5 ) ).expect( "shutdown" ) );
+	assert!( done.load( Ordering::SeqCst ) );
+
+	assert_eq!( &rt::ErrorKind::ExecutorShutdown, rt::spawn( async {} ).unwrap_err().kind() );
+	assert_eq!( &rt::ErrorKind::ExecutorShutdown, rt::spawn_handle( async {} ).unwrap_err().kind() );
+}
+
+
+
+#[ cfg( feature = "threadpool" ) ]
+//
+#[test]
+//
+fn threadpool_timeout()
+{
+	use futures::task::SpawnExt;
+
+	let pool = rt::ThreadPool::new( Default::default() );
+
+	pool.spawn( futures::future::pending() ).expect( "spawn" );
+
+	assert!( !pool.shutdown( Duration::from_millis( 50 ) ) );
+	assert!( SpawnExt::spawn( &pool, async {} ).is_err() );
+}
+
+
+
+// A timeout too big to add to the current time means no deadline.
+//
+#[ cfg( feature = "threadpool" ) ]
+//
+#[test]
+//
+fn threadpool_wait_forever()
+{
+	let pool = rt::ThreadPool::new( Default::default() );
+	let done = Arc::new( AtomicBool::new( false ) );
+
+	pool.spawn( slow_task( done.clone() ) ).expect( "spawn" );
+
+	assert!( pool.shutdown( Duration::MAX ) );
+	assert!( done.load( Ordering::SeqCst ) );
+}
+
+
+
+#[ cfg( feature = "juliex" ) ]
+//
+#[test]
+//
+fn juliex()
+{
+	// Use our own pool, so we don't shut down the pool other tests use.
+	//
+	rt::init_with( rt::Juliex::new( Default::default() ) ).expect( "no double executor init" );
+
+	let done = Arc::new( AtomicBool::new( false ) );
+
+	rt::spawn( slow_task( done.clone() ) ).expect( "spawn" );
+
+	assert!( rt::shutdown( Duration::from_secs( 5 ) ).expect( "shutdown" ) );
+	assert!( done.load( Ordering::SeqCst ) );
+
+	assert_eq!( &rt::ErrorKind::ExecutorShutdown, rt::spawn( async {} ).unwrap_err().kind() );
+}
+
+
+
+#[ cfg( feature = "localpool" ) ]
+//
+#[test]
+//
+fn wrong_executor()
+{
+	rt::init( rt::Config::LocalPool ).expect( "no double executor init" );
+
+	let result = rt::shutdown( Duration::from_secs( 1 ) );
+
+	assert_eq!( &rt::ErrorKind::WrongExecutor, result.unwrap_err().kind() );
+}
+
+
+
+#[test]
+//
+fn no_executor()
+{
+	let result = rt::shutdown( Duration::from_secs( 1 ) );
+
+	assert_eq!( &rt::ErrorKind::NoExecutorInitialized, result.unwrap_err().kind() );
+}
@@ -0,0 +1,250 @@
+#![ cfg(not( target_arch = "wasm32" )) ]
+#![ cfg(     feature     = "smol"      ) ]
+
+// Tested:
+//
+// ✔ Smol: spawn runs on a worker thread
+// ✔ Smol: worker thread spawns on same threadpool
+// ✔ Smol: spawn_local and spawn_handle_local return the right error
+// ✔ Smol: worker threads use the configured name prefix and are initialized with the full configuration
+// ✔ Smol: tasks can use the async-io reactor
+// ✔ Smol: shutdown waits for tasks in flight
+// ✔ Smol: dropping the last clone of an owned pool stops it's worker threads
+// ✔ SmolLocal: spawn !Send futures and run them on the current thread
+// ✔ SmolLocal: spawn from within a task
+// ✔ SmolLocal: run_until returns the output of the future
+// ✔ SmolLocal: run_until_stalled returns with pending tasks
+// ✔ SmolLocal: run functions error with the wrong executor
+//
+use
+{
+	async_runtime as rt,
+
+	std     :: { rc::Rc, cell::RefCell, thread, time::Duration, sync::mpsc, num::NonZeroUsize } ,
+	futures :: { channel::oneshot, future::pending                                            } ,
+};
+
+
+
+#[test]
+//
+fn basic_spawn()
+{
+	rt::init( rt::Config::Smol( Default::default() ) ).expect( "no double executor init" );
+
+	let (tx, rx) = oneshot::channel();
+
+	rt::spawn( async move { tx.send( thread::current().id() ).expect( "send on channel" ); } ).expect( "Spawn task" );
+
+	rt::block_on( async move
+	{
+		assert_ne!( thread::current().id(), rx.await.expect( "wait on channel" ) );
+	});
+}
+
+
+
+#[test]
+//
+fn within()
+{
+	rt::init( rt::Config::Smol( Default::default() ) ).expect( "no double executor init" );
+
+	let handle = rt::spawn_handle( async
+	{
+		rt::spawn_handle( async { rt::current_rt() } ).expect( "spawn_handle" ).await.expect( "join task" )
+
+	}).expect( "spawn_handle" );
+
+	assert_eq!( Some( rt::Config::Smol( Default::default() ) ), rt::block_on( handle ).expect( "join task" ) );
+}
+
+
+
+#[test]
+//
+fn spawn_local_on_pool()
+{
+	rt::init( rt::Config::Smol( Default::default() ) ).expect( "no double executor init" );
+
+	let res    = rt::spawn_local       ( async {}   );
+	let handle = rt::spawn_handle_local( async { 1 } );
+
+	assert_eq!( &rt::ErrorKind::SpawnLocalOnThreadPool, res   .unwrap_err().kind() );
+	assert_eq!( &rt::ErrorKind::SpawnLocalOnThreadPool, handle.unwrap_err().kind() );
+}
+
+
+
+// Worker threads should be named after the configured prefix and be initialized with the same config.
+//
+#[test]
+//
+fn config_name_prefix()
+{
+	let config = rt::SmolConfig::default()
+
+		.workers    ( NonZeroUsize::new( 2 ).expect( "non zero" ) )
+		.name_prefix( "config_test_smol"                          )
+		.stack_size ( 1024 * 1024                                 )
+	;
+
+	rt::init( rt::Config::Smol( config.clone() ) ).expect( "no double executor init" );
+
+	let handle = rt::spawn_handle( async
+	{
+		( thread::current().name().map( String::from ), rt::current_rt() )
+
+	}).expect( "spawn_handle" );
+
+	let (name, worker_config) = rt::block_on( handle ).expect( "join task" );
+
+	assert!( name.expect( "worker has a name" ).starts_with( "config_test_smol" ) );
+	assert_eq!( Some( rt::Config::Smol( config ) ), worker_config );
+}
+
+
+
+#[test]
+//
+fn reactor()
+{
+	rt::init( rt::Config::Smol( Default::default() ) ).expect( "no double executor init" );
+
+	let handle = rt::spawn_handle( async
+	{
+		async_io::Timer::after( Duration::from_millis( 10 ) ).await;
+		5
+
+	}).expect( "spawn_handle" );
+
+	assert_eq!( 5, rt::block_on( handle ).expect( "join task" ) );
+}
+
+
+
+#[test]
+//
+fn shutdown()
+{
+	// Use a separate pool, so we don't shut down the one shared by the other tests.
+	//
+	rt::init_with( rt::Smol::new( Default::default() ) ).expect( "no double executor init" );
+
+	let (tx, rx) = std::sync::mpsc::channel();
+
+	rt::spawn( async move
+	{
+		thread::sleep( Duration::from_millis( 50 ) );
+		tx.send(()).expect( "send" );
+
+	}).expect( "spawn" );
+
+	assert!( rt::shutdown( Duration::from_secs( 5 ) ).expect( "shutdown" ) );
+	assert!( rx.try_recv().is_ok() );
+
+	assert_eq!( &rt::ErrorKind::ExecutorShutdown, rt::spawn( async {} ).unwrap_err().kind() );
+}
+
+
+
+// A sender that is dropped when the thread it's stored on exits, so the receiver disconnects.
+//
+thread_local!
+{
+	static EXIT: RefCell< Option< mpsc::Sender<()> > > = const { RefCell::new( None ) };
+}
+
+
+// The worker threads have a clone of the pool installed as their executor, but that must not keep it alive.
+//
+#[test]
+//
+fn drop_stops_workers()
+{
+	let pool     = rt::Smol::new( rt::SmolConfig::default().workers( NonZeroUsize::MIN ) );
+	let (tx, rx) = mpsc::channel();
+
+	let handle = pool.spawn_handle( async move { EXIT.with( |exit| *exit.borrow_mut() = Some( tx ) ); } ).expect( "spawn_handle" );
+
+	rt::block_on( handle ).expect( "join task" );
+
+	drop( pool );
+
+	assert_eq!( Err( mpsc::RecvTimeoutError::Disconnected ), rx.recv_timeout( Duration::from_secs( 10 ) ) );
+}
+
+
+
+#[test]
+//
+fn local()
+{
+	rt::init( rt::Config::SmolLocal ).expect( "no double executor init" );
+
+	let number = Rc::new( RefCell::new( 0 ) );
+	let num2   = number.clone();
+
+	rt::spawn_local( async move
+	{
+		*num2.borrow_mut() = 2;
+
+		let num3 = num2.clone();
+
+		rt::spawn_local( async move { *num3.borrow_mut() += 1; } ).expect( "spawn from within" );
+
+	}).expect( "spawn_local" );
+
+	rt::smol_local::run().expect( "run smol local" );
+
+	assert_eq!( 3, *number.borrow() );
+}
+
+
+
+#[test]
+//
+fn local_run_until()
+{
+	rt::init( rt::Config::SmolLocal ).expect( "no double executor init" );
+
+	let handle = rt::spawn_handle_local( async
+	{
+		async_io::Timer::after( Duration::from_millis( 10 ) ).await;
+		thread::current().id()
+
+	}).expect( "spawn_handle_local" );
+
+	let id = rt::smol_local::run_until( handle ).expect( "run smol local" ).expect( "join task" );
+
+	assert_eq!( thread::current().id(), id );
+}
+
+
+
+#[test]
+//
+fn local_run_until_stalled()
+{
+	rt::init( rt::Config::SmolLocal ).expect( "no double executor init" );
+
+	let handle = rt::spawn_handle( pending::<()>() ).expect( "spawn_handle" );
+
+	rt::spawn( async {} ).expect( "spawn" );
+	rt::smol_local::run_until_stalled().expect( "run smol local" );
+
+	assert!( !handle.is_finished() );
+}
+
+
+
+#[test]
+//
+fn local_wrong_executor()
+{
+	assert_eq!( &rt::ErrorKind::NoExecutorInitialized, rt::smol_local::run().unwrap_err().kind() );
+
+	rt::init( rt::Config::Smol( Default::default() ) ).expect( "no double executor init" );
+
+	assert_eq!( &rt::ErrorKind::WrongExecutor, rt::smol_local::run_until_stalled().unwrap_err().kind() );
+}
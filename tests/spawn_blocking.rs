@@ -0,0 +1,117 @@
+#![ cfg(not( target_arch = "wasm32" )) ]
+
+// Tested:
+//
+// ✔ returns the output of the closure without an executor
+// ✔ runs on another thread than the caller
+// ✔ a panic is returned as JoinError
+// ✔ several closures run in parallel
+// ✔ can be awaited from a task on localpool
+// ✔ can be awaited from a task on tokio_ct
+//
+use
+{
+	async_runtime as rt,
+
+	std :: { thread, time::{ Duration, Instant } } ,
+};
+
+
+
+#[test]
+//
+fn no_executor()
+{
+	let handle = rt::spawn_blocking( || 3 );
+
+	assert_eq!( 3, rt::block_on( handle ).expect( "join task" ) );
+}
+
+
+
+#[test]
+//
+fn other_thread()
+{
+	let handle = rt::spawn_blocking( || thread::current().id() );
+
+	assert_ne!( thread::current().id(), rt::block_on( handle ).expect( "join task" ) );
+}
+
+
+
+#[test]
+//
+fn panic()
+{
+	let handle = rt::spawn_blocking( || panic!( "boom" ) );
+
+	let error: rt::JoinError = rt::block_on( handle ).unwrap_err();
+
+	assert!( error.is_panic() );
+	assert_eq!( "boom", *error.into_panic().expect( "panic payload" ).downcast::<&str>().expect( "&str payload" ) );
+}
+
+
+
+#[test]
+//
+fn parallel()
+{
+	let start = Instant::now();
+
+	let handles: Vec<_> = ( 0..8 ).map( |_|
+
+		rt::spawn_blocking( || thread::sleep( Duration::from_millis( 200 ) ) )
+
+	).collect();
+
+	for handle in handles
+	{
+		rt::block_on( handle ).expect( "join task" );
+	}
+
+	assert!( start.elapsed() < Duration::from_millis( 1600 ) );
+}
+
+
+
+#[ cfg( feature = "localpool" ) ]
+//
+#[test]
+//
+fn localpool()
+{
+	rt::init( rt::Config::LocalPool ).expect( "no double executor init" );
+
+	let handle = rt::spawn_handle( async
+	{
+		rt::spawn_blocking( || "hello".to_string() ).await.expect( "join blocking task" )
+
+	}).expect( "spawn_handle" );
+
+	let out = rt::localpool::run_until( handle ).expect( "run localpool" ).expect( "join task" );
+
+	assert_eq!( "hello", out );
+}
+
+
+
+#[ cfg( feature = "tokio_ct" ) ]
+//
+#[test]
+//
+fn tokio_ct()
+{
+	rt::init( rt::Config::TokioCt( Default::default() ) ).expect( "no double executor init" );
+
+	let handle = rt::spawn_handle( async
+	{
+		rt::spawn_blocking( || "hello".to_string() ).await.expect( "join blocking task" )
+
+	}).expect( "spawn_handle" );
+
+	rt::tokio_ct::run().expect( "run tokio_ct" );
+
+	assert_eq!( "hello", rt::block_on( handle ).expect( "join task" ) );
+}
@@ -0,0 +1,160 @@
+#![ cfg(not( target_arch = "wasm32"   )) ]
+#![ cfg(     feature     = "localpool" ) ]
+
+// Tested:
+//
+// ✔ returns the output of the future without an executor, on another thread
+// ✔ the future can be !Send and can spawn_local on it's thread
+// ✔ can be used from a task on a threadpool
+// ✔ a panic is returned as JoinError
+// ✔ dropping the handle cancels the task
+// ✔ the handle knows where the task was spawned
+// ✔ many tasks all complete
+//
+use
+{
+	async_runtime as rt,
+
+	std     :: { rc::Rc, cell::RefCell, thread, time::Duration                  } ,
+	std     :: { sync::{ Arc, atomic::{ AtomicBool, Ordering } }                 } ,
+	futures :: { channel::oneshot, future::join_all                              } ,
+};
+
+
+
+#[test]
+//
+fn no_executor()
+{
+	let handle = rt::spawn_pinned( || async { thread::current().id() } ).expect( "spawn pinned" );
+
+	assert_ne!( thread::current().id(), rt::block_on( handle ).expect( "join task" ) );
+}
+
+
+
+#[test]
+//
+fn not_send()
+{
+	let handle = rt::spawn_pinned( ||
+	{
+		let number = Rc::new( RefCell::new( 1 ) );
+
+		async move
+		{
+			let num2  = number.clone();
+			let local = rt::spawn_handle_local( async move { *num2.borrow_mut() += 1; } ).expect( "spawn_local" );
+
+			local.await.expect( "join local task" );
+
+			let out = *number.borrow();
+			out
+		}
+
+	}).expect( "spawn pinned" );
+
+	assert_eq!( 2, rt::block_on( handle ).expect( "join task" ) );
+}
+
+
+
+#[ cfg( feature = "threadpool" ) ]
+//
+#[test]
+//
+fn from_threadpool()
+{
+	rt::init( rt::Config::ThreadPool( Default::default() ) ).expect( "no double executor init" );
+
+	assert_eq!( &rt::ErrorKind::SpawnLocalOnThreadPool, rt::spawn_local( async {} ).unwrap_err().kind() );
+
+	let handle = rt::spawn_handle( async
+	{
+		rt::spawn_pinned( || { let number = Rc::new( 3 ); async move { *number } } )
+
+			.expect( "spawn pinned" )
+			.await
+			.expect( "join pinned task" )
+
+	}).expect( "spawn on threadpool" );
+
+	assert_eq!( 3, rt::block_on( handle ).expect( "join task" ) );
+}
+
+
+
+#[test]
+//
+fn panic()
+{
+	let handle = rt::spawn_pinned( || async { panic!( "boom" ) } ).expect( "spawn pinned" );
+
+	let error: rt::JoinError = rt::block_on( handle ).unwrap_err();
+
+	assert!( error.is_panic() );
+}
+
+
+
+#[test]
+//
+fn cancel()
+{
+	struct Dropped( Arc<AtomicBool> );
+
+	impl Drop for Dropped
+	{
+		fn drop( &mut self ) { self.0.store( true, Ordering::SeqCst ); }
+	}
+
+	let dropped  = Arc::new( AtomicBool::new( false ) );
+	let guard    = Dropped( dropped.clone() );
+	let (tx, rx) = oneshot::channel::<()>();
+
+	// The task might get cancelled before the closure runs, so the guard lives in the closure.
+	//
+	let handle = rt::spawn_pinned( move || async move { let _guard = guard; let _ = rx.await; } ).expect( "spawn pinned" );
+
+	drop( handle );
+
+	for _ in 0..100
+	{
+		if dropped.load( Ordering::SeqCst ) { break; }
+
+		thread::sleep( Duration::from_millis( 10 ) );
+	}
+
+	assert!( dropped.load( Ordering::SeqCst ) );
+	drop( tx );
+}
+
+
+
+#[test]
+//
+fn location()
+{
+	let handle = rt::spawn_pinned( || async {} ).expect( "spawn pinned" );
+
+	assert_eq!( file!(), handle.task().expect( "task info" ).location().file() );
+
+	rt::block_on( handle ).expect( "join task" );
+}
+
+
+
+#[test]
+//
+fn many()
+{
+	let handles: Vec<_> = (0..50).map( |i|
+	{
+		rt::spawn_pinned( move || { let number = Rc::new( i ); async move { *number * 2 } } ).expect( "spawn pinned" )
+
+	}).collect();
+
+	let outputs: Vec<usize> = rt::block_on( join_all( handles ) ).into_iter().map( |o| o.expect( "join task" ) ).collect();
+
+	assert_eq!( (0..50).map( |i| i * 2 ).collect::<Vec<_>>(), outputs );
+}
@@ -0,0 +1,227 @@
+#![ cfg(not( target_arch = "wasm32" )) ]
+#![ cfg(     feature     = "static_exec" ) ]
+
+// Tested:
+//
+// ✔ spawn futures and run them to completion
+// ✔ spawn from within a task while the executor runs
+// ✔ spawn_handle returns the right value
+// ✔ a wake up from another thread, like an interrupt would, unparks the executor
+// ✔ spawning from another thread while the executor runs
+// ✔ run_until_stalled returns while tasks wait, is_woken tells when there is work again
+// ✔ spawn_local is refused
+// ✔ running the executor from within a task errors
+// ✔ an owned handle can be passed as `T: Spawn`
+//
+// This test also runs against the no_std build:
+//
+//    cargo test --no-default-features --features static_exec --test static_exec
+//
+use
+{
+	async_runtime as rt,
+
+	std     :: { sync::{ Arc, Mutex, MutexGuard, atomic::{ AtomicUsize, Ordering } }, thread, time::Duration } ,
+	futures :: { channel::oneshot, task::SpawnExt, FutureExt                                                } ,
+};
+
+
+// There is only one static executor, so the tests can not run it at the same time.
+//
+static SERIAL: Mutex<()> = Mutex::new(());
+
+
+fn serial() -> MutexGuard< 'static, () >
+{
+	let guard = SERIAL.lock().unwrap_or_else( |poisoned| poisoned.into_inner() );
+
+	rt::init_allow_same( rt::Config::Static ).expect( "no double executor init" );
+
+	guard
+}
+
+
+
+#[test]
+//
+fn spawn()
+{
+	let _serial = serial();
+
+	let number = Arc::new( AtomicUsize::new( 0 ) );
+
+	for _ in 0..3
+	{
+		let num2 = number.clone();
+
+		rt::spawn( async move { num2.fetch_add( 1, Ordering::SeqCst ); } ).expect( "spawn" );
+	}
+
+	rt::static_exec::run().expect( "run static executor" );
+
+	assert_eq!( 3, number.load( Ordering::SeqCst ) );
+}
+
+
+
+#[test]
+//
+fn within()
+{
+	let _serial = serial();
+
+	let number = Arc::new( AtomicUsize::new( 0 ) );
+	let num2   = number.clone();
+
+	rt::spawn( async move
+	{
+		num2.fetch_add( 2, Ordering::SeqCst );
+
+		let num3 = num2.clone();
+
+		rt::spawn( async move { num3.fetch_add( 1, Ordering::SeqCst ); } ).expect( "spawn from within" );
+
+	}).expect( "spawn" );
+
+	rt::static_exec::run().expect( "run static executor" );
+
+	assert_eq!( 3, number.load( Ordering::SeqCst ) );
+}
+
+
+
+#[test]
+//
+fn spawn_handle()
+{
+	let _serial = serial();
+
+	let handle = rt::spawn_handle( async { 5 } ).expect( "spawn_handle" );
+
+	rt::static_exec::run().expect( "run static executor" );
+
+	assert_eq!( Some( 5 ), handle.now_or_never().map( |out| out.expect( "join task" ) ) );
+}
+
+
+
+#[test]
+//
+fn wake_from_thread()
+{
+	let _serial = serial();
+
+	let (tx, rx) = oneshot::channel();
+
+	let handle = rt::spawn_handle( async { rx.await.expect( "receive" ) } ).expect( "spawn_handle" );
+
+	let interrupt = thread::spawn( move ||
+	{
+		thread::sleep( Duration::from_millis( 20 ) );
+		tx.send( 7 ).expect( "send" );
+	});
+
+	rt::static_exec::run().expect( "run static executor" );
+	interrupt.join().expect( "join thread" );
+
+	assert_eq!( Some( 7 ), handle.now_or_never().map( |out| out.expect( "join task" ) ) );
+}
+
+
+
+#[test]
+//
+fn spawn_from_thread()
+{
+	let _serial = serial();
+
+	let (tx, rx) = oneshot::channel::<()>();
+	let number   = Arc::new( AtomicUsize::new( 0 ) );
+	let num2     = number.clone();
+
+	// Keeps the executor running until the other thread is done.
+	//
+	rt::spawn( async { rx.await.expect( "receive" ); } ).expect( "spawn" );
+
+	let interrupt = thread::spawn( move ||
+	{
+		thread::sleep( Duration::from_millis( 20 ) );
+
+		rt::Static::new().spawn( async move { num2.fetch_add( 1, Ordering::SeqCst ); } ).expect( "spawn from thread" );
+
+		tx.send(()).expect( "send" );
+	});
+
+	rt::static_exec::run().expect( "run static executor" );
+	interrupt.join().expect( "join thread" );
+
+	assert_eq!( 1, number.load( Ordering::SeqCst ) );
+}
+
+
+
+#[test]
+//
+fn run_until_stalled()
+{
+	let _serial = serial();
+
+	let (tx, rx) = oneshot::channel();
+
+	let handle = rt::spawn_handle( async { rx.await.expect( "receive" ) } ).expect( "spawn_handle" );
+
+	rt::static_exec::run_until_stalled().expect( "run static executor" );
+	assert!( !rt::static_exec::is_woken() );
+
+	tx.send( 3 ).expect( "send" );
+	assert!( rt::static_exec::is_woken() );
+
+	rt::static_exec::run_until_stalled().expect( "run static executor" );
+
+	assert_eq!( Some( 3 ), handle.now_or_never().map( |out| out.expect( "join task" ) ) );
+}
+
+
+
+#[test]
+//
+fn spawn_local()
+{
+	let _serial = serial();
+
+	assert_eq!( &rt::ErrorKind::SpawnLocalOnThreadPool, rt::spawn_local( async {} ).unwrap_err().kind() );
+}
+
+
+
+#[test]
+//
+fn run_within()
+{
+	let _serial = serial();
+
+	let handle = rt::spawn_handle( async { rt::static_exec::run_until_stalled() } ).expect( "spawn_handle" );
+
+	rt::static_exec::run().expect( "run static executor" );
+
+	let err = handle.now_or_never().expect( "finished" ).expect( "join task" ).unwrap_err();
+
+	assert_eq!( &rt::ErrorKind::Run, err.kind() );
+}
+
+
+
+#[test]
+//
+fn owned()
+{
+	let _serial = serial();
+
+	let exec = rt::Static::new();
+
+	SpawnExt::spawn( &exec, async { rt::spawn( async {} ).expect( "spawn from within" ); } ).expect( "spawn" );
+
+	exec.run().expect( "run static executor" );
+
+	assert_eq!( Some( rt::Config::Static ), rt::current_rt() );
+}
@@ -0,0 +1,220 @@
+#![ cfg(not( target_arch = "wasm32" )) ]
+#![ cfg( feature = "localpool" ) ]
+
+// Tested:
+//
+// ✔ a task spawned with a builder knows it's name
+// ✔ an anonymous task has no name but knows where it was spawned
+// ✔ current returns None outside of a task
+// ✔ each task gets a different id
+// ✔ the JoinError of a panicked task mentions the name and the spawn location
+// ✔ so does the rt::Error it converts into
+// ✔ the JoinHandle knows it's task
+// ✔ nursery children are located at the call to nurse
+// ✔ the name is available on threadpool worker threads
+// ✔ a panic hook can tell which task panicked on juliex
+//
+use
+{
+	async_runtime as rt,
+
+	rt :: { task::Task } ,
+};
+
+
+
+// The task information from within a task.
+//
+async fn current() -> Task
+{
+	rt::task::current().expect( "in a task" )
+}
+
+
+
+#[test]
+//
+fn named()
+{
+	rt::init( rt::Config::LocalPool ).expect( "no double executor init" );
+
+	let handle = rt::Builder::new().name( "flush-loop" ).spawn_handle( current() ).expect( "spawn_handle" );
+	let task   = rt::localpool::run_until( handle ).expect( "run localpool" ).expect( "join task" );
+
+	assert_eq!( Some( "flush-loop" ), task.name() );
+	assert_eq!( file!(), task.location().file() );
+}
+
+
+
+#[test]
+//
+fn anonymous()
+{
+	rt::init( rt::Config::LocalPool ).expect( "no double executor init" );
+
+	let handle = rt::spawn_handle( current() ).expect( "spawn_handle" );
+
+	let line = line!() - 2;
+	let task = rt::localpool::run_until( handle ).expect( "run localpool" ).expect( "join task" );
+
+	assert_eq!( None   , task.name()            );
+	assert_eq!( file!(), task.location().file() );
+	assert_eq!( line   , task.location().line() );
+}
+
+
+
+#[test]
+//
+fn outside_task()
+{
+	assert!( rt::task::current().is_none() );
+
+	rt::init( rt::Config::LocalPool ).expect( "no double executor init" );
+
+	rt::spawn( async { assert!( rt::task::current().is_some() ); } ).expect( "spawn" );
+	rt::localpool::run().expect( "run localpool" );
+
+	assert!( rt::task::current().is_none() );
+}
+
+
+
+#[test]
+//
+fn unique_id()
+{
+	rt::init( rt::Config::LocalPool ).expect( "no double executor init" );
+
+	let builder = rt::Builder::new().name( "worker" );
+
+	let a = builder.spawn_handle_local( current() ).expect( "spawn_handle_local" );
+	let b = builder.spawn_handle_local( current() ).expect( "spawn_handle_local" );
+
+	let a = rt::localpool::run_until( a ).expect( "run localpool" ).expect( "join task" );
+	let b = rt::localpool::run_until( b ).expect( "run localpool" ).expect( "join task" );
+
+	assert_eq!( a.name(), b.name() );
+	assert_ne!( a.id()  , b.id()   );
+}
+
+
+
+#[test]
+//
+fn join_error()
+{
+	rt::init( rt::Config::LocalPool ).expect( "no double executor init" );
+
+	let handle = rt::Builder::new().name( "flush-loop" ).spawn_handle( async { panic!( "boom" ) } ).expect( "spawn_handle" );
+	let task   = handle.task().expect( "task" ).clone();
+
+	let err: rt::JoinError = rt::localpool::run_until( handle ).expect( "run localpool" ).unwrap_err();
+	let msg = err.to_string();
+
+	assert_eq!( task.id(), err.task().expect( "task" ).id() );
+
+	assert!( msg.contains( "boom"            ) );
+	assert!( msg.contains( "\"flush-loop\""  ) );
+	assert!( msg.contains( &task.location().to_string() ) );
+}
+
+
+
+#[test]
+//
+fn error_task()
+{
+	rt::init( rt::Config::LocalPool ).expect( "no double executor init" );
+
+	let handle = rt::Builder::new().name( "flush-loop" ).spawn_handle( async { panic!( "boom" ) } ).expect( "spawn_handle" );
+	let task   = handle.task().expect( "task" ).clone();
+
+	let err: rt::Error = rt::localpool::run_until( handle ).expect( "run localpool" ).unwrap_err().into();
+	let msg = err.to_string();
+
+	assert_eq!( &rt::ErrorKind::Join, err.kind() );
+	assert_eq!( task.id(), err.task().expect( "task" ).id() );
+
+	assert!( msg.contains( "\"flush-loop\"" ) );
+	assert!( msg.contains( &task.location().to_string() ) );
+}
+
+
+
+#[test]
+//
+fn nursery()
+{
+	rt::init( rt::Config::LocalPool ).expect( "no double executor init" );
+
+	let mut nursery = rt::Nursery::<Task, rt::Error>::new();
+
+	nursery.nurse( async { Ok( current().await ) } ).expect( "nurse" );
+
+	let line  = line!() - 2;
+	let tasks = rt::localpool::run_until( nursery ).expect( "run localpool" ).expect( "join children" );
+
+	assert_eq!( line, tasks[0].location().line() );
+}
+
+
+
+#[ cfg( feature = "threadpool" ) ]
+//
+#[test]
+//
+fn threadpool()
+{
+	rt::init( rt::Config::ThreadPool( Default::default() ) ).expect( "no double executor init" );
+
+	let handle = rt::Builder::new().name( "pooled" ).spawn_handle( current() ).expect( "spawn_handle" );
+	let task   = rt::block_on( handle ).expect( "join task" );
+
+	assert_eq!( Some( "pooled" ), task.name() );
+}
+
+
+
+// Juliex doesn't report panics of tasks spawned without a handle, but a panic hook can find out which
+// task it was.
+//
+#[ cfg( feature = "juliex" ) ]
+//
+#[test]
+//
+fn panic_hook()
+{
+	static PANICKED: std::sync::Mutex< Vec<String> > = std::sync::Mutex::new( Vec::new() );
+
+	let default = std::panic::take_hook();
+
+	std::panic::set_hook( Box::new( move |info|
+	{
+		if let Some( task ) = rt::task::current()
+		{
+			PANICKED.lock().expect( "lock" ).push( task.to_string() );
+		}
+
+		default( info );
+	}));
+
+	rt::init( rt::Config::Juliex( Default::default() ) ).expect( "no double executor init" );
+
+	let (tx, rx) = futures::channel::oneshot::channel::<()>();
+
+	rt::Builder::new().name( "doomed" ).spawn( async move
+	{
+		let _tx = tx;
+		panic!( "boom" );
+
+	}).expect( "spawn" );
+
+	// The sender is dropped when the task unwinds.
+	//
+	assert!( rt::block_on( rx ).is_err() );
+
+	assert!( PANICKED.lock().expect( "lock" ).iter().any( |t| t.contains( "\"doomed\"" ) ) );
+}
+
@@ -0,0 +1,255 @@
+#![ cfg(not( target_arch = "wasm32" )) ]
+#![ cfg( feature = "localpool" ) ]
+
+// Tested:
+//
+// ✔ the value is visible inside the scope
+// ✔ try_with and get fail outside of the scope
+// ✔ nested scopes shadow the value and restore it
+// ✔ tasks interleaving on the same thread each see their own value
+// ✔ spawned tasks don't inherit by default
+// ✔ inherit_locals gives the task a clone of all the values
+// ✔ the same on threadpool, juliex, async_std and tokio_ct
+// ✔ the macro works where `std` is shadowed
+//
+use
+{
+	async_runtime as rt,
+
+	futures :: { channel::oneshot, future::join } ,
+};
+
+
+rt::task_local!
+{
+	static TENANT  : u32;
+	static DEADLINE: &'static str;
+}
+
+
+// The macro must not rely on the name `std` at the call site.
+//
+mod shadowed
+{
+	#[ allow( dead_code ) ] mod std {}
+
+	async_runtime::task_local!
+	{
+		pub static SHADOWED: u8;
+	}
+}
+
+
+
+#[test]
+//
+fn scope()
+{
+	rt::init( rt::Config::LocalPool ).expect( "no double executor init" );
+
+	let handle = rt::spawn_handle( TENANT.scope( 7, async
+	{
+		TENANT.with( |t| *t ) + TENANT.get()
+
+	})).expect( "spawn_handle" );
+
+	assert_eq!( 14, rt::localpool::run_until( handle ).expect( "run localpool" ).expect( "join task" ) );
+}
+
+
+
+#[test]
+//
+fn outside_scope()
+{
+	assert_eq!( &rt::ErrorKind::TaskLocalNotSet, TENANT.try_with( |t| *t ).unwrap_err().kind() );
+
+	let fut = TENANT.scope( 7, async {} );
+
+	// The value is only set while the future is polled.
+	//
+	assert!( TENANT.try_with( |t| *t ).is_err() );
+
+	rt::block_on( fut );
+
+	assert!( TENANT.try_with( |t| *t ).is_err() );
+	assert!( std::panic::catch_unwind( || TENANT.get() ).is_err() );
+}
+
+
+
+#[test]
+//
+fn std_shadowed()
+{
+	use shadowed::SHADOWED;
+
+	assert_eq!( 3, rt::block_on( SHADOWED.scope( 3, async { SHADOWED.get() } ) ) );
+}
+
+
+
+#[test]
+//
+fn nested()
+{
+	let fut = TENANT.scope( 1, async
+	{
+		let inner = TENANT.scope( 2, async { TENANT.get() } ).await;
+
+		( inner, TENANT.get() )
+	});
+
+	assert_eq!( ( 2, 1 ), rt::block_on( fut ) );
+}
+
+
+
+#[test]
+//
+fn interleaved()
+{
+	rt::init( rt::Config::LocalPool ).expect( "no double executor init" );
+
+	let (tx_a, rx_a) = oneshot::channel::<()>();
+	let (tx_b, rx_b) = oneshot::channel::<()>();
+
+	// Each task waits for the other one, so they get polled in turns.
+	//
+	let a = TENANT.scope( 1, async move
+	{
+		let before = TENANT.get();
+		tx_b.send(()).expect( "send" );
+		rx_a.await.expect( "recv" );
+
+		( before, TENANT.get() )
+	});
+
+	let b = TENANT.scope( 2, async move
+	{
+		let before = TENANT.get();
+		tx_a.send(()).expect( "send" );
+		rx_b.await.expect( "recv" );
+
+		( before, TENANT.get() )
+	});
+
+	let a = rt::spawn_handle( a ).expect( "spawn_handle" );
+	let b = rt::spawn_handle( b ).expect( "spawn_handle" );
+
+	let (a, b) = rt::localpool::run_until( join( a, b ) ).expect( "run localpool" );
+
+	assert_eq!( ( 1, 1 ), a.expect( "join task" ) );
+	assert_eq!( ( 2, 2 ), b.expect( "join task" ) );
+}
+
+
+
+// Spawn a child from within the scope, and return what it sees.
+//
+async fn child( builder: rt::Builder ) -> Option<( u32, &'static str )>
+{
+	let fut = TENANT.scope( 3, DEADLINE.scope( "soon", async move
+	{
+		let handle = builder.spawn_handle( async
+		{
+			Some(( TENANT.try_with( |t| *t ).ok()?, DEADLINE.try_with( |d| *d ).ok()? ))
+
+		}).expect( "spawn_handle" );
+
+		handle.await.expect( "join child" )
+	}));
+
+	fut.await
+}
+
+
+
+#[test]
+//
+fn not_inherited()
+{
+	rt::init( rt::Config::LocalPool ).expect( "no double executor init" );
+
+	let handle = rt::spawn_handle( child( rt::Builder::new() ) ).expect( "spawn_handle" );
+
+	assert_eq!( None, rt::localpool::run_until( handle ).expect( "run localpool" ).expect( "join task" ) );
+}
+
+
+
+#[test]
+//
+fn inherited()
+{
+	rt::init( rt::Config::LocalPool ).expect( "no double executor init" );
+
+	let handle = rt::spawn_handle( child( rt::Builder::new().inherit_locals( true ) ) ).expect( "spawn_handle" );
+
+	assert_eq!( Some(( 3, "soon" )), rt::localpool::run_until( handle ).expect( "run localpool" ).expect( "join task" ) );
+}
+
+
+
+// Run `child` with inheritance on the given executor.
+//
+#[ cfg(any( feature = "threadpool", feature = "juliex", feature = "async_std" )) ]
+//
+fn inherited_on( config: rt::Config )
+{
+	rt::init( config ).expect( "no double executor init" );
+
+	let handle = rt::spawn_handle( child( rt::Builder::new().inherit_locals( true ) ) ).expect( "spawn_handle" );
+
+	assert_eq!( Some(( 3, "soon" )), rt::block_on( handle ).expect( "join task" ) );
+}
+
+
+
+#[ cfg( feature = "threadpool" ) ]
+//
+#[test]
+//
+fn threadpool()
+{
+	inherited_on( rt::Config::ThreadPool( Default::default() ) );
+}
+
+
+
+#[ cfg( feature = "juliex" ) ]
+//
+#[test]
+//
+fn juliex()
+{
+	inherited_on( rt::Config::Juliex( Default::default() ) );
+}
+
+
+
+#[ cfg( feature = "async_std" ) ]
+//
+#[test]
+//
+fn async_std()
+{
+	inherited_on( rt::Config::AsyncStd );
+}
+
+
+
+#[ cfg( feature = "tokio_ct" ) ]
+//
+#[test]
+//
+fn tokio_ct()
+{
+	rt::init( rt::Config::TokioCt( Default::default() ) ).expect( "no double executor init" );
+
+	let handle = rt::spawn_handle( child( rt::Builder::new().inherit_locals( true ) ) ).expect( "spawn_handle" );
+
+	rt::tokio_ct::run().expect( "run tokio_ct" );
+
+	assert_eq!( Some(( 3, "soon" )), rt::block_on( handle ).expect( "join task" ) );
+}
@@ -0,0 +1,197 @@
+#![ cfg(not( target_arch = "wasm32"   )) ]
+#![ cfg(     feature     = "localpool" ) ]
+
+// Tested:
+//
+// ✔ the new thread inherits the executor configuration of the current thread
+// ✔ inside `rt::exec`, the new thread inherits the configuration of the scoped executor
+// ✔ the builder can give the thread another configuration
+// ✔ without executor, the new thread doesn't have one either
+// ✔ the builder sets the name of the thread
+// ✔ spawn_async runs the future and returns it's output when joining
+// ✔ spawn_async runs the localpool until the tasks the future spawned are finished
+// ✔ spawn_async on a threadpool
+// ✔ a panic in the future is returned when joining
+// ✔ spawn_async errors without a configuration
+// ✔ the task of spawn_async knows where it was spawned
+//
+use
+{
+	async_runtime as rt,
+
+	std     :: { rc::Rc, cell::RefCell, io, sync::{ Arc, atomic::{ AtomicBool, Ordering } } } ,
+};
+
+
+
+#[test]
+//
+fn inherit()
+{
+	rt::init( rt::Config::LocalPool ).expect( "no double executor init" );
+
+	let config = rt::thread::spawn( rt::current_rt ).join().expect( "join thread" );
+
+	assert_eq!( Some( rt::Config::LocalPool ), config );
+}
+
+
+
+#[ cfg( feature = "threadpool" ) ]
+//
+#[test]
+//
+fn inherit_scope()
+{
+	rt::init( rt::Config::LocalPool ).expect( "no double executor init" );
+
+	let config = rt::exec( rt::Config::ThreadPool( Default::default() ), |_|
+	{
+		rt::thread::spawn( rt::current_rt ).join().expect( "join thread" )
+	});
+
+	assert_eq!( Some( rt::Config::ThreadPool( Default::default() ) ), config );
+}
+
+
+
+#[ cfg( feature = "threadpool" ) ]
+//
+#[test]
+//
+fn config()
+{
+	rt::init( rt::Config::LocalPool ).expect( "no double executor init" );
+
+	let thread = rt::thread::Builder::new()
+
+		.config( rt::Config::ThreadPool( Default::default() ) )
+		.spawn( rt::current_rt )
+		.expect( "spawn thread" )
+	;
+
+	assert_eq!( Some( rt::Config::ThreadPool( Default::default() ) ), thread.join().expect( "join thread" ) );
+}
+
+
+
+#[test]
+//
+fn no_executor()
+{
+	assert_eq!( None, rt::thread::spawn( rt::current_rt ).join().expect( "join thread" ) );
+}
+
+
+
+#[test]
+//
+fn name()
+{
+	let thread = rt::thread::Builder::new()
+
+		.name( "worker".to_string() )
+		.spawn( || std::thread::current().name().map( String::from ) )
+		.expect( "spawn thread" )
+	;
+
+	assert_eq!( Some( "worker" ), thread.join().expect( "join thread" ).as_deref() );
+}
+
+
+
+#[test]
+//
+fn spawn_async()
+{
+	let thread = rt::thread::spawn_async( rt::Config::LocalPool, async
+	{
+		( rt::current_rt(), 5 )
+	});
+
+	assert_eq!( ( Some( rt::Config::LocalPool ), 5 ), thread.join().expect( "join thread" ) );
+}
+
+
+
+#[test]
+//
+fn run_tasks()
+{
+	let done  = Arc::new( AtomicBool::new( false ) );
+	let done2 = done.clone();
+
+	let thread = rt::thread::spawn_async( rt::Config::LocalPool, async move
+	{
+		let number = Rc::new( RefCell::new( 0 ) );
+
+		rt::spawn_local( async move
+		{
+			*number.borrow_mut() += 1;
+			done2.store( true, Ordering::SeqCst );
+
+		}).expect( "spawn_local" );
+	});
+
+	thread.join().expect( "join thread" );
+
+	assert!( done.load( Ordering::SeqCst ) );
+}
+
+
+
+#[ cfg( feature = "threadpool" ) ]
+//
+#[test]
+//
+fn threadpool()
+{
+	let thread = rt::thread::spawn_async( rt::Config::ThreadPool( Default::default() ), async
+	{
+		rt::spawn_handle( async { rt::current_rt() } ).expect( "spawn" ).await.expect( "join task" )
+	});
+
+	assert_eq!( Some( rt::Config::ThreadPool( Default::default() ) ), thread.join().expect( "join thread" ) );
+}
+
+
+
+#[test]
+//
+fn panic()
+{
+	let thread = rt::thread::spawn_async( rt::Config::LocalPool, async { panic!( "boom" ) } );
+
+	let payload = thread.join().unwrap_err();
+
+	assert_eq!( Some( &"boom" ), payload.downcast_ref::<&str>() );
+}
+
+
+
+#[test]
+//
+fn async_no_executor()
+{
+	let err = rt::thread::Builder::new().spawn_async( async {} ).unwrap_err();
+
+	assert_eq!( io::ErrorKind::Other, err.kind() );
+
+	let inner = err.into_inner().expect( "inner error" ).downcast::<rt::Error>().expect( "rt::Error" );
+
+	assert_eq!( &rt::ErrorKind::NoExecutorInitialized, inner.kind() );
+}
+
+
+
+#[test]
+//
+fn location()
+{
+	let thread = rt::thread::spawn_async( rt::Config::LocalPool, async
+	{
+		rt::task::current().expect( "in a task" ).location().file()
+	});
+
+	assert_eq!( file!(), thread.join().expect( "join thread" ) );
+}
@@ -13,12 +13,16 @@
 // ✔ spawn_local returns the right error
 // ✔ spawn_handle returns the right value
 // ✔ spawn_handle_local returns an error
+// ✔ worker threads use the configured name prefix
+// ✔ worker threads are named after the executor by default
+// ✔ worker threads are initialized with the full configuration
+// ✔ init_allow_same compares the full configuration
 //
 use
 {
 	async_runtime as rt,
 
-	std     :: { thread                              } ,
+	std     :: { thread, num::NonZeroUsize           } ,
 	futures :: { future::FutureExt, channel::oneshot } ,
 };
 
@@ -28,7 +32,7 @@ use
 //
 fn basic_spawn()
 {
-	rt::init( rt::Config::ThreadPool ).expect( "no double executor init" );
+	rt::init( rt::Config::ThreadPool( Default::default() ) ).expect( "no double executor init" );
 
 	let (tx, rx) = oneshot::channel();
 
@@ -54,7 +58,7 @@ fn basic_spawn()
 //
 fn spawn_boxed()
 {
-	rt::init( rt::Config::ThreadPool ).expect( "no double executor init" );
+	rt::init( rt::Config::ThreadPool( Default::default() ) ).expect( "no double executor init" );
 
 	let (tx, rx) = oneshot::channel();
 
@@ -79,7 +83,7 @@ fn spawn_boxed()
 //
 fn several()
 {
-	rt::init( rt::Config::ThreadPool ).expect( "no double executor init" );
+	rt::init( rt::Config::ThreadPool( Default::default() ) ).expect( "no double executor init" );
 
 	let (tx , rx ) = oneshot::channel();
 	let (tx2, rx2) = oneshot::channel();
@@ -109,7 +113,7 @@ fn several()
 //
 fn within()
 {
-	rt::init( rt::Config::ThreadPool ).expect( "no double executor init" );
+	rt::init( rt::Config::ThreadPool( Default::default() ) ).expect( "no double executor init" );
 
 	let (tx , rx ) = oneshot::channel();
 	let (tx2, rx2) = oneshot::channel();
@@ -122,7 +126,7 @@ fn within()
 
 		let task = async move
 		{
-			assert_eq!( rt::Config::ThreadPool, rt::current_rt().expect( "some executor" ) );
+			assert_eq!( rt::Config::ThreadPool( Default::default() ), rt::current_rt().expect( "some executor" ) );
 
 			tx2.send( 5 + rx.await.expect( "channel" ) ).expect( "send on channel" );
 		};
@@ -144,7 +148,7 @@ fn within()
 //
 fn not_running_local()
 {
-	rt::init( rt::Config::ThreadPool ).expect( "no double executor init" );
+	rt::init( rt::Config::ThreadPool( Default::default() ) ).expect( "no double executor init" );
 
 	let (tx, rx) = oneshot::channel();
 
@@ -170,7 +174,7 @@ fn not_running_local()
 //
 fn spawn_local_on_thread_pool()
 {
-	rt::init( rt::Config::ThreadPool ).expect( "no double executor init" );
+	rt::init( rt::Config::ThreadPool( Default::default() ) ).expect( "no double executor init" );
 
 	let res = rt::spawn_local( async {} );
 
@@ -188,11 +192,11 @@ fn spawn_local_on_thread_pool()
 //
 fn spawn_handle()
 {
-	rt::init( rt::Config::ThreadPool ).expect( "no double executor init" );
+	rt::init( rt::Config::ThreadPool( Default::default() ) ).expect( "no double executor init" );
 
 	let handle = rt::spawn_handle( async { "hello".to_string() } ).expect( "spawn_handle" );
 
-	rt::block_on( async { assert_eq!( "hello", &handle.await ); } );
+	rt::block_on( async { assert_eq!( "hello", &handle.await.expect( "join task" ) ); } );
 }
 
 
@@ -203,13 +207,13 @@ fn spawn_handle()
 //
 fn spawn_handle_spawn_on_self()
 {
-	rt::init( rt::Config::ThreadPool ).expect( "no double executor init" );
+	rt::init( rt::Config::ThreadPool( Default::default() ) ).expect( "no double executor init" );
 
 	let handle = rt::spawn_handle( async
 	{
 		rt::spawn( async
 		{
-			assert_eq!( rt::Config::ThreadPool, rt::current_rt().expect( "some executor" ) );
+			assert_eq!( rt::Config::ThreadPool( Default::default() ), rt::current_rt().expect( "some executor" ) );
 
 		}).expect( "spawn" );
 
@@ -217,7 +221,7 @@ fn spawn_handle_spawn_on_self()
 
 	}).expect( "spawn_handle" );
 
-	rt::block_on( async { assert_eq!( "hello", &handle.await ); } );
+	rt::block_on( async { assert_eq!( "hello", &handle.await.expect( "join task" ) ); } );
 }
 
 
@@ -228,7 +232,7 @@ fn spawn_handle_spawn_on_self()
 //
 fn spawn_handle_local()
 {
-	rt::init( rt::Config::ThreadPool ).expect( "no double executor init" );
+	rt::init( rt::Config::ThreadPool( Default::default() ) ).expect( "no double executor init" );
 
 	let handle = rt::spawn_handle_local( async {});
 
@@ -245,3 +249,61 @@ fn spawn_handle_local()
 
 
 
+// Worker threads should be named after the configured prefix and be initialized with the same config.
+//
+#[test]
+//
+fn config_name_prefix()
+{
+	let config = rt::ThreadPoolConfig::default()
+
+		.workers    ( NonZeroUsize::new( 2 ).expect( "non zero" ) )
+		.name_prefix( "config_test_pool"                          )
+		.stack_size ( 1024 * 1024                                 )
+	;
+
+	rt::init( rt::Config::ThreadPool( config.clone() ) ).expect( "no double executor init" );
+
+	let handle = rt::spawn_handle( async
+	{
+		( thread::current().name().map( String::from ), rt::current_rt() )
+
+	}).expect( "spawn_handle" );
+
+	let (name, worker_config) = rt::block_on( handle ).expect( "join task" );
+
+	assert!( name.expect( "worker has a name" ).starts_with( "config_test_pool" ) );
+	assert_eq!( Some( rt::Config::ThreadPool( config ) ), worker_config );
+}
+
+
+
+#[test]
+//
+fn config_default_name()
+{
+	rt::init( rt::Config::ThreadPool( Default::default() ) ).expect( "no double executor init" );
+
+	let handle = rt::spawn_handle( async { thread::current().name().map( String::from ) } ).expect( "spawn_handle" );
+	let name   = rt::block_on( handle ).expect( "join task" );
+
+	assert!( name.expect( "worker has a name" ).starts_with( "async_runtime_threadpool_worker" ) );
+}
+
+
+
+// init_allow_same should only allow the exact same configuration.
+//
+#[test]
+//
+fn config_allow_same()
+{
+	let config = rt::ThreadPoolConfig::default().workers( NonZeroUsize::new( 2 ).expect( "non zero" ) );
+
+	rt::init_allow_same( rt::Config::ThreadPool( config.clone() ) ).expect( "no double executor init" );
+	rt::init_allow_same( rt::Config::ThreadPool( config         ) ).expect( "same config"             );
+
+	let result = rt::init_allow_same( rt::Config::ThreadPool( rt::ThreadPoolConfig::default().workers( NonZeroUsize::new( 3 ).expect( "non zero" ) ) ) );
+
+	assert_eq!( &rt::ErrorKind::DoubleExecutorInit, result.unwrap_err().kind() );
+}
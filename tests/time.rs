@@ -0,0 +1,171 @@
+#![ cfg(not( target_arch = "wasm32" )) ]
+
+// Tested:
+//
+// ✔ sleep on localpool, threadpool, juliex, async_std and tokio_ct
+// ✔ sleep outside of any executor with block_on
+// ✔ timeout returns the output if the future finishes in time
+// ✔ timeout returns ErrorKind::Timeout if it doesn't
+// ✔ interval ticks at least one period apart
+// ✔ reset a sleep
+//
+use
+{
+	async_runtime as rt,
+
+	std     :: { time::{ Duration, Instant } } ,
+	futures :: { StreamExt, future::pending  } ,
+};
+
+
+
+// Sleep and return how long it took.
+//
+async fn measure_sleep( ms: u64 ) -> Duration
+{
+	let start = Instant::now();
+
+	rt::time::sleep( Duration::from_millis( ms ) ).await;
+
+	start.elapsed()
+}
+
+
+
+#[test]
+//
+fn block_on()
+{
+	assert!( rt::block_on( measure_sleep( 20 ) ) >= Duration::from_millis( 20 ) );
+}
+
+
+
+#[ cfg( feature = "localpool" ) ]
+//
+#[test]
+//
+fn localpool()
+{
+	rt::init( rt::Config::LocalPool ).expect( "no double executor init" );
+
+	let handle = rt::spawn_handle( measure_sleep( 20 ) ).expect( "spawn_handle" );
+
+	let elapsed = rt::localpool::run_until( handle ).expect( "run localpool" ).expect( "join task" );
+
+	assert!( elapsed >= Duration::from_millis( 20 ) );
+}
+
+
+
+#[ cfg( feature = "threadpool" ) ]
+//
+#[test]
+//
+fn threadpool()
+{
+	rt::init( rt::Config::ThreadPool( Default::default() ) ).expect( "no double executor init" );
+
+	let handle = rt::spawn_handle( measure_sleep( 20 ) ).expect( "spawn_handle" );
+
+	assert!( rt::block_on( handle ).expect( "join task" ) >= Duration::from_millis( 20 ) );
+}
+
+
+
+#[ cfg( feature = "juliex" ) ]
+//
+#[test]
+//
+fn juliex()
+{
+	rt::init( rt::Config::Juliex( Default::default() ) ).expect( "no double executor init" );
+
+	let handle = rt::spawn_handle( measure_sleep( 20 ) ).expect( "spawn_handle" );
+
+	assert!( rt::block_on( handle ).expect( "join task" ) >= Duration::from_millis( 20 ) );
+}
+
+
+
+#[ cfg( feature = "async_std" ) ]
+//
+#[test]
+//
+fn async_std()
+{
+	rt::init( rt::Config::AsyncStd ).expect( "no double executor init" );
+
+	let handle = rt::spawn_handle( measure_sleep( 20 ) ).expect( "spawn_handle" );
+
+	assert!( rt::block_on( handle ).expect( "join task" ) >= Duration::from_millis( 20 ) );
+}
+
+
+
+#[ cfg( feature = "tokio_ct" ) ]
+//
+#[test]
+//
+fn tokio_ct()
+{
+	rt::init( rt::Config::TokioCt( Default::default() ) ).expect( "no double executor init" );
+
+	let handle = rt::spawn_handle( measure_sleep( 20 ) ).expect( "spawn_handle" );
+
+	rt::tokio_ct::run().expect( "run tokio_ct" );
+
+	assert!( rt::block_on( handle ).expect( "join task" ) >= Duration::from_millis( 20 ) );
+}
+
+
+
+#[test]
+//
+fn timeout_ok()
+{
+	let result = rt::block_on( rt::time::timeout( Duration::from_secs( 5 ), async { 3 } ) );
+
+	assert_eq!( 3, result.expect( "no timeout" ) );
+}
+
+
+
+#[test]
+//
+fn timeout_expires()
+{
+	let result = rt::block_on( rt::time::timeout( Duration::from_millis( 10 ), pending::<()>() ) );
+
+	assert_eq!( &rt::ErrorKind::Timeout, result.unwrap_err().kind() );
+}
+
+
+
+#[test]
+//
+fn interval()
+{
+	let start = Instant::now();
+
+	let ticks = rt::block_on( rt::time::interval( Duration::from_millis( 10 ) ).take( 3 ).count() );
+
+	assert_eq!( 3, ticks );
+	assert!( start.elapsed() >= Duration::from_millis( 30 ) );
+}
+
+
+
+#[test]
+//
+fn reset()
+{
+	let start     = Instant::now();
+	let mut sleep = rt::time::sleep( Duration::from_secs( 60 ) );
+
+	sleep.reset( Duration::from_millis( 10 ) );
+
+	rt::block_on( sleep );
+
+	assert!( start.elapsed() < Duration::from_secs( 60 ) );
+}
@@ -13,15 +13,18 @@
 // ✔ tokio_cts on several threads
 // ✔ spawn_handle returns the right value
 // ✔ spawn_handle_local returns the right value and can spawn !Send futures
+// ✔ spawn_handle_local can return a !Send output
 // ✔ rt::tokio_ct::run should error if no executor initialized
 // ✔ rt::tokio_ct::run should error if the wrong executor is initialized
+// ✔ the configured clock is used by tokio
+// ✔ configs with a clock are only equal to their clones
 
 //
 use
 {
 	async_runtime as rt,
 
-	std     :: { rc::Rc, cell::RefCell, sync::{ Arc, Mutex }, thread } ,
+	std     :: { rc::Rc, cell::RefCell, sync::{ Arc, Mutex }, thread, time::{ Duration, Instant } } ,
 	futures :: { future::FutureExt, channel::oneshot                 } ,
 };
 
@@ -36,7 +39,7 @@ fn basic_spawn()
 	let number  = Rc::new( RefCell::new( 0 ) );
 	let num2    = number.clone();
 
-	rt::init( rt::Config::TokioCt ).expect( "no double executor init" );
+	rt::init( rt::Config::TokioCt( Default::default() ) ).expect( "no double executor init" );
 
 	let task = async move
 	{
@@ -57,7 +60,7 @@ fn spawn_boxedlocal()
 {
 	let (tx, rx) = oneshot::channel();
 
-	rt::init( rt::Config::TokioCt ).expect( "no double executor init" );
+	rt::init( rt::Config::TokioCt( Default::default() ) ).expect( "no double executor init" );
 
 
 	rt::spawn_local( async move
@@ -87,7 +90,7 @@ fn spawn_boxed()
 	let number  = Arc::new( Mutex::new( 0 ) );
 	let num2    = number.clone();
 
-	rt::init( rt::Config::TokioCt ).expect( "no double executor init" );
+	rt::init( rt::Config::TokioCt( Default::default() ) ).expect( "no double executor init" );
 
 	let task = async move
 	{
@@ -115,7 +118,7 @@ fn several()
 	let num2     = number.clone();
 	let (tx, rx) = oneshot::channel();
 
-	rt::init( rt::Config::TokioCt ).expect( "no double executor init" );
+	rt::init( rt::Config::TokioCt( Default::default() ) ).expect( "no double executor init" );
 
 	let task = async move
 	{
@@ -149,7 +152,7 @@ fn within()
 	let num2     = number.clone();
 	let (tx, rx) = oneshot::channel();
 
-	rt::init( rt::Config::TokioCt ).expect( "no double executor init" );
+	rt::init( rt::Config::TokioCt( Default::default() ) ).expect( "no double executor init" );
 
 
 	let task2 = async move
@@ -184,7 +187,7 @@ fn threads()
 	let num2     = number.clone();
 	let (tx, rx) = oneshot::channel();
 
-	rt::init( rt::Config::TokioCt ).expect( "no double executor init" );
+	rt::init( rt::Config::TokioCt( Default::default() ) ).expect( "no double executor init" );
 
 	let task = async move
 	{
@@ -193,7 +196,7 @@ fn threads()
 
 	thread::spawn( move ||
 	{
-		rt::init( rt::Config::TokioCt ).expect( "no double executor init" );
+		rt::init( rt::Config::TokioCt( Default::default() ) ).expect( "no double executor init" );
 
 		let task2 = async move
 		{
@@ -223,13 +226,13 @@ fn threads()
 //
 fn spawn_handle()
 {
-	rt::init( rt::Config::TokioCt ).expect( "no double executor init" );
+	rt::init( rt::Config::TokioCt( Default::default() ) ).expect( "no double executor init" );
 
 	let handle = rt::spawn_handle( async { "hello".to_string() } ).expect( "spawn_handle" );
 
 	rt::tokio_ct::run().expect( "run tokio_ct" );
 
-	rt::block_on( async { assert_eq!( "hello", &handle.await ); } );
+	rt::block_on( async { assert_eq!( "hello", &handle.await.expect( "join task" ) ); } );
 }
 
 
@@ -240,7 +243,7 @@ fn spawn_handle()
 //
 fn spawn_handle_local()
 {
-	rt::init( rt::Config::TokioCt ).expect( "no double executor init" );
+	rt::init( rt::Config::TokioCt( Default::default() ) ).expect( "no double executor init" );
 
 	let handle = rt::spawn_handle_local( async
 	{
@@ -256,7 +259,28 @@ fn spawn_handle_local()
 
 	rt::tokio_ct::run().expect( "run tokio_ct" );
 
-	rt::block_on( async { assert_eq!( "hello", &handle.await ); } );
+	rt::block_on( async { assert_eq!( "hello", &handle.await.expect( "join task" ) ); } );
+}
+
+
+
+// The output of a local task doesn't have to be Send.
+//
+#[test]
+//
+fn spawn_handle_local_not_send()
+{
+	rt::init( rt::Config::TokioCt( Default::default() ) ).expect( "no double executor init" );
+
+	let handle = rt::spawn_handle_local( async { Rc::new( RefCell::new( 5 ) ) } ).expect( "spawn_handle_local" );
+
+	rt::tokio_ct::run().expect( "run tokio_ct" );
+
+	let out = rt::block_on( handle ).expect( "join task" );
+
+	*out.borrow_mut() += 1;
+
+	assert_eq!( 6, *out.borrow() );
 }
 
 
@@ -274,6 +298,52 @@ fn run_without_init()
 
 
 
+// A clock that is stuck at a given instant.
+//
+struct Frozen( Instant );
+
+impl rt::tokio_ct::Now for Frozen
+{
+	fn now( &self ) -> Instant { self.0 }
+}
+
+
+// Tasks see the clock from the configuration.
+//
+#[test]
+//
+fn config_clock()
+{
+	let frozen = Instant::now() + Duration::from_secs( 3600 );
+	let clock  = rt::tokio_ct::Clock::new_with_now( Frozen( frozen ) );
+	let config = rt::TokioCtConfig::default().clock( clock );
+
+	rt::init( rt::Config::TokioCt( config.clone() ) ).expect( "no double executor init" );
+
+	let handle = rt::spawn_handle( async { tokio::clock::now() } ).expect( "spawn_handle" );
+
+	rt::tokio_ct::run().expect( "run tokio_ct" );
+
+	assert_eq!( frozen, rt::block_on( handle ).expect( "join task" ) );
+	assert_eq!( Some( rt::Config::TokioCt( config ) ), rt::current_rt() );
+}
+
+
+#[test]
+//
+fn config_clock_eq()
+{
+	let config = rt::TokioCtConfig::default().clock( rt::tokio_ct::Clock::new() );
+	let other  = rt::TokioCtConfig::default().clock( rt::tokio_ct::Clock::new() );
+
+	assert_eq!( config, config.clone()                 );
+	assert_ne!( config, other                          );
+	assert_ne!( config, rt::TokioCtConfig::default()   );
+	assert_eq!( rt::TokioCtConfig::default(), rt::TokioCtConfig::new() );
+}
+
+
+
 // This is how the spawn error can be triggered on Localpool
 //
 // #[test]
@@ -0,0 +1,159 @@
+#![ cfg(not( target_arch = "wasm32" )) ]
+#![ cfg(     feature     = "tokio_local" ) ]
+
+// Tested:
+//
+// ✔ spawn !Send futures and run them on the current thread
+// ✔ spawn from within a task while the runtime runs
+// ✔ run_until returns the output of the future and runs spawned tasks
+// ✔ tasks can use the tokio timer
+// ✔ spawn_handle returns the right value
+// ✔ an owned runtime can be passed as `T: Spawn` and installed with init_with
+// ✔ run functions error with the wrong executor
+//
+use
+{
+	async_runtime as rt,
+
+	std     :: { rc::Rc, cell::RefCell, thread, time::Duration } ,
+	futures :: { task::SpawnExt                                } ,
+};
+
+
+
+#[test]
+//
+fn spawn_local()
+{
+	rt::init( rt::Config::TokioLocal ).expect( "no double executor init" );
+
+	let number = Rc::new( RefCell::new( 0 ) );
+	let num2   = number.clone();
+
+	rt::spawn_local( async move { *num2.borrow_mut() = 2; } ).expect( "spawn_local" );
+
+	rt::tokio_local::run().expect( "run tokio local" );
+
+	assert_eq!( 2, *number.borrow() );
+}
+
+
+
+#[test]
+//
+fn within()
+{
+	rt::init( rt::Config::TokioLocal ).expect( "no double executor init" );
+
+	let number = Rc::new( RefCell::new( 0 ) );
+	let num2   = number.clone();
+
+	rt::spawn_local( async move
+	{
+		*num2.borrow_mut() = 2;
+
+		let num3 = num2.clone();
+
+		rt::spawn_local( async move { *num3.borrow_mut() += 1; } ).expect( "spawn from within" );
+
+	}).expect( "spawn_local" );
+
+	rt::tokio_local::run().expect( "run tokio local" );
+
+	assert_eq!( 3, *number.borrow() );
+}
+
+
+
+#[test]
+//
+fn run_until()
+{
+	rt::init( rt::Config::TokioLocal ).expect( "no double executor init" );
+
+	let out = rt::tokio_local::run_until( async
+	{
+		let handle = rt::spawn_handle_local( async { thread::current().id() } ).expect( "spawn_handle_local" );
+
+		handle.await.expect( "join task" )
+
+	}).expect( "run tokio local" );
+
+	assert_eq!( thread::current().id(), out );
+}
+
+
+
+#[test]
+//
+fn tokio_timer()
+{
+	rt::init( rt::Config::TokioLocal ).expect( "no double executor init" );
+
+	let handle = rt::spawn_handle_local( async
+	{
+		tokio1::time::sleep( Duration::from_millis( 10 ) ).await;
+		5
+
+	}).expect( "spawn_handle_local" );
+
+	assert_eq!( 5, rt::tokio_local::run_until( handle ).expect( "run tokio local" ).expect( "join task" ) );
+}
+
+
+
+#[test]
+//
+fn spawn_handle()
+{
+	rt::init( rt::Config::TokioLocal ).expect( "no double executor init" );
+
+	let handle = rt::spawn_handle( async { "hello".to_string() } ).expect( "spawn_handle" );
+
+	rt::tokio_local::run().expect( "run tokio local" );
+
+	assert_eq!( "hello", rt::block_on( handle ).expect( "join task" ) );
+}
+
+
+
+#[test]
+//
+fn owned()
+{
+	let exec = rt::TokioLocal::new();
+
+	SpawnExt::spawn( &exec, async { rt::spawn( async {} ).expect( "spawn from within" ); } ).expect( "spawn" );
+
+	rt::init_with( exec.clone() ).expect( "no double executor init" );
+
+	assert_eq!( Some( rt::Config::TokioLocal ), rt::current_rt() );
+
+	exec.run();
+}
+
+
+
+#[test]
+//
+fn wrong_executor()
+{
+	assert_eq!( &rt::ErrorKind::NoExecutorInitialized, rt::tokio_local::run().unwrap_err().kind() );
+
+	rt::init_with( rt::CustomBackend::new( Nothing ) ).expect( "no double executor init" );
+
+	assert_eq!( &rt::ErrorKind::WrongExecutor, rt::tokio_local::run_until( async {} ).unwrap_err().kind() );
+}
+
+
+
+#[ derive( Debug ) ]
+//
+struct Nothing;
+
+impl rt::Backend for Nothing
+{
+	fn capabilities( &self ) -> rt::Capabilities { rt::Capabilities::default() }
+
+	fn spawn( &self, _: futures::task::FutureObj<'static, ()> ) -> Result< (), rt::Error > { Ok(()) }
+}
@@ -0,0 +1,209 @@
+#![ cfg(not( target_arch = "wasm32" )) ]
+#![ cfg(     feature     = "tokio_tp"  ) ]
+
+// Tested:
+//
+// ✔ basic spawning
+// ✔ it's actually running on a threadpool
+// ✔ worker thread spawns on same runtime
+// ✔ tasks can use the tokio timer
+// ✔ spawn_local and spawn_handle_local return the right error
+// ✔ spawn_handle returns the right value
+// ✔ spawn_handle on an owned runtime returns tokio's JoinHandle
+// ✔ worker threads use the configured name prefix and are initialized with the full configuration
+// ✔ shutdown waits for tasks in flight
+// ✔ dropping the last clone of an owned runtime stops it's worker threads
+//
+use
+{
+	async_runtime as rt,
+
+	std     :: { thread, time::Duration, cell::RefCell, sync::mpsc, num::NonZeroUsize } ,
+	futures :: { channel::oneshot                                                     } ,
+};
+
+
+
+#[test]
+//
+fn basic_spawn()
+{
+	rt::init( rt::Config::TokioTp( Default::default() ) ).expect( "no double executor init" );
+
+	let (tx, rx) = oneshot::channel();
+
+	rt::spawn( async move { tx.send( thread::current().id() ).expect( "send on channel" ); } ).expect( "Spawn task" );
+
+	rt::block_on( async move
+	{
+		assert_ne!( thread::current().id(), rx.await.expect( "wait on channel" ) );
+	});
+}
+
+
+
+// Verify that nested calls to spawn spawn on the right executor
+//
+#[test]
+//
+fn within()
+{
+	rt::init( rt::Config::TokioTp( Default::default() ) ).expect( "no double executor init" );
+
+	let handle = rt::spawn_handle( async
+	{
+		rt::spawn_handle( async { rt::current_rt() } ).expect( "spawn_handle" ).await.expect( "join task" )
+
+	}).expect( "spawn_handle" );
+
+	assert_eq!( Some( rt::Config::TokioTp( Default::default() ) ), rt::block_on( handle ).expect( "join task" ) );
+}
+
+
+
+// The tasks run inside the tokio runtime.
+//
+#[test]
+//
+fn tokio_timer()
+{
+	rt::init( rt::Config::TokioTp( Default::default() ) ).expect( "no double executor init" );
+
+	let handle = rt::spawn_handle( async
+	{
+		tokio1::time::sleep( Duration::from_millis( 10 ) ).await;
+		5
+
+	}).expect( "spawn_handle" );
+
+	assert_eq!( 5, rt::block_on( handle ).expect( "join task" ) );
+}
+
+
+
+#[test]
+//
+fn spawn_local()
+{
+	rt::init( rt::Config::TokioTp( Default::default() ) ).expect( "no double executor init" );
+
+	let res    = rt::spawn_local       ( async {}   );
+	let handle = rt::spawn_handle_local( async { 1 } );
+
+	assert_eq!( &rt::ErrorKind::SpawnLocalOnThreadPool, res   .unwrap_err().kind() );
+	assert_eq!( &rt::ErrorKind::SpawnLocalOnThreadPool, handle.unwrap_err().kind() );
+}
+
+
+
+#[test]
+//
+fn spawn_handle()
+{
+	rt::init( rt::Config::TokioTp( Default::default() ) ).expect( "no double executor init" );
+
+	let handle = rt::spawn_handle( async { "hello".to_string() } ).expect( "spawn_handle" );
+
+	assert_eq!( "hello", rt::block_on( handle ).expect( "join task" ) );
+}
+
+
+
+// The owned runtime returns tokio's own JoinHandle.
+//
+#[test]
+//
+fn owned_spawn_handle()
+{
+	let exec = rt::TokioTp::new( Default::default() );
+
+	let handle: tokio1::task::JoinHandle<u8> = exec.spawn_handle( async { 5 } ).expect( "spawn_handle" );
+
+	assert_eq!( 5, rt::block_on( handle ).expect( "join task" ) );
+
+	let handle = exec.spawn_handle( async { panic!( "boom" ) } ).expect( "spawn_handle" );
+
+	assert!( rt::block_on( handle ).unwrap_err().is_panic() );
+}
+
+
+
+// Worker threads should be named after the configured prefix and be initialized with the same config.
+//
+#[test]
+//
+fn config_name_prefix()
+{
+	let config = rt::TokioTpConfig::default()
+
+		.workers    ( NonZeroUsize::new( 2 ).expect( "non zero" ) )
+		.name_prefix( "config_test_tokio"                         )
+		.stack_size ( 1024 * 1024                                 )
+	;
+
+	rt::init( rt::Config::TokioTp( config.clone() ) ).expect( "no double executor init" );
+
+	let handle = rt::spawn_handle( async
+	{
+		( thread::current().name().map( String::from ), rt::current_rt() )
+
+	}).expect( "spawn_handle" );
+
+	let (name, worker_config) = rt::block_on( handle ).expect( "join task" );
+
+	assert!( name.expect( "worker has a name" ).starts_with( "config_test_tokio" ) );
+	assert_eq!( Some( rt::Config::TokioTp( config ) ), worker_config );
+}
+
+
+
+#[test]
+//
+fn shutdown()
+{
+	// Use a separate runtime, so we don't shut down the one shared by the other tests.
+	//
+	rt::init_with( rt::TokioTp::new( Default::default() ) ).expect( "no double executor init" );
+
+	let (tx, rx) = std::sync::mpsc::channel();
+
+	rt::spawn( async move
+	{
+		thread::sleep( Duration::from_millis( 50 ) );
+		tx.send(()).expect( "send" );
+
+	}).expect( "spawn" );
+
+	assert!( rt::shutdown( Duration::from_secs( 5 ) ).expect( "shutdown" ) );
+	assert!( rx.try_recv().is_ok() );
+
+	assert_eq!( &rt::ErrorKind::ExecutorShutdown, rt::spawn( async {} ).unwrap_err().kind() );
+}
+
+
+
+// A sender that is dropped when the thread it's stored on exits, so the receiver disconnects.
+//
+thread_local!
+{
+	static EXIT: RefCell< Option< mpsc::Sender<()> > > = const { RefCell::new( None ) };
+}
+
+
+// The worker threads have a clone of the runtime installed as their executor, but that must not keep it alive.
+//
+#[test]
+//
+fn drop_stops_workers()
+{
+	let exec     = rt::TokioTp::new( rt::TokioTpConfig::default().workers( NonZeroUsize::MIN ) );
+	let (tx, rx) = mpsc::channel();
+
+	let handle = exec.spawn_handle( async move { EXIT.with( |exit| *exit.borrow_mut() = Some( tx ) ); } ).expect( "spawn_handle" );
+
+	rt::block_on( handle ).expect( "join task" );
+
+	drop( exec );
+
+	assert_eq!( Err( mpsc::RecvTimeoutError::Disconnected ), rx.recv_timeout( Duration::from_secs( 10 ) ) );
+}
@@ -0,0 +1,269 @@
+#![ cfg(not( target_arch = "wasm32" )) ]
+#![ cfg(all( feature = "tracing", feature = "localpool" )) ]
+
+// Tested:
+//
+// ✔ a spawned task gets a span which is a child of the span at the spawn site
+// ✔ the span carries the executor and a task id
+// ✔ poll and completion events are recorded in the task span
+// ✔ each task gets a different id
+// ✔ spawn_local and spawn_handle are instrumented too
+// ✔ the same on tokio_ct
+// ✔ on threadpool, events from a worker thread are in the task span, which has the executor, id, name and location
+//
+use
+{
+	async_runtime as rt,
+
+	std           :: { fmt, cell::RefCell, thread::{ self, ThreadId }                          } ,
+	std           :: { sync::{ Arc, Mutex, atomic::{ AtomicU64, Ordering } }                   } ,
+	tracing_crate :: { info_span, Subscriber, Event, Metadata, span, field::{ Field, Visit }     } ,
+};
+
+
+
+#[ derive( Debug, Clone ) ]
+//
+struct SpanRecord
+{
+	id     : u64                       ,
+	name   : &'static str              ,
+	parent : Option<u64>               ,
+	fields : Vec<( &'static str, String )> ,
+}
+
+
+#[ derive( Debug, Clone ) ]
+//
+struct EventRecord
+{
+	span   : Option<u64> ,
+	message: String      ,
+	thread : ThreadId    ,
+}
+
+
+#[ derive( Default ) ]
+//
+struct Data
+{
+	next  : AtomicU64                ,
+	spans : Mutex< Vec<SpanRecord>  > ,
+	events: Mutex< Vec<EventRecord> > ,
+}
+
+
+// A subscriber that remembers spans and events. The stack of entered spans is kept per thread.
+//
+#[ derive( Clone, Default ) ]
+//
+struct Recorder
+{
+	data: Arc<Data>,
+}
+
+
+std::thread_local!
+(
+	static STACK: RefCell< Vec<u64> > = const { RefCell::new( Vec::new() ) };
+);
+
+
+struct Fields( Vec<( &'static str, String )> );
+
+impl Visit for Fields
+{
+	fn record_debug( &mut self, field: &Field, value: &dyn fmt::Debug )
+	{
+		self.0.push( ( field.name(), format!( "{:?}", value ) ) );
+	}
+}
+
+
+impl Subscriber for Recorder
+{
+	fn enabled( &self, _: &Metadata<'_> ) -> bool { true }
+
+	fn new_span( &self, attrs: &span::Attributes<'_> ) -> span::Id
+	{
+		let id = self.data.next.fetch_add( 1, Ordering::SeqCst ) + 1;
+
+		let parent = if attrs.is_contextual() { STACK.with( |s| s.borrow().last().copied() ) }
+		             else                     { attrs.parent().map( |p| p.into_u64() )          };
+
+		let mut fields = Fields( Vec::new() );
+		attrs.record( &mut fields );
+
+		self.data.spans.lock().unwrap().push( SpanRecord { id, name: attrs.metadata().name(), parent, fields: fields.0 } );
+
+		span::Id::from_u64( id )
+	}
+
+	fn record            ( &self, _: &span::Id, _: &span::Record<'_> ) {}
+	fn record_follows_from( &self, _: &span::Id, _: &span::Id         ) {}
+
+	fn event( &self, event: &Event<'_> )
+	{
+		let mut fields = Fields( Vec::new() );
+		event.record( &mut fields );
+
+		let message = fields.0.into_iter().find( |(name, _)| *name == "message" ).map( |(_, v)| v ).unwrap_or_default();
+		let span    = STACK.with( |s| s.borrow().last().copied() );
+
+		self.data.events.lock().unwrap().push( EventRecord { span, message, thread: thread::current().id() } );
+	}
+
+	fn enter( &self, id: &span::Id ) { STACK.with( |s| s.borrow_mut().push( id.into_u64() ) ); }
+	fn exit ( &self, _ : &span::Id ) { STACK.with( |s| s.borrow_mut().pop()                 ); }
+}
+
+
+
+impl Recorder
+{
+	fn tasks( &self ) -> Vec<SpanRecord>
+	{
+		self.data.spans.lock().unwrap().iter().filter( |s| s.name == "task" ).cloned().collect()
+	}
+
+
+	fn field<'a>( span: &'a SpanRecord, name: &str ) -> &'a str
+	{
+		&span.fields.iter().find( |(n, _)| *n == name ).expect( "field" ).1
+	}
+
+
+	fn events_in( &self, span: u64 ) -> Vec<String>
+	{
+		self.data.events.lock().unwrap().iter().filter( |e| e.span == Some( span ) ).map( |e| e.message.clone() ).collect()
+	}
+}
+
+
+
+#[test]
+//
+fn localpool()
+{
+	let recorder = Recorder::default();
+
+	tracing_crate::subscriber::with_default( recorder.clone(), ||
+	{
+		rt::init( rt::Config::LocalPool ).expect( "no double executor init" );
+
+		let request = info_span!( "request" );
+
+		request.in_scope( ||
+		{
+			rt::spawn      ( async {} ).expect( "spawn"       );
+			rt::spawn_local( async {} ).expect( "spawn_local" );
+		});
+
+		let handle = rt::spawn_handle( async { 3 } ).expect( "spawn_handle" );
+
+		assert_eq!( 3, rt::localpool::run_until( handle ).expect( "run localpool" ).expect( "join task" ) );
+		rt::localpool::run().expect( "run localpool" );
+
+		let request_id = recorder.data.spans.lock().unwrap().iter().find( |s| s.name == "request" ).expect( "request span" ).id;
+		let tasks      = recorder.tasks();
+
+		assert_eq!( 3, tasks.len() );
+
+		// The first two were spawned inside the request span, the last one outside.
+		//
+		assert_eq!( Some( request_id ), tasks[0].parent );
+		assert_eq!( Some( request_id ), tasks[1].parent );
+		assert_eq!( None              , tasks[2].parent );
+
+		for task in &tasks
+		{
+			assert_eq!( "\"LocalPool\"", Recorder::field( task, "executor" ) );
+
+			let events = recorder.events_in( task.id );
+
+			assert_eq!( "poll"     , events.first().expect( "poll event" ) );
+			assert_eq!( "completed", events.last ().expect( "completion" ) );
+		}
+
+		assert_ne!( Recorder::field( &tasks[0], "task.id" ), Recorder::field( &tasks[1], "task.id" ) );
+	});
+}
+
+
+
+#[ cfg( feature = "tokio_ct" ) ]
+//
+#[test]
+//
+fn tokio_ct()
+{
+	let recorder = Recorder::default();
+
+	tracing_crate::subscriber::with_default( recorder.clone(), ||
+	{
+		rt::init( rt::Config::TokioCt( Default::default() ) ).expect( "no double executor init" );
+
+		info_span!( "request" ).in_scope( ||
+		{
+			rt::spawn_local( async {} ).expect( "spawn_local" );
+		});
+
+		rt::tokio_ct::run().expect( "run tokio_ct" );
+
+		let tasks = recorder.tasks();
+
+		assert_eq!( 1, tasks.len() );
+		assert!( tasks[0].parent.is_some() );
+		assert_eq!( "\"TokioCt\"", Recorder::field( &tasks[0], "executor" ) );
+		assert_eq!( vec![ "poll", "completed" ], recorder.events_in( tasks[0].id ) );
+	});
+}
+
+
+
+// The span is created on the thread that spawns and entered on a worker thread. The worker threads don't see
+// a subscriber set with `with_default`, so this is the only test that sets the global one.
+//
+#[ cfg( feature = "threadpool" ) ]
+//
+#[test]
+//
+fn threadpool()
+{
+	let recorder = Recorder::default();
+
+	tracing_crate::subscriber::set_global_default( recorder.clone() ).expect( "set global subscriber" );
+
+	rt::init( rt::Config::ThreadPool( Default::default() ) ).expect( "no double executor init" );
+
+	let handle = info_span!( "request" ).in_scope( ||
+	{
+		rt::Builder::new().name( "pooled" ).spawn_handle( async
+		{
+			tracing_crate::info!( "on worker" );
+			thread::current().id()
+
+		}).expect( "spawn_handle" )
+	});
+
+	let worker = rt::block_on( handle ).expect( "join task" );
+
+	assert_ne!( thread::current().id(), worker );
+
+	let tasks = recorder.tasks();
+
+	assert_eq!( 1, tasks.len() );
+	assert!( tasks[0].parent.is_some() );
+
+	assert_eq!( "\"ThreadPool\"", Recorder::field( &tasks[0], "executor"  ) );
+	assert_eq!( "\"pooled\""    , Recorder::field( &tasks[0], "task.name" ) );
+
+	assert!( Recorder::field( &tasks[0], "task.id"       ).parse::<u64>().is_ok() );
+	assert!( Recorder::field( &tasks[0], "task.location" ).starts_with( "tests/tracing.rs:" ) );
+
+	let events = recorder.data.events.lock().unwrap().clone();
+	let event  = events.iter().find( |e| e.message == "on worker" ).expect( "event from the task" );
+
+	assert_eq!( Some( tasks[0].id ), event.span   );
+	assert_eq!( worker             , event.thread );
+}
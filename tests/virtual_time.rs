@@ -0,0 +1,208 @@
+#![ cfg(not( target_arch = "wasm32" )) ]
+
+// Tested:
+//
+// ✔ advance and elapsed fail if the clock is not paused
+// ✔ advance wakes up the timers that expire
+// ✔ the localpool advances the clock in run and run_until
+// ✔ timeout and interval on the virtual clock
+// ✔ reset a sleep on the virtual clock
+// ✔ the deterministic executor advances the clock and fires timers in order
+// ✔ a long sleep doesn't take real time
+// ✔ timers and advance don't overflow with Duration::MAX
+//
+use
+{
+	async_runtime as rt,
+
+	std     :: { time::Duration } ,
+	futures :: { FutureExt      } ,
+};
+
+
+
+const HOUR: Duration = Duration::from_secs( 3600 );
+
+
+
+#[test]
+//
+fn not_paused()
+{
+	assert_eq!( &rt::ErrorKind::ClockNotPaused, rt::time::advance( HOUR ).unwrap_err().kind() );
+	assert_eq!( &rt::ErrorKind::ClockNotPaused, rt::time::elapsed(     ).unwrap_err().kind() );
+}
+
+
+
+#[test]
+//
+fn advance()
+{
+	rt::time::pause();
+
+	let mut sleep = rt::time::sleep( Duration::from_secs( 10 ) );
+	let     waker = futures::task::noop_waker();
+	let mut cx    = std::task::Context::from_waker( &waker );
+
+	assert!( sleep.poll_unpin( &mut cx ).is_pending() );
+
+	rt::time::advance( Duration::from_secs( 9 ) ).expect( "advance" );
+	assert!( sleep.poll_unpin( &mut cx ).is_pending() );
+
+	rt::time::advance( Duration::from_secs( 1 ) ).expect( "advance" );
+	assert!( sleep.poll_unpin( &mut cx ).is_ready() );
+
+	assert_eq!( Duration::from_secs( 10 ), rt::time::elapsed().expect( "elapsed" ) );
+}
+
+
+
+// A deadline beyond what the clock can count never fires, and the clock stops at `Duration::MAX`.
+//
+#[test]
+//
+fn duration_max()
+{
+	rt::time::pause();
+	rt::time::advance( HOUR ).expect( "advance" );
+
+	let mut sleep   = rt::time::sleep( Duration::MAX );
+	let mut timeout = rt::time::timeout( Duration::MAX, futures::future::pending::<()>() ).boxed();
+	let     waker   = futures::task::noop_waker();
+	let mut cx      = std::task::Context::from_waker( &waker );
+
+	assert!( sleep  .poll_unpin( &mut cx ).is_pending() );
+	assert!( timeout.poll_unpin( &mut cx ).is_pending() );
+
+	rt::time::advance( Duration::MAX ).expect( "advance" );
+	rt::time::advance( Duration::MAX ).expect( "advance" );
+
+	assert!( sleep  .poll_unpin( &mut cx ).is_pending() );
+	assert!( timeout.poll_unpin( &mut cx ).is_pending() );
+
+	sleep.reset( Duration::MAX );
+	assert!( sleep.poll_unpin( &mut cx ).is_pending() );
+
+	assert_eq!( Duration::MAX, rt::time::elapsed().expect( "elapsed" ) );
+}
+
+
+
+#[ cfg( feature = "localpool" ) ]
+//
+#[test]
+//
+fn localpool()
+{
+	rt::init( rt::Config::LocalPool ).expect( "no double executor init" );
+	rt::time::pause();
+
+	let start = std::time::Instant::now();
+
+	rt::spawn( async { rt::time::sleep( 2 * HOUR ).await; } ).expect( "spawn" );
+	rt::localpool::run().expect( "run localpool" );
+
+	assert_eq!( 2 * HOUR, rt::time::elapsed().expect( "elapsed" ) );
+
+	let handle = rt::spawn_handle( async
+	{
+		rt::time::sleep( HOUR ).await;
+		rt::time::elapsed().expect( "elapsed" )
+
+	}).expect( "spawn_handle" );
+
+	let slept = rt::localpool::run_until( handle ).expect( "run localpool" ).expect( "join task" );
+
+	assert_eq!( 3 * HOUR, slept );
+	assert!( start.elapsed() < Duration::from_secs( 5 ) );
+}
+
+
+
+#[ cfg( feature = "localpool" ) ]
+//
+#[test]
+//
+fn timeout_interval()
+{
+	use futures::{ StreamExt, future::pending };
+
+	rt::init( rt::Config::LocalPool ).expect( "no double executor init" );
+	rt::time::pause();
+
+	let program = async
+	{
+		let result = rt::time::timeout( HOUR, pending::<()>() ).await;
+		assert_eq!( &rt::ErrorKind::Timeout, result.unwrap_err().kind() );
+
+		let ticks: Vec<()> = rt::time::interval( HOUR ).take( 3 ).collect().await;
+		assert_eq!( 3, ticks.len() );
+
+		let out = rt::time::timeout( HOUR, async { rt::time::sleep( HOUR / 2 ).await; 5 } ).await;
+		assert_eq!( 5, out.expect( "no timeout" ) );
+	};
+
+	rt::localpool::run_until( program ).expect( "run localpool" );
+
+	assert_eq!( 4 * HOUR + HOUR / 2, rt::time::elapsed().expect( "elapsed" ) );
+}
+
+
+
+#[ cfg( feature = "localpool" ) ]
+//
+#[test]
+//
+fn reset()
+{
+	rt::init( rt::Config::LocalPool ).expect( "no double executor init" );
+	rt::time::pause();
+
+	let program = async
+	{
+		let mut sleep = rt::time::sleep( 2 * HOUR );
+
+		sleep.reset( HOUR );
+		sleep.await;
+	};
+
+	rt::localpool::run_until( program ).expect( "run localpool" );
+
+	assert_eq!( HOUR, rt::time::elapsed().expect( "elapsed" ) );
+}
+
+
+
+#[ cfg( feature = "deterministic" ) ]
+//
+#[test]
+//
+fn deterministic()
+{
+	use futures::StreamExt;
+
+	rt::init( rt::Config::Deterministic{ seed: 3 } ).expect( "no double executor init" );
+	rt::time::pause();
+
+	let (tx, rx) = futures::channel::mpsc::unbounded();
+
+	for id in ( 1..=5 ).rev()
+	{
+		let tx = tx.clone();
+
+		rt::spawn( async move
+		{
+			rt::time::sleep( id * HOUR ).await;
+			tx.unbounded_send( id ).expect( "send" );
+
+		}).expect( "spawn" );
+	}
+
+	drop( tx );
+
+	let order: Vec<u32> = rt::deterministic::run_until( rx.collect() ).expect( "run" );
+
+	assert_eq!( vec![ 1, 2, 3, 4, 5 ], order );
+	assert_eq!( 5 * HOUR, rt::time::elapsed().expect( "elapsed" ) );
+}